@@ -0,0 +1,19 @@
+//! With the `header` feature, regenerates `include/wordle.h` from the C interface in
+//! `src/ffi.rs`, so that the header cannot drift from the functions it declares.
+
+fn main() {
+    #[cfg(feature = "header")]
+    {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+            .expect("cbindgen.toml is malformed");
+        cbindgen::Builder::new()
+            .with_src(format!("{}/src/ffi.rs", crate_dir))
+            .with_config(config)
+            .generate()
+            .expect("could not generate the C header")
+            .write_to_file(format!("{}/include/wordle.h", crate_dir));
+    }
+}
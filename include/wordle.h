@@ -0,0 +1,100 @@
+/* The C interface of wordl-rust-bot, implemented in src/ffi.rs.
+ *
+ * Build the library with `cargo build --release` and link against
+ * target/release/libwordl_rust_bot.a or the shared library next to it.
+ * All strings are NUL-terminated UTF-8. */
+
+#ifndef WORDLE_H
+#define WORDLE_H
+
+/* Generated by cbindgen from src/ffi.rs with `cargo build --features header`, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The number of letters of a word, [`WORD_LENGTH`] for the C interface.
+ */
+#define WORDLE_WORD_LENGTH 5
+
+/**
+ * A game of the C interface, declared in `include/wordle.h`, for embedding the solver in C,
+ * C++ or Swift apps. Build the crate as a static or dynamic library and link against it.
+ * Every function takes NUL-terminated UTF-8 strings and reports invalid input with a negative
+ * return value or `NULL`.
+ *
+ * A game owns its word lists, which the solver borrows for as long as the game lives.
+ *
+ * # Example
+ *
+ * ```c
+ * #include "wordle.h"
+ *
+ * Wordle *wordle = wordle_new("tears\nbears\ngears\ncrane\nslimy\n", NULL);
+ * char guess[WORDLE_WORD_LENGTH + 1];
+ * wordle_best_guess(wordle, guess);
+ * wordle_feedback(wordle, "crane", "bygby");
+ * wordle_free(wordle);
+ * ```
+ */
+typedef struct Wordle Wordle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Starts a game where the guesses are the words of `words` and the solutions are the words
+ * of `solutions`, or `words` again if `solutions` is `NULL`. Both lists have one word per line.
+ *
+ * # Returns
+ * The game, to be released with [`wordle_free`], or `NULL` if a list is missing, empty or
+ * contains a line that is not a word.
+ *
+ * # Safety
+ * `words` and `solutions`, unless `NULL`, must be valid NUL-terminated strings.
+ */
+struct Wordle *wordle_new(const char *words, const char *solutions);
+
+/**
+ * Enters the feedback `pattern`, e.g. `"bygby"`, that the game gave for `guess`.
+ *
+ * # Returns
+ * The number of solutions that are still possible, or -1 if `guess` is not a word or
+ * `pattern` is not a pattern.
+ *
+ * # Safety
+ * `wordle` must come from [`wordle_new`], and `guess` and `pattern` must be valid
+ * NUL-terminated strings.
+ */
+int wordle_feedback(struct Wordle *wordle, const char *guess, const char *pattern);
+
+/**
+ * Writes the guess with the highest entropy, or the solution once it is known, to `out` as
+ * a NUL-terminated string.
+ *
+ * # Returns
+ * 0, or -1 if no word fits the feedback so far or the guess has letters outside ASCII, which
+ * take more than one byte each. In both cases `out` is left unchanged.
+ *
+ * # Safety
+ * `wordle` must come from [`wordle_new`], and `out` must have room for [`WORDLE_WORD_LENGTH`] + 1 bytes.
+ */
+int wordle_best_guess(const struct Wordle *wordle,
+                      char *out);
+
+/**
+ * Releases a game created by [`wordle_new`]. Does nothing for `NULL`.
+ *
+ * # Safety
+ * `wordle` must come from [`wordle_new`] and must not be used afterwards.
+ */
+void wordle_free(struct Wordle *wordle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WORDLE_H */
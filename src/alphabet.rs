@@ -0,0 +1,82 @@
+use std::fmt::{Display, Formatter};
+use crate::word::Word;
+
+/// The letters that occur in a word list, for counting letters without assuming the
+/// alphabet is `a` to `z`.
+///
+/// Each letter has an index from 0 to `len() - 1`, in the order of the code points, so that
+/// per-letter counts can be kept in a plain array instead of a map.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::alphabet::Alphabet;
+/// use wordl_rust_bot::Word;
+///
+/// let alphabet = Alphabet::of(&["äpfel", "hallo"].map(Word::from_str));
+/// assert_eq!(alphabet.to_string(), "aefhlopä");
+/// assert_eq!(alphabet.index('ä'), Some(7));
+/// assert_eq!(alphabet.index('z'), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Alphabet {
+    letters: Vec<char>,
+}
+
+impl Alphabet {
+    /// The letters occurring in any of `words`.
+    pub fn of<'a, I: IntoIterator<Item = &'a Word>>(words: I) -> Alphabet {
+        let mut letters = words.into_iter().flat_map(|word| word.letters()).collect::<Vec<char>>();
+        letters.sort_unstable();
+        letters.dedup();
+        Alphabet { letters }
+    }
+
+    /// The index of `letter`, or `None` if it does not occur in the word list.
+    pub fn index(&self, letter: char) -> Option<usize> {
+        self.letters.binary_search(&letter).ok()
+    }
+
+    pub fn contains(&self, letter: char) -> bool {
+        self.index(letter).is_some()
+    }
+
+    /// The first letter of `word` that is not in the alphabet, if any.
+    pub fn missing(&self, word: &Word) -> Option<char> {
+        word.letters().into_iter().find(|letter| !self.contains(*letter))
+    }
+
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+}
+
+impl Display for Alphabet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.letters.iter().try_for_each(|letter| write!(f, "{}", letter))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alphabet() {
+        let alphabet = Alphabet::of(&["жираф", "слово", "crane"].map(Word::from_str));
+        assert_eq!(alphabet.len(), 14);
+        assert_eq!(alphabet.index('a'), Some(0));
+        assert!(alphabet.contains('ж'));
+        assert_eq!(alphabet.missing(&Word::from_str("щвета")), Some('щ'));
+        assert_eq!(alphabet.missing(&Word::from_str("слово")), None);
+        assert!(Alphabet::default().is_empty());
+    }
+}
@@ -0,0 +1,668 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use crate::game::{SimulatedGame, Solver, Strategy};
+use crate::matrix::PatternMatrix;
+use crate::style;
+use crate::word::{Word, WORD_LENGTH};
+use crate::wordlist::{Sharpening, WeightedList};
+
+/// The outcome of one simulated game.
+///
+/// # Fields
+/// * `solution` - The word the game was played against.
+/// * `guesses` - The guesses, in order. The last one is the solution if the game was solved.
+/// * `solved` - Whether the solution was found within the round limit.
+/// * `space_sizes` - The number of remaining candidates before each guess.
+#[derive(Clone, Debug)]
+pub struct GameRecord {
+    pub solution: Word,
+    pub guesses: Vec<Word>,
+    pub solved: bool,
+    pub space_sizes: Vec<usize>,
+}
+
+impl GameRecord {
+    /// The number of guesses made.
+    pub fn rounds(&self) -> usize {
+        self.guesses.len()
+    }
+}
+
+/// Plays a game against every word of `solutions` in parallel, starting with `first_guess`
+/// and picking the following guesses with `strategy`. The games print nothing.
+///
+/// # Arguments
+/// * `matrix` - The feedback of every word for every word of `words`, shared by all games.
+/// * `seed` - The seed of the run, from which every game seeds its random choices, see
+///   [`SimulatedGame::with_seed`].
+pub fn simulate(matrix: &Arc<PatternMatrix>, words: &[Word], solutions: &[Word], first_guess: Word,
+                strategy: Strategy, seed: Option<u64>) -> Vec<GameRecord> {
+    solutions.par_iter().enumerate().map(|(i, solution)| {
+        let mut game = SimulatedGame::new(words, *solution, first_guess)
+            .with_matrix(Arc::clone(matrix))
+            .with_strategy(strategy)
+            .quiet();
+        if let Some(seed) = seed {
+            game = game.with_seed(seed, i);
+        }
+        game.run_game();
+        game.record()
+    }).collect()
+}
+
+/// How well the solver does with a fixed first guess, over a whole list of solutions.
+///
+/// # Fields
+/// * `opener` - The first guess.
+/// * `average` - The average number of guesses over all games, failures included.
+/// * `worst` - The largest number of guesses of a solved game.
+/// * `failures` - The number of games that were not solved within the round limit.
+#[derive(Clone, Debug)]
+pub struct OpenerResult {
+    pub opener: Word,
+    pub average: f64,
+    pub worst: usize,
+    pub failures: usize,
+}
+
+impl OpenerResult {
+    pub fn new(opener: Word, records: &[GameRecord]) -> OpenerResult {
+        let rounds = records.iter().map(GameRecord::rounds).sum::<usize>();
+        OpenerResult {
+            opener,
+            average: rounds as f64 / records.len().max(1) as f64,
+            worst: records.iter().filter(|r| r.solved).map(GameRecord::rounds).max().unwrap_or(0),
+            failures: records.iter().filter(|r| !r.solved).count(),
+        }
+    }
+}
+
+/// Compares first guesses by simulating every solution with each of them.
+///
+/// # Arguments
+/// * `words` - The list of allowed guesses.
+/// * `solutions` - The solutions to simulate for every opener.
+/// * `openers` - The first guesses to compare. If empty, the `top` guesses with the highest
+///   entropy over `solutions` are used.
+/// * `top` - The number of openers to pick if `openers` is empty.
+/// * `progress` - Called with the result of every opener as soon as it is known.
+///
+/// # Returns
+/// The results, best first: fewest failures, then lowest average.
+pub fn tournament<F>(words: &[Word], solutions: &[Word], openers: &[Word], top: usize,
+                     mut progress: F) -> Vec<OpenerResult> where F: FnMut(&OpenerResult) {
+    let openers = if openers.is_empty() {
+        Solver::with_solutions(words, solutions).suggestions(top).iter().map(|e| *e.word).collect()
+    } else {
+        openers.to_vec()
+    };
+    let matrix = Arc::new(PatternMatrix::new(words, words));
+    let mut results = openers.into_iter().map(|opener| {
+        let result = OpenerResult::new(opener, &simulate(&matrix, words, solutions, opener, Strategy::Entropy, None));
+        progress(&result);
+        result
+    }).collect::<Vec<_>>();
+    results.sort_by(|a, b| a.failures.cmp(&b.failures).then(f64::total_cmp(&a.average, &b.average)));
+    results
+}
+
+/// How well a strategy does over a whole list of solutions, see [`compare`].
+///
+/// # Fields
+/// * `strategy` - The strategy that picked the guesses.
+/// * `average` - The average number of guesses over all games, failures included.
+/// * `worst` - The largest number of guesses of a solved game.
+/// * `failures` - The number of games that were not solved within the round limit.
+/// * `elapsed` - The time it took to play all games.
+#[derive(Clone, Debug)]
+pub struct StrategyResult {
+    pub strategy: Strategy,
+    pub average: f64,
+    pub worst: usize,
+    pub failures: usize,
+    pub elapsed: Duration,
+}
+
+/// Compares strategies by simulating every solution with each of them, all starting with
+/// the same `first_guess`. The strategies are run one after another, so that their runtimes
+/// are comparable. The [`PatternMatrix`] they share is computed beforehand and not included
+/// in the runtimes.
+///
+/// # Arguments
+/// * `words` - The list of allowed guesses.
+/// * `solutions` - The solutions to simulate for every strategy.
+/// * `strategies` - The strategies to compare.
+/// * `first_guess` - The opening guess of every game.
+/// * `seed` - The seed of the random choices of every strategy, see [`simulate`].
+/// * `progress` - Called with the result of every strategy as soon as it is known.
+///
+/// # Returns
+/// The results in the order of `strategies`.
+pub fn compare<F>(words: &[Word], solutions: &[Word], strategies: &[Strategy], first_guess: Word, seed: u64,
+                  mut progress: F) -> Vec<StrategyResult> where F: FnMut(&StrategyResult) {
+    let matrix = Arc::new(PatternMatrix::new(words, words));
+    strategies.iter().map(|strategy| {
+        let start = Instant::now();
+        let records = simulate(&matrix, words, solutions, first_guess, *strategy, Some(seed));
+        let stats = OpenerResult::new(first_guess, &records);
+        let result = StrategyResult {
+            strategy: *strategy,
+            average: stats.average,
+            worst: stats.worst,
+            failures: stats.failures,
+            elapsed: start.elapsed(),
+        };
+        progress(&result);
+        result
+    }).collect()
+}
+
+/// How likely the solutions of a [`backtest`] are taken to be the answer.
+///
+/// # Variants
+/// * `Uniform` - All solutions are equally likely.
+/// * `Weighted` - Every solution is as likely as its weight in the list, e.g. its frequency.
+/// * `Sharpened` - Like `Weighted`, but with the weights sharpened toward common words from
+///   round to round, see [`Sharpening`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Prior {
+    Uniform,
+    Weighted,
+    Sharpened(Sharpening),
+}
+
+impl Display for Prior {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Prior::Uniform => write!(f, "uniform"),
+            Prior::Weighted => write!(f, "weighted"),
+            Prior::Sharpened(_) => write!(f, "sharpened"),
+        }
+    }
+}
+
+/// Plays the `answers` of past puzzles in the order they were published, e.g. the daily
+/// answers of Wordle, to see how well `prior` predicts the words the editors really picked.
+/// Every game leaves the answers before it out of the solutions, as the game does not repeat
+/// answers. The games run in parallel and print nothing.
+///
+/// # Arguments
+/// * `matrix` - The feedback of every word of `words` for every solution, shared by all games.
+/// * `solutions` - The possible answers with their weights, e.g. their frequencies.
+/// * `answers` - The past answers, oldest first. Answers that are not among `solutions` are
+///   skipped.
+///
+/// # Returns
+/// The records of the games, in the order of `answers`.
+pub fn backtest(matrix: &Arc<PatternMatrix>, words: &[Word], solutions: &Arc<WeightedList>, answers: &[Word],
+                first_guess: Word, prior: Prior) -> Vec<GameRecord> {
+    answers.par_iter().enumerate().filter(|(_, answer)| solutions.weight(answer).is_some()).map(|(i, answer)| {
+        let earlier = answers[..i].iter().filter(|w| *w != answer).collect::<HashSet<&Word>>();
+        let candidates = solutions.words().iter().copied().filter(|w| !earlier.contains(w)).collect::<Vec<Word>>();
+        let game = SimulatedGame::with_solutions(words, &candidates, *answer, first_guess)
+            .with_matrix(Arc::clone(matrix))
+            .quiet();
+        let mut game = match prior {
+            Prior::Uniform => game,
+            Prior::Weighted => game.with_weights(Arc::clone(solutions), None),
+            Prior::Sharpened(sharpening) => game.with_weights(Arc::clone(solutions), Some(sharpening)),
+        };
+        game.run_game();
+        game.record()
+    }).collect()
+}
+
+/// The machine-readable formats batch results can be written in, see [`RecordWriter`].
+///
+/// # Variants
+/// * `Csv` - A header line, then one line per game. Lists (guesses and solution space sizes)
+///   are separated by spaces.
+/// * `Json` - One JSON object per line and game (only with the `serde` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFormat {
+    Csv,
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// Writes one record per game, for analysis with other tools such as pandas or R.
+///
+/// # Example
+///
+/// ```text
+/// solution,guesses,rounds,solved,space_sizes
+/// aahed,tears abled addin adhan aahed,5,true,14855 1121 37 3 1
+/// ```
+pub struct RecordWriter<W: Write> {
+    out: W,
+    format: RecordFormat,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(mut out: W, format: RecordFormat) -> io::Result<RecordWriter<W>> {
+        if format == RecordFormat::Csv {
+            writeln!(out, "solution,guesses,rounds,solved,space_sizes")?;
+        }
+        Ok(RecordWriter { out, format })
+    }
+
+    pub fn write(&mut self, record: &GameRecord) -> io::Result<()> {
+        let join = |items: Vec<String>| items.join(" ");
+        match self.format {
+            RecordFormat::Csv => writeln!(self.out, "{},{},{},{},{}",
+                record.solution,
+                join(record.guesses.iter().map(Word::to_string).collect()),
+                record.rounds(),
+                record.solved,
+                join(record.space_sizes.iter().map(usize::to_string).collect())),
+            #[cfg(feature = "serde")]
+            RecordFormat::Json => writeln!(self.out, "{}", serde_json::json!({
+                "solution": record.solution,
+                "guesses": record.guesses,
+                "rounds": record.rounds(),
+                "solved": record.solved,
+                "space_sizes": record.space_sizes,
+            })),
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// Per-letter statistics over a batch of games, useful to design better opening sequences.
+///
+/// # Fields
+/// * `failed` - How often each letter occurs in the solutions of failed games.
+/// * `tested` - For each letter, the rounds (counting from 1) in which it was first part of a
+///   guess, one entry per game where it was guessed at all.
+/// * `resolved` - For each letter of a solution, the rounds in which all its positions were
+///   first marked green, one entry per game and letter.
+#[derive(Default)]
+pub struct LetterStats {
+    failed: BTreeMap<char, u32>,
+    tested: BTreeMap<char, Vec<usize>>,
+    resolved: BTreeMap<char, Vec<usize>>,
+}
+
+impl LetterStats {
+
+    /// The letters whose testing order is reported separately.
+    const VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
+
+    pub fn new(records: &[GameRecord]) -> LetterStats {
+        let mut stats = LetterStats::default();
+        for record in records {
+            stats.add(record);
+        }
+        stats
+    }
+
+    fn add(&mut self, record: &GameRecord) {
+        if !record.solved {
+            for i in 0..WORD_LENGTH {
+                *self.failed.entry(record.solution.letter(i)).or_insert(0) += 1;
+            }
+        }
+
+        let mut tested = BTreeMap::new();
+        for (round, guess) in record.guesses.iter().enumerate() {
+            for i in 0..WORD_LENGTH {
+                tested.entry(guess.letter(i)).or_insert(round + 1);
+            }
+        }
+        for (letter, round) in tested {
+            self.tested.entry(letter).or_default().push(round);
+        }
+
+        let mut placed = [None; WORD_LENGTH];
+        for (round, guess) in record.guesses.iter().enumerate() {
+            for i in 0..WORD_LENGTH {
+                if placed[i].is_none() && guess[i] == record.solution[i] {
+                    placed[i] = Some(round + 1);
+                }
+            }
+        }
+        let mut resolved: BTreeMap<char, Option<usize>> = BTreeMap::new();
+        for (i, round) in placed.iter().enumerate() {
+            let entry = resolved.entry(record.solution.letter(i)).or_insert(Some(0));
+            *entry = match (*entry, *round) {
+                (Some(a), Some(b)) => Some(usize::max(a, b)),
+                _ => None,
+            };
+        }
+        for (letter, round) in resolved {
+            if let Some(round) = round {
+                self.resolved.entry(letter).or_default().push(round);
+            }
+        }
+    }
+
+    fn average(rounds: Option<&Vec<usize>>) -> Option<f64> {
+        rounds.filter(|r| !r.is_empty())
+            .map(|r| r.iter().sum::<usize>() as f64 / r.len() as f64)
+    }
+
+    /// The vowels that were tested at all, ordered by the average round of their first test.
+    pub fn vowel_order(&self) -> Vec<(char, f64)> {
+        let mut order = Self::VOWELS.iter()
+            .filter_map(|v| Self::average(self.tested.get(v)).map(|avg| (*v, avg)))
+            .collect::<Vec<_>>();
+        order.sort_by(|a, b| f64::total_cmp(&a.1, &b.1));
+        order
+    }
+
+    pub fn print(&self) {
+        let format = |avg: Option<f64>| avg.map_or("-".to_string(), |a| format!("{:.2}", a));
+        let mut letters = self.tested.keys().chain(self.resolved.keys()).chain(self.failed.keys())
+            .copied().collect::<Vec<char>>();
+        letters.sort();
+        letters.dedup();
+        println!("{}", style::bold("Letter statistics:"));
+        println!("letter  in failed  first tested  resolved");
+        for letter in letters {
+            println!("{:<6}  {:>9}  {:>12}  {:>8}", letter,
+                     self.failed.get(&letter).unwrap_or(&0),
+                     format(Self::average(self.tested.get(&letter))),
+                     format(Self::average(self.resolved.get(&letter))));
+        }
+        let vowels = self.vowel_order().iter()
+            .map(|(v, avg)| format!("{} ({:.2})", v, avg))
+            .collect::<Vec<String>>();
+        println!("{} {}", style::bold("Vowels by first test:"), vowels.join(", "));
+    }
+}
+
+/// The distribution of the number of guesses over a batch of games, like the statistics
+/// screen of Wordle.
+///
+/// # Example
+///
+/// ```text
+/// Guess distribution (2315 games, average 3.62):
+/// 1  ▏ 0
+/// 2  ██▎ 71
+/// 3  ████████████████████████████████████████ 1002
+/// ...
+/// X  ▏ 4
+/// ```
+pub struct GuessHistogram {
+    counts: Vec<u32>,
+    failed: u32,
+}
+
+impl GuessHistogram {
+    const BAR_WIDTH: usize = 40;
+
+    /// Counts the games of `records` by their number of guesses. Games that were solved in
+    /// more than `max_rounds` guesses count as failures.
+    pub fn new(records: &[GameRecord], max_rounds: usize) -> GuessHistogram {
+        let mut histogram = GuessHistogram { counts: vec![0; max_rounds], failed: 0 };
+        for record in records {
+            match record.rounds() {
+                rounds if record.solved && (1..=max_rounds).contains(&rounds) =>
+                    histogram.counts[rounds - 1] += 1,
+                _ => histogram.failed += 1,
+            }
+        }
+        histogram
+    }
+
+    /// The average number of guesses of the solved games.
+    pub fn average(&self) -> Option<f64> {
+        let solved = self.counts.iter().sum::<u32>();
+        let guesses = self.counts.iter().enumerate()
+            .map(|(i, count)| (i as u32 + 1) * count)
+            .sum::<u32>();
+        (solved > 0).then(|| guesses as f64 / solved as f64)
+    }
+
+    /// A bar of eighth blocks, `BAR_WIDTH` characters long for the most frequent count.
+    fn bar(&self, count: u32) -> String {
+        let max = self.counts.iter().copied().chain([self.failed]).max().unwrap_or(0).max(1);
+        let eighths = (count as usize * Self::BAR_WIDTH * 8 + max as usize / 2) / max as usize;
+        let mut bar = "█".repeat(eighths / 8);
+        match eighths % 8 {
+            0 if eighths > 0 => {}
+            rest => bar.push([' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'][rest.max(1)]),
+        }
+        bar
+    }
+
+    pub fn print(&self) {
+        let games = self.counts.iter().sum::<u32>() + self.failed;
+        let average = self.average().map(|average| format!(", average {:.2}", average)).unwrap_or_default();
+        println!("{}", style::bold(format_args!("Guess distribution ({} games{}):", games, average)));
+        for (i, count) in self.counts.iter().enumerate() {
+            println!("{:<2} {} {}", i + 1, self.bar(*count), count);
+        }
+        println!("{:<2} {} {}", "X", self.bar(self.failed), self.failed);
+    }
+}
+
+/// Everything needed to reproduce the numbers of a batch run: the exact inputs, the program
+/// version and the parameters of the strategy.
+///
+/// # Example
+///
+/// ```text
+/// wordl-rust-bot 0.1.0
+/// word list wordle.txt: 14855 words, sha256 5d0c4e...
+/// word list wordle-answers.txt: 2315 words, sha256 8f1a9b...
+/// first guess: tears
+/// seed: none
+/// threads: 8
+/// elapsed: 812.417s
+/// ```
+pub struct Manifest {
+    word_lists: Vec<(String, usize, String)>,
+    params: Vec<(String, String)>,
+    seed: Option<u64>,
+    threads: usize,
+    elapsed: Option<Duration>,
+}
+
+impl Manifest {
+    pub fn new() -> Manifest {
+        Manifest {
+            word_lists: Vec::new(),
+            params: Vec::new(),
+            seed: None,
+            threads: rayon::current_num_threads(),
+            elapsed: None,
+        }
+    }
+
+    /// The SHA-256 hash of a word list in its canonical form, one word per line. It equals the
+    /// `sha256sum` of a list file without blank lines or surrounding whitespace.
+    pub fn hash(words: &[Word]) -> String {
+        let mut hasher = Sha256::new();
+        for word in words {
+            hasher.update(format!("{}\n", word).as_bytes());
+        }
+        hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    pub fn word_list(mut self, name: &str, words: &[Word]) -> Manifest {
+        self.word_lists.push((name.to_string(), words.len(), Self::hash(words)));
+        self
+    }
+
+    /// Records a parameter of the strategy, e.g. the first guess.
+    pub fn param(mut self, name: &str, value: impl ToString) -> Manifest {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Records a strategy and its parameters, e.g. the rollouts of [`Strategy::MonteCarlo`].
+    /// Called once per strategy when a run compares several.
+    pub fn strategy(self, strategy: Strategy) -> Manifest {
+        let manifest = self.param("strategy", strategy);
+        match strategy {
+            Strategy::MonteCarlo { rollouts } => manifest.param("rollouts", rollouts),
+            Strategy::Beam { width, depth } => manifest.param("beam width", width).param("beam depth", depth),
+            Strategy::Composite { weight } => manifest.param("win weight", weight),
+            Strategy::Noisy { error_rate } => manifest.param("error rate", error_rate),
+            Strategy::Entropy | Strategy::Minimax | Strategy::Frequency | Strategy::Partitions
+            | Strategy::Positional | Strategy::Random => manifest,
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Manifest {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = Some(elapsed);
+    }
+
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))?;
+        for (name, length, hash) in &self.word_lists {
+            writeln!(out, "word list {}: {} words, sha256 {}", name, length, hash)?;
+        }
+        for (name, value) in &self.params {
+            writeln!(out, "{}: {}", name, value)?;
+        }
+        match self.seed {
+            Some(seed) => writeln!(out, "seed: {}", seed)?,
+            None => writeln!(out, "seed: none")?,
+        }
+        writeln!(out, "threads: {}", self.threads)?;
+        if let Some(elapsed) = self.elapsed {
+            writeln!(out, "elapsed: {:.3}s", elapsed.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Manifest::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(solution: &str, guesses: &[&str], solved: bool) -> GameRecord {
+        GameRecord {
+            solution: Word::from_str(solution),
+            guesses: guesses.iter().map(|g| Word::from_str(g)).collect(),
+            solved,
+            space_sizes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tournament() {
+        let words = ["tears", "bears", "gears", "fears", "crane", "bagel"].map(Word::from_str);
+        let openers = ["tears", "bagel"].map(Word::from_str);
+        let mut seen = Vec::new();
+        let results = tournament(&words, &words, &openers, 0, |r| seen.push(r.opener));
+        assert_eq!(seen, openers);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].failures, 0);
+        assert!(results[0].average <= results[1].average);
+        assert_eq!(tournament(&words, &words, &[], 3, |_| {}).len(), 3);
+    }
+
+    #[test]
+    fn test_compare() {
+        let words = ["tears", "bears", "gears", "fears", "crane", "bagel"].map(Word::from_str);
+        let results = compare(&words, &words, &Strategy::ALL, Word::from_str("tears"), 0, |_| {});
+        assert_eq!(results.iter().map(|r| r.strategy).collect::<Vec<_>>(), Strategy::ALL);
+        for result in &results {
+            assert_eq!(result.failures, 0);
+            assert!(result.average >= 1.0 && result.worst <= 6);
+        }
+    }
+
+    #[test]
+    fn test_backtest() {
+        // Common words have high weights, and the answers are mostly common words.
+        let solutions = Arc::new(WeightedList::new([("tears", 50.0), ("bears", 30.0), ("gears", 1.0), ("fears", 1.0),
+                                                    ("crane", 40.0), ("bagel", 20.0)]
+            .map(|(word, weight)| (Word::from_str(word), weight))));
+        let words = solutions.words();
+        let answers = ["bears", "crane", "slimy", "tears", "bagel"].map(Word::from_str);
+        let matrix = Arc::new(PatternMatrix::new(words, words));
+        let play = |prior| backtest(&matrix, words, &solutions, &answers, Word::from_str("fears"), prior);
+        let uniform = play(Prior::Uniform);
+        assert_eq!(uniform.iter().map(|r| r.solution).collect::<Vec<_>>(),
+                   ["bears", "crane", "tears", "bagel"].map(Word::from_str));
+        assert!(uniform.iter().all(|r| r.solved));
+        // The last game knows all other answers, so only its answer and the rare words are left.
+        assert_eq!(uniform[3].space_sizes[0], 3);
+        let sharpened = play(Prior::Sharpened(Sharpening::DEFAULT));
+        let rounds = |records: &[GameRecord]| records.iter().map(GameRecord::rounds).sum::<usize>();
+        assert!(rounds(&sharpened) <= rounds(&uniform));
+    }
+
+    #[test]
+    fn test_manifest() {
+        let words = ["tears", "crane"].map(Word::from_str);
+        // printf 'tears\ncrane\n' | sha256sum
+        assert_eq!(Manifest::hash(&words),
+                   "3cb676dcb9002235285d65de76546a0aef410e4a542401b0b2ba95539f2ff37d");
+        let mut manifest = Manifest::new().word_list("list.txt", &words).param("first guess", "tears")
+            .strategy(Strategy::Entropy)
+            .strategy(Strategy::Beam { width: 4, depth: 3 });
+        manifest.set_elapsed(Duration::from_millis(1500));
+        let mut out = Vec::new();
+        manifest.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("word list list.txt: 2 words, sha256 3cb6"));
+        assert!(text.contains("first guess: tears\nstrategy: entropy\nstrategy: beam\nbeam width: 4\nbeam depth: 3\nseed: none\n"));
+        assert!(text.ends_with("elapsed: 1.500s\n"));
+    }
+
+    #[test]
+    fn test_guess_histogram() {
+        let histogram = GuessHistogram::new(&[
+            record("bears", &["tears", "bears"], true),
+            record("tears", &["tears"], true),
+            record("lolly", &["tears", "crane"], false),
+            record("gears", &["tears", "bears", "fears", "gears"], true),
+        ], 3);
+        assert_eq!(histogram.counts, vec![1, 1, 0]);
+        assert_eq!(histogram.failed, 2);
+        assert_eq!(histogram.average(), Some(1.5));
+        assert_eq!(histogram.bar(2), "█".repeat(GuessHistogram::BAR_WIDTH));
+        assert_eq!(histogram.bar(0), "▏");
+    }
+
+    #[test]
+    fn test_record_writer() {
+        let mut game = record("bears", &["tears", "bears"], true);
+        game.space_sizes = vec![100, 3];
+        let mut writer = RecordWriter::new(Vec::new(), RecordFormat::Csv).unwrap();
+        writer.write(&game).unwrap();
+        let csv = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(csv, "solution,guesses,rounds,solved,space_sizes\nbears,tears bears,2,true,100 3\n");
+    }
+
+    #[test]
+    fn test_letter_stats() {
+        let stats = LetterStats::new(&[
+            record("bears", &["tears", "bears"], true),
+            record("lolly", &["tears", "crane"], false),
+        ]);
+        assert_eq!(stats.failed[&'l'], 3);
+        assert_eq!(stats.tested[&'t'], vec![1, 1]);
+        assert_eq!(stats.tested[&'c'], vec![2]);
+        assert_eq!(stats.resolved[&'e'], vec![1]);
+        assert_eq!(stats.resolved[&'b'], vec![2]);
+        assert!(!stats.resolved.contains_key(&'l'));
+        assert_eq!(stats.vowel_order(), vec![('a', 1.0), ('e', 1.0)]);
+    }
+}
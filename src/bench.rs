@@ -0,0 +1,106 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::game::{SimulatedGame, Solver};
+use crate::matrix::PatternMatrix;
+use crate::word::Word;
+
+/// The runtimes of one benchmark.
+///
+/// # Fields
+/// * `name` - What was timed, e.g. `score matrix`.
+/// * `runs` - How often it was timed.
+/// * `min` - The fastest run.
+/// * `median` - The median run.
+/// * `mean` - The average run.
+/// * `scores` - The number of guesses scored against a solution in one run, if known.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timing {
+    pub name: &'static str,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub scores: Option<u64>,
+}
+
+impl Timing {
+    /// Times `runs` calls of `f`, at least one. Its result is passed to [`black_box`], so that
+    /// the work is not optimised away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::bench::Timing;
+    ///
+    /// let timing = Timing::measure("sum", 3, Some(1000), || (0..1000_u64).sum::<u64>());
+    /// assert_eq!(timing.runs, 3);
+    /// assert!(timing.min <= timing.median);
+    /// ```
+    pub fn measure<T, F: FnMut() -> T>(name: &'static str, runs: usize, scores: Option<u64>, mut f: F) -> Timing {
+        let runs = runs.max(1);
+        let mut times = (0..runs)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(f());
+                start.elapsed()
+            })
+            .collect::<Vec<Duration>>();
+        times.sort();
+        Timing {
+            name,
+            runs,
+            min: times[0],
+            median: times[runs / 2],
+            mean: times.iter().sum::<Duration>() / runs as u32,
+            scores,
+        }
+    }
+
+    /// The number of scores computed per second in the median run, if known.
+    pub fn scores_per_second(&self) -> Option<f64> {
+        self.scores.map(|scores| scores as f64 / self.median.as_secs_f64().max(f64::EPSILON))
+    }
+}
+
+/// Times the hot paths of the solver on `words` and `solutions`, each `runs` times:
+///
+/// * `score matrix` - Building the [`PatternMatrix`] of all guesses and solutions.
+/// * `entropy pass` - The entropy of a single guess over all solutions.
+/// * `full evaluation` - Ranking all guesses by entropy, without skipping any.
+/// * `simulated game` - One [`SimulatedGame`] from the best first guess, against a different
+///   solution in every run.
+///
+/// The other benchmarks do not look up the matrix, so that they measure
+/// [`score`](crate::score) and the entropy themselves.
+///
+/// # Arguments
+/// * `words` - The allowed guesses, at least one.
+/// * `solutions` - The possible solutions, at least one.
+/// * `runs` - How often each benchmark is timed.
+/// * `progress` - Called with every timing as soon as it is known.
+///
+/// # Returns
+/// The timings in the order above.
+pub fn run<F>(words: &[Word], solutions: &[Word], runs: usize, mut progress: F) -> Vec<Timing>
+where F: FnMut(&Timing) {
+    let pairs = (words.len() * solutions.len()) as u64;
+    let solver = Solver::with_solutions(words, solutions);
+    let mut best = words[0];
+    let mut game = 0;
+    let mut timings = Vec::new();
+    let mut add = |timing: Timing| {
+        progress(&timing);
+        timings.push(timing);
+    };
+    add(Timing::measure("score matrix", runs, Some(pairs), || PatternMatrix::new(words, solutions)));
+    add(Timing::measure("entropy pass", runs, Some(solutions.len() as u64), || solver.entropy(&words[0])));
+    add(Timing::measure("full evaluation", runs, Some(pairs), || {
+        best = *solver.suggestions(words.len())[0].word;
+    }));
+    add(Timing::measure("simulated game", runs, None, || {
+        let solution = solutions[game % solutions.len()];
+        game += 1;
+        SimulatedGame::new(words, solution, best).quiet().run_game()
+    }));
+    timings
+}
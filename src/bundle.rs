@@ -0,0 +1,145 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use crate::word::Word;
+
+/// Marks the end of an executable that has word lists appended to it.
+const MAGIC: &[u8; 8] = b"WRBBNDL1";
+
+/// The size of the trailer after the bundled lists: the length of the lists, then [`MAGIC`].
+const TRAILER: u64 = 16;
+
+/// A word list that is shipped inside the executable, see [`write_bundle`].
+///
+/// # Fields
+/// * `name` - The name the list is selected by, usually the file name it was bundled from.
+/// * `text` - The list with one word per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundledList {
+    pub name: String,
+    pub text: String,
+}
+
+impl BundledList {
+    pub fn new(name: &str, words: &[Word]) -> BundledList {
+        let text = words.iter().map(|w| format!("{}\n", w)).collect();
+        BundledList { name: name.to_string(), text }
+    }
+}
+
+/// Writes a self-contained executable: the program `executable` followed by `lists`. The
+/// result runs like the original program, but [`bundled_lists`] returns the appended lists.
+///
+/// If `executable` already has lists appended, they are replaced.
+///
+/// # Layout
+///
+/// ```text
+/// program | (name length: u32, name, text length: u64, text)* | lists length: u64 | MAGIC
+/// ```
+///
+/// All lengths are little endian.
+pub fn write_bundle<W: Write>(executable: &Path, lists: &[BundledList], mut out: W) -> io::Result<()> {
+    let mut file = File::open(executable)?;
+    let program_length = match read_trailer(&mut file)? {
+        Some((offset, _)) => offset,
+        None => file.metadata()?.len(),
+    };
+    file.seek(SeekFrom::Start(0))?;
+    io::copy(&mut file.take(program_length), &mut out)?;
+
+    let mut payload = Vec::new();
+    for list in lists {
+        payload.extend_from_slice(&(list.name.len() as u32).to_le_bytes());
+        payload.extend_from_slice(list.name.as_bytes());
+        payload.extend_from_slice(&(list.text.len() as u64).to_le_bytes());
+        payload.extend_from_slice(list.text.as_bytes());
+    }
+    out.write_all(&payload)?;
+    out.write_all(&(payload.len() as u64).to_le_bytes())?;
+    out.write_all(MAGIC)?;
+    out.flush()
+}
+
+/// Finds the bundled lists at the end of `file`, returning their offset and length.
+fn read_trailer(file: &mut File) -> io::Result<Option<(u64, u64)>> {
+    let length = file.metadata()?.len();
+    if length < TRAILER {
+        return Ok(None);
+    }
+    let mut trailer = [0u8; TRAILER as usize];
+    file.seek(SeekFrom::End(-(TRAILER as i64)))?;
+    file.read_exact(&mut trailer)?;
+    if &trailer[8..] != MAGIC {
+        return Ok(None);
+    }
+    let payload = u64::from_le_bytes(trailer[..8].try_into().unwrap());
+    match (length - TRAILER).checked_sub(payload) {
+        Some(offset) => Ok(Some((offset, payload))),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "corrupt word list bundle")),
+    }
+}
+
+/// Reads the word lists bundled with `executable`, see [`write_bundle`]. A program without
+/// bundled lists has none.
+pub fn read_bundle(executable: &Path) -> io::Result<Vec<BundledList>> {
+    let mut file = File::open(executable)?;
+    let Some((offset, length)) = read_trailer(&mut file)? else {
+        return Ok(Vec::new());
+    };
+    let mut payload = Vec::with_capacity(length as usize);
+    file.seek(SeekFrom::Start(offset))?;
+    file.take(length).read_to_end(&mut payload)?;
+
+    let corrupt = || io::Error::new(io::ErrorKind::InvalidData, "corrupt word list bundle");
+    let mut lists = Vec::new();
+    let mut rest = payload.as_slice();
+    let mut take = |n: usize| -> io::Result<&[u8]> {
+        if rest.len() < n {
+            return Err(corrupt());
+        }
+        let (head, tail) = rest.split_at(n);
+        rest = tail;
+        Ok(head)
+    };
+    while let Ok(bytes) = take(4) {
+        let name_length = u32::from_le_bytes(bytes.try_into().unwrap()) as usize;
+        let name = String::from_utf8(take(name_length)?.to_vec()).map_err(|_| corrupt())?;
+        let text_length = u64::from_le_bytes(take(8)?.try_into().unwrap()) as usize;
+        let text = String::from_utf8(take(text_length)?.to_vec()).map_err(|_| corrupt())?;
+        lists.push(BundledList { name, text });
+    }
+    Ok(lists)
+}
+
+/// The word lists bundled with the running program.
+pub fn bundled_lists() -> io::Result<Vec<BundledList>> {
+    read_bundle(&std::env::current_exe()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let directory = std::env::temp_dir().join(format!("wordle-bundle-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let program = directory.join("program");
+        std::fs::write(&program, b"#!/bin/true\n").unwrap();
+        assert_eq!(read_bundle(&program).unwrap(), vec![]);
+
+        let words = ["tears", "crane"].map(Word::from_str);
+        let lists = vec![BundledList::new("a.txt", &words), BundledList::new("b.txt", &words[..1])];
+        let bundled = directory.join("bundled");
+        write_bundle(&program, &lists, File::create(&bundled).unwrap()).unwrap();
+        assert_eq!(read_bundle(&bundled).unwrap(), lists);
+
+        // Bundling a bundled program replaces its lists.
+        let rebundled = directory.join("rebundled");
+        write_bundle(&bundled, &lists[1..], File::create(&rebundled).unwrap()).unwrap();
+        assert_eq!(read_bundle(&rebundled).unwrap(), lists[1..]);
+        assert!(std::fs::read(&rebundled).unwrap().starts_with(b"#!/bin/true\n"));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
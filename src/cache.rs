@@ -0,0 +1,161 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::batch::Manifest;
+use crate::game::Solver;
+use crate::matrix::PatternMatrix;
+use crate::word::Word;
+
+/// Marks the start of a cache file, followed by the version of its layout.
+const MAGIC: &[u8; 8] = b"WRBCACH1";
+
+/// The expensive part of starting a game, kept on disk between runs: the [`PatternMatrix`]
+/// of a word list and the entropy ranking of all guesses before the first round.
+///
+/// Cache files are named after the hashes of the word lists they were computed for (see
+/// [`Manifest::hash`]), so a changed list simply misses the cache instead of reading stale
+/// data.
+///
+/// # Fields
+/// * `matrix` - The feedback of every guess for every solution.
+/// * `ranking` - The ids of all guesses in the matrix together with their entropy over all
+///   solutions, best first.
+pub struct ScoreCache {
+    pub matrix: Arc<PatternMatrix>,
+    pub ranking: Vec<(u32, f64)>,
+}
+
+impl ScoreCache {
+    /// Computes the cache for the guesses `guesses` and the solutions `solutions`.
+    pub fn build(guesses: &[Word], solutions: &[Word]) -> ScoreCache {
+        let matrix = Arc::new(PatternMatrix::new(guesses, solutions));
+        let solver = Solver::with_solutions(guesses, solutions).with_matrix(Arc::clone(&matrix));
+        let ranking = solver.suggestions(guesses.len()).iter()
+            .map(|eval| (matrix.guess_id(eval.word).unwrap(), eval.entropy))
+            .collect();
+        ScoreCache { matrix, ranking }
+    }
+
+    /// The file in `directory` that holds the cache for `guesses` and `solutions`.
+    pub fn path(directory: &Path, guesses: &[Word], solutions: &[Word]) -> PathBuf {
+        let (guesses, solutions) = (Manifest::hash(guesses), Manifest::hash(solutions));
+        directory.join(format!("scores-{}-{}.bin", &guesses[..16], &solutions[..16]))
+    }
+
+    /// Reads the cache for `guesses` and `solutions` from `directory`, or computes it and
+    /// writes it there if it is missing or does not belong to these lists.
+    ///
+    /// # Errors
+    /// If the directory cannot be created or the cache file cannot be read or written.
+    pub fn load_or_build(directory: &Path, guesses: &[Word], solutions: &[Word]) -> io::Result<ScoreCache> {
+        let path = Self::path(directory, guesses, solutions);
+        match File::open(&path) {
+            Ok(file) => {
+                if let Some(cache) = Self::read(BufReader::new(file), guesses, solutions)? {
+                    return Ok(cache);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let cache = Self::build(guesses, solutions);
+        fs::create_dir_all(directory)?;
+        // Write to a temporary file first, so that an interrupted run leaves no broken cache.
+        let temporary = path.with_extension(format!("{}.tmp", std::process::id()));
+        cache.write(BufWriter::new(File::create(&temporary)?), guesses, solutions)?;
+        fs::rename(&temporary, &path)?;
+        Ok(cache)
+    }
+
+    /// Writes the cache for `guesses` and `solutions`.
+    ///
+    /// # Layout
+    ///
+    /// ```text
+    /// MAGIC | guesses hash | solutions hash | guesses: u64 | solutions: u64 | patterns |
+    /// ranking length: u64 | (guess id: u32, entropy: f64)*
+    /// ```
+    ///
+    /// The hashes are the 64 hex digits of [`Manifest::hash`], numbers are little endian.
+    pub fn write<W: Write>(&self, mut out: W, guesses: &[Word], solutions: &[Word]) -> io::Result<()> {
+        out.write_all(MAGIC)?;
+        out.write_all(Manifest::hash(guesses).as_bytes())?;
+        out.write_all(Manifest::hash(solutions).as_bytes())?;
+        out.write_all(&(self.matrix.guesses() as u64).to_le_bytes())?;
+        out.write_all(&(self.matrix.solutions() as u64).to_le_bytes())?;
+        for guess in 0..self.matrix.guesses() as u32 {
+            out.write_all(self.matrix.row(guess))?;
+        }
+        out.write_all(&(self.ranking.len() as u64).to_le_bytes())?;
+        for (guess, entropy) in &self.ranking {
+            out.write_all(&guess.to_le_bytes())?;
+            out.write_all(&entropy.to_le_bytes())?;
+        }
+        out.flush()
+    }
+
+    /// Reads a cache written by [`ScoreCache::write`].
+    ///
+    /// # Returns
+    /// The cache, or `None` if it was written for other word lists or by another version.
+    ///
+    /// # Errors
+    /// If the input cannot be read or ends early.
+    pub fn read<R: Read>(mut input: R, guesses: &[Word], solutions: &[Word]) -> io::Result<Option<ScoreCache>> {
+        let mut header = [0u8; 8 + 64 + 64];
+        input.read_exact(&mut header)?;
+        let expected = [&MAGIC[..], Manifest::hash(guesses).as_bytes(), Manifest::hash(solutions).as_bytes()].concat();
+        if header[..] != expected[..] {
+            return Ok(None);
+        }
+        let mut read_u64 = || -> io::Result<u64> {
+            let mut bytes = [0u8; 8];
+            input.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+        let (rows, columns) = (read_u64()? as usize, read_u64()? as usize);
+        if (rows, columns) != (guesses.len(), solutions.len()) {
+            return Ok(None);
+        }
+        let mut patterns = vec![0u8; rows * columns];
+        input.read_exact(&mut patterns)?;
+        let mut length = [0u8; 8];
+        input.read_exact(&mut length)?;
+        let mut ranking = Vec::with_capacity(rows);
+        for _ in 0..u64::from_le_bytes(length) {
+            let mut entry = [0u8; 12];
+            input.read_exact(&mut entry)?;
+            let guess = u32::from_le_bytes(entry[..4].try_into().unwrap());
+            let entropy = f64::from_le_bytes(entry[4..].try_into().unwrap());
+            ranking.push((guess, entropy));
+        }
+        let matrix = PatternMatrix::from_patterns(guesses, solutions, patterns);
+        Ok(Some(ScoreCache { matrix: Arc::new(matrix), ranking }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let words = sample_words();
+        let solutions = &words[..3];
+        let cache = ScoreCache::build(&words, solutions);
+        assert_eq!(cache.ranking.len(), words.len());
+
+        let mut bytes = Vec::new();
+        cache.write(&mut bytes, &words, solutions).unwrap();
+        let read = ScoreCache::read(bytes.as_slice(), &words, solutions).unwrap().unwrap();
+        assert_eq!(read.ranking, cache.ranking);
+        for guess in 0..words.len() as u32 {
+            assert_eq!(read.matrix.row(guess), cache.matrix.row(guess));
+        }
+        // A cache for other lists is not used.
+        assert!(ScoreCache::read(bytes.as_slice(), &words, &words).unwrap().is_none());
+        assert!(ScoreCache::read(&bytes[..100], &words, solutions).is_err());
+    }
+}
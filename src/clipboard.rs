@@ -0,0 +1,42 @@
+use std::time::Duration;
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+use std::time::Instant;
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+use arboard::SetExtLinux;
+use crate::error::WordleError;
+
+/// Access to the system clipboard, for pasting share grids and suggestions into chats.
+///
+/// On Linux and the BSDs, copied text is served by the program that copied it, so it
+/// disappears once the `Clipboard` is dropped. Keep it alive for as long as the text should
+/// stay available, or use [`Clipboard::copy_and_wait`] right before the program exits.
+pub struct Clipboard {
+    clipboard: arboard::Clipboard,
+}
+
+impl Clipboard {
+    pub fn new() -> Result<Clipboard, WordleError> {
+        Ok(Clipboard { clipboard: arboard::Clipboard::new()? })
+    }
+
+    /// Replaces the contents of the clipboard with `text`.
+    pub fn copy(&mut self, text: &str) -> Result<(), WordleError> {
+        self.clipboard.set_text(text)?;
+        Ok(())
+    }
+
+    /// Like [`Clipboard::copy`], but on platforms where copied text vanishes with the program,
+    /// blocks until another program takes over the clipboard or `timeout` has passed.
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+    pub fn copy_and_wait(&mut self, text: &str, timeout: Duration) -> Result<(), WordleError> {
+        self.clipboard.set().wait_until(Instant::now() + timeout).text(text)?;
+        Ok(())
+    }
+
+    /// Like [`Clipboard::copy`], but on platforms where copied text vanishes with the program,
+    /// blocks until another program takes over the clipboard or `timeout` has passed.
+    #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+    pub fn copy_and_wait(&mut self, text: &str, _timeout: Duration) -> Result<(), WordleError> {
+        self.copy(text)
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A day of the Gregorian calendar, for numbering the daily puzzles.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::daily::Date;
+///
+/// let date: Date = "2022-01-01".parse().unwrap();
+/// assert_eq!(date.puzzle_number(), Some(196));
+/// assert_eq!(date.next().to_string(), "2022-01-02");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// The day of Wordle #0.
+    pub const LAUNCH: Date = Date { year: 2021, month: 6, day: 19 };
+
+    /// The date `year`-`month`-`day`, if it exists.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        let date = Date { year, month, day };
+        (Date::from_days(date.days()) == date).then_some(date)
+    }
+
+    /// Today in UTC. The puzzle changes at local midnight, so in the hours around midnight
+    /// this can be a day off; pass the date explicitly then.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    /// The number of days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        // Howard Hinnant's `days_from_civil`, counting years from March.
+        let year = self.year as i64 - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+        let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+        let year = (year_of_era + era * 400) as i32 + i32::from(month <= 2);
+        Date { year, month, day }
+    }
+
+    /// The following day.
+    pub fn next(&self) -> Date {
+        Date::from_days(self.days() + 1)
+    }
+
+    /// The number of the Wordle puzzle of this day, or `None` before the launch.
+    pub fn puzzle_number(&self) -> Option<u32> {
+        (self.days() - Date::LAUNCH.days()).try_into().ok()
+    }
+}
+
+/// Where the New York Times publishes the metadata of the puzzle of a day, with `{date}`
+/// standing for the date.
+pub const METADATA_URL: &str = "https://www.nytimes.com/svc/wordle/v2/{date}.json";
+
+/// The metadata of a daily puzzle, as published at [`METADATA_URL`].
+///
+/// # Fields
+/// * `days_since_launch` - The number of the puzzle.
+/// * `solution` - The answer.
+/// * `editor` - Who picked the answer, if known.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Puzzle {
+    pub days_since_launch: u32,
+    pub solution: String,
+    #[serde(default)]
+    pub editor: Option<String>,
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Date, String> {
+        let invalid = || format!("<{}> is not a date written as YYYY-MM-DD", value);
+        let mut parts = value.trim().splitn(3, '-');
+        let mut next = || parts.next().and_then(|part| part.parse::<u32>().ok());
+        let (Some(year), Some(month), Some(day)) = (next(), next(), next()) else {
+            return Err(invalid());
+        };
+        Date::new(year as i32, month, day).ok_or_else(invalid)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_date() {
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!("2024-02-29".parse::<Date>().unwrap().next().to_string(), "2024-03-01");
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2023-13-01".parse::<Date>().is_err());
+        assert_eq!(Date::LAUNCH.puzzle_number(), Some(0));
+        assert_eq!("2021-06-18".parse::<Date>().unwrap().puzzle_number(), None);
+        let mut date = Date::LAUNCH;
+        for _ in 0..1000 {
+            assert_eq!(Date::from_days(date.days()), date);
+            date = date.next();
+        }
+    }
+}
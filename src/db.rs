@@ -0,0 +1,213 @@
+use std::path::Path;
+use std::time::Duration;
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::batch::GameRecord;
+use crate::transcript::Mode;
+use crate::word::Word;
+
+/// A run of the program whose games are stored in a [`ResultStore`]: a whole `batch` run,
+/// or a single game of `play`.
+///
+/// # Fields
+/// * `mode` - The subcommand of the run.
+/// * `strategy` - How the guesses were chosen, e.g. `entropy`, `tree` or `human`.
+/// * `first_guess` - The first guess of the solver, if it plays a fixed one.
+/// * `word_list` - The [`Manifest::hash`](crate::batch::Manifest::hash) of the allowed words.
+/// * `solution_list` - The hash of the possible solutions.
+/// * `elapsed` - How long the run took.
+pub struct Run {
+    pub mode: Mode,
+    pub strategy: String,
+    pub first_guess: Option<Word>,
+    pub word_list: String,
+    pub solution_list: String,
+    pub elapsed: Duration,
+}
+
+/// One game of a [`Run`].
+///
+/// # Fields
+/// * `solution` - The word the game was played against.
+/// * `guesses` - The guesses, in order.
+/// * `solved` - Whether the solution was found within the round limit.
+/// * `time` - How long the game took, if it was timed.
+pub struct StoredGame {
+    pub solution: Word,
+    pub guesses: Vec<Word>,
+    pub solved: bool,
+    pub time: Option<Duration>,
+}
+
+impl From<&GameRecord> for StoredGame {
+    fn from(record: &GameRecord) -> StoredGame {
+        StoredGame { solution: record.solution, guesses: record.guesses.clone(), solved: record.solved, time: None }
+    }
+}
+
+/// The results of a stored run, as listed by [`ResultStore::runs`].
+///
+/// # Fields
+/// * `id` - The number of the run, counting up from 1.
+/// * `started` - When the run was stored, in UTC, e.g. `2024-01-31 18:02:11`.
+/// * `games` - The number of games of the run.
+/// * `solved` - How many of them were solved.
+/// * `average` - The average number of guesses of the solved games, if any were solved.
+/// * The other fields are those of [`Run`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunSummary {
+    pub id: i64,
+    pub started: String,
+    pub mode: String,
+    pub strategy: String,
+    pub first_guess: Option<String>,
+    pub word_list: String,
+    pub games: u32,
+    pub solved: u32,
+    pub average: Option<f64>,
+    pub elapsed: Duration,
+}
+
+/// An SQLite database that collects the results of many runs, for experiments that span
+/// days or versions of the program. The tables can also be queried directly:
+///
+/// * `runs` - One row per run, with the columns `id`, `started`, `mode`, `strategy`,
+///   `first_guess`, `word_list`, `solution_list` and `elapsed_ms`.
+/// * `games` - One row per game, with the columns `run_id`, `solution`, `guesses` (separated
+///   by spaces), `rounds`, `solved` and `time_ms`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wordl_rust_bot::db::{ResultStore, Run, StoredGame};
+/// use wordl_rust_bot::transcript::Mode;
+/// use wordl_rust_bot::Word;
+///
+/// let mut store = ResultStore::open_in_memory().unwrap();
+/// let run = Run {
+///     mode: Mode::Batch,
+///     strategy: "entropy".to_string(),
+///     first_guess: Some(Word::from_str("tears")),
+///     word_list: "5d0c4e".to_string(),
+///     solution_list: "8f1a9b".to_string(),
+///     elapsed: Duration::from_secs(2),
+/// };
+/// let game = StoredGame {
+///     solution: Word::from_str("bears"),
+///     guesses: vec![Word::from_str("tears"), Word::from_str("bears")],
+///     solved: true,
+///     time: None,
+/// };
+/// store.insert(&run, [game]).unwrap();
+/// let runs = store.runs(None, 10).unwrap();
+/// assert_eq!((runs[0].games, runs[0].solved, runs[0].average), (1, 1, Some(2.0)));
+/// ```
+pub struct ResultStore {
+    connection: Connection,
+}
+
+impl ResultStore {
+    const SCHEMA: &'static str = "
+        CREATE TABLE IF NOT EXISTS runs (
+            id INTEGER PRIMARY KEY,
+            started TEXT NOT NULL DEFAULT (datetime('now')),
+            mode TEXT NOT NULL,
+            strategy TEXT NOT NULL,
+            first_guess TEXT,
+            word_list TEXT NOT NULL,
+            solution_list TEXT NOT NULL,
+            elapsed_ms INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS games (
+            run_id INTEGER NOT NULL REFERENCES runs(id),
+            solution TEXT NOT NULL,
+            guesses TEXT NOT NULL,
+            rounds INTEGER NOT NULL,
+            solved INTEGER NOT NULL,
+            time_ms INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS games_run ON games(run_id);";
+
+    /// Opens the database at `path`, creating it and its tables if needed.
+    ///
+    /// # Errors
+    /// If the file cannot be opened or is not an SQLite database.
+    pub fn open(path: &Path) -> rusqlite::Result<ResultStore> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A database that only lives in memory, e.g. for tests.
+    pub fn open_in_memory() -> rusqlite::Result<ResultStore> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> rusqlite::Result<ResultStore> {
+        connection.execute_batch(Self::SCHEMA)?;
+        Ok(ResultStore { connection })
+    }
+
+    /// Stores `run` and its `games` in a single transaction.
+    ///
+    /// # Returns
+    /// The id of the run.
+    pub fn insert(&mut self, run: &Run, games: impl IntoIterator<Item = StoredGame>) -> rusqlite::Result<i64> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO runs (mode, strategy, first_guess, word_list, solution_list, elapsed_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run.mode.to_string(), run.strategy, run.first_guess.map(|word| word.to_string()),
+                    run.word_list, run.solution_list, run.elapsed.as_millis() as i64],
+        )?;
+        let id = transaction.last_insert_rowid();
+        {
+            let mut insert = transaction.prepare(
+                "INSERT INTO games (run_id, solution, guesses, rounds, solved, time_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+            for game in games {
+                let guesses = game.guesses.iter().map(Word::to_string).collect::<Vec<String>>();
+                insert.execute(params![id, game.solution.to_string(), guesses.join(" "), game.guesses.len(),
+                                       game.solved, game.time.map(|time| time.as_millis() as i64)])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(id)
+    }
+
+    /// The `limit` latest runs, latest first, only those of `mode` if given.
+    pub fn runs(&self, mode: Option<Mode>, limit: usize) -> rusqlite::Result<Vec<RunSummary>> {
+        let mut query = self.connection.prepare(
+            "SELECT runs.id, started, mode, strategy, first_guess, word_list, elapsed_ms,
+                    COUNT(games.run_id), COALESCE(SUM(games.solved), 0),
+                    AVG(CASE WHEN games.solved THEN games.rounds END)
+             FROM runs LEFT JOIN games ON games.run_id = runs.id
+             WHERE ?1 IS NULL OR mode = ?1
+             GROUP BY runs.id ORDER BY runs.id DESC LIMIT ?2")?;
+        let rows = query.query_map(params![mode.map(|mode| mode.to_string()), limit as i64], |row| {
+            Ok(RunSummary {
+                id: row.get(0)?,
+                started: row.get(1)?,
+                mode: row.get(2)?,
+                strategy: row.get(3)?,
+                first_guess: row.get(4)?,
+                word_list: row.get(5)?,
+                elapsed: Duration::from_millis(row.get::<_, i64>(6)? as u64),
+                games: row.get(7)?,
+                solved: row.get(8)?,
+                average: row.get(9)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// The solutions the games of run `id` failed on, in the order they were played, or
+    /// `None` if there is no such run.
+    pub fn failures(&self, id: i64) -> rusqlite::Result<Option<Vec<String>>> {
+        let exists = self.connection.query_row("SELECT id FROM runs WHERE id = ?1", [id], |row| row.get::<_, i64>(0))
+            .optional()?;
+        if exists.is_none() {
+            return Ok(None);
+        }
+        let mut query = self.connection.prepare("SELECT solution FROM games WHERE run_id = ?1 AND NOT solved")?;
+        let rows = query.query_map([id], |row| row.get(0))?;
+        rows.collect::<rusqlite::Result<Vec<String>>>().map(Some)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::error::WordListError;
+use crate::word::{read_words, Word};
+
+/// A word list compiled into the program, so that it runs without any files.
+///
+/// # Fields
+/// * `name` - The name the dictionary is selected by, e.g. with `--dict wordle-en`.
+/// * `description` - A short description for the command line help.
+/// * `words` - The allowed guesses, one per line.
+/// * `answers` - The possible answers, one per line, if the game draws them from a smaller
+///   list than the allowed guesses.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::dictionary::Dictionary;
+///
+/// let wordle = Dictionary::find("wordle-en").unwrap();
+/// assert_eq!(wordle.words().unwrap().len(), 14855);
+/// assert_eq!(wordle.answers().unwrap().len(), 2315);
+/// ```
+pub struct Dictionary {
+    pub name: &'static str,
+    pub description: &'static str,
+    words: &'static str,
+    answers: Option<&'static str>,
+}
+
+/// The dictionaries compiled into the program. The first one is the default.
+pub const DICTIONARIES: &[Dictionary] = &[
+    Dictionary {
+        name: "wordle-en",
+        description: "the allowed guesses and answers of the English Wordle",
+        words: include_str!("../wordle.txt"),
+        answers: Some(include_str!("../wordle-answers.txt")),
+    },
+    Dictionary {
+        name: "lordle-en",
+        description: "the words of Lordle of the Rings",
+        words: include_str!("../lordle.txt"),
+        answers: None,
+    },
+];
+
+impl Dictionary {
+    /// The dictionary called `name`, if it is compiled into the program.
+    pub fn find(name: &str) -> Option<&'static Dictionary> {
+        DICTIONARIES.iter().find(|dictionary| dictionary.name == name)
+    }
+
+    /// The names of all dictionaries, separated by commas.
+    pub fn names() -> String {
+        DICTIONARIES.iter().map(|dictionary| dictionary.name).collect::<Vec<&str>>().join(", ")
+    }
+
+    /// The allowed guesses.
+    ///
+    /// # Errors
+    /// Only if the compiled-in list is malformed.
+    pub fn words(&self) -> Result<Vec<Word>, WordListError> {
+        read_words(self.name, self.words.as_bytes())
+    }
+
+    /// The possible answers, which are the allowed guesses if the dictionary has no separate
+    /// list of answers.
+    ///
+    /// # Errors
+    /// Only if the compiled-in list is malformed.
+    pub fn answers(&self) -> Result<Vec<Word>, WordListError> {
+        match self.answers {
+            Some(answers) => read_words(&format!("{}/answers", self.name), answers.as_bytes()),
+            None => self.words(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dictionaries() {
+        for dictionary in DICTIONARIES {
+            let words = dictionary.words().unwrap();
+            let answers = dictionary.answers().unwrap();
+            assert!(answers.iter().all(|answer| words.contains(answer)), "{}", dictionary.name);
+        }
+        assert!(Dictionary::find("klingon").is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serenity::all::{ChannelId, Context, EventHandler, GatewayIntents, Message};
+use serenity::{async_trait, Client};
+use crate::error::WordleError;
+use crate::game::Solver;
+use crate::pattern::Pattern;
+use crate::server::Session;
+use crate::share::Share;
+use crate::word::Word;
+
+/// The prefix of the messages the bot answers.
+pub const PREFIX: &str = "!wordle";
+
+/// A message to the bot like `!wordle crane bbygb`.
+///
+/// # Variants
+/// * `Suggest` - `!wordle`: shows the board and the best guesses.
+/// * `Guess` - `!wordle crane bbygb`: enters a guess and its pattern, then suggests.
+/// * `Reset` - `!wordle reset`: starts a new game.
+/// * `Help` - `!wordle help`, or anything else: explains the command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiscordCommand {
+    Suggest,
+    Guess(String, String),
+    Reset,
+    Help,
+}
+
+impl DiscordCommand {
+    /// Parses a message, or returns `None` if it does not start with [`PREFIX`].
+    pub fn parse(message: &str) -> Option<DiscordCommand> {
+        let text = message.trim().strip_prefix(PREFIX)?;
+        if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let parts = text.split_whitespace().collect::<Vec<&str>>();
+        Some(match parts.as_slice() {
+            [] | ["suggest"] => DiscordCommand::Suggest,
+            ["reset"] | ["new"] => DiscordCommand::Reset,
+            [guess, pattern] => DiscordCommand::Guess(guess.to_lowercase(), pattern.to_lowercase()),
+            _ => DiscordCommand::Help,
+        })
+    }
+}
+
+/// The usage of the bot, as shown by [`DiscordCommand::Help`].
+pub const USAGE: &str = "`!wordle GUESS PATTERN` enters a guess and its colors (g = green, y = yellow, \
+                         b = black), e.g. `!wordle crane bbygb`. `!wordle` shows suggestions, \
+                         `!wordle reset` starts a new game.";
+
+/// The reply for the game `solver`: the emoji board so far with the guessed words, and the
+/// best guesses, or the solution once it is known.
+pub fn reply(solver: &Solver) -> String {
+    let mut lines = solver.state().history.iter()
+        .map(|(guess, pattern)| format!("{} `{}`", Share::row(pattern), guess))
+        .collect::<Vec<String>>();
+    let remaining = solver.solution_space();
+    lines.push(match remaining.len() {
+        0 => "No word in the list fits this feedback. `!wordle reset` starts a new game.".to_string(),
+        1 => format!("The solution is **{}**.", remaining[0]),
+        n => {
+            let suggestions = solver.suggestions(5).iter()
+                .map(|e| format!("`{}` ({:.2})", e.word, e.entropy))
+                .collect::<Vec<String>>();
+            format!("{} possible solutions. Try {}.", n, suggestions.join(", "))
+        }
+    });
+    lines.join("\n")
+}
+
+/// Plays one game per channel, which every member of the channel can add guesses to.
+///
+/// # Fields
+/// * `initial` - The session every game starts as.
+/// * `channels` - The game of every channel that has talked to the bot.
+pub struct DiscordBot {
+    initial: Session<'static>,
+    channels: Mutex<HashMap<ChannelId, Session<'static>>>,
+}
+
+impl DiscordBot {
+    pub fn new(solver: Solver<'static>) -> DiscordBot {
+        DiscordBot { initial: Session::new(solver), channels: Mutex::new(HashMap::new()) }
+    }
+
+    /// Answers `command` for the game of `channel`.
+    pub fn answer(&self, channel: ChannelId, command: DiscordCommand) -> String {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        let session = channels.entry(channel).or_insert_with(|| self.initial.clone());
+        match command {
+            DiscordCommand::Suggest => reply(session.solver()),
+            DiscordCommand::Reset => {
+                session.reset();
+                format!("New game. {}", reply(session.solver()))
+            }
+            DiscordCommand::Guess(guess, pattern) => {
+                match (Word::try_from_str_ignore_case(&guess), Pattern::try_from_str(&pattern)) {
+                    (Ok(guess), Ok(pattern)) => {
+                        session.feedback(guess, pattern);
+                        reply(session.solver())
+                    }
+                    (Err(e), _) => format!("{}. {}", e, USAGE),
+                    (_, Err(e)) => format!("{}. {}", e, USAGE),
+                }
+            }
+            DiscordCommand::Help => USAGE.to_string(),
+        }
+    }
+
+    /// Connects to Discord with the bot `token` and answers messages until the connection
+    /// fails. The bot needs the message content intent, enabled in the developer portal.
+    ///
+    /// # Errors
+    /// If the connection cannot be made or is lost.
+    pub fn run(self, token: &str) -> Result<(), WordleError> {
+        let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::DIRECT_MESSAGES
+            | GatewayIntents::MESSAGE_CONTENT;
+        let runtime = tokio::runtime::Runtime::new()?;
+        runtime.block_on(async {
+            let mut client = Client::builder(token, intents).event_handler(self).await?;
+            client.start().await
+        }).map_err(|e| WordleError::Discord(Box::new(e)))
+    }
+}
+
+#[async_trait]
+impl EventHandler for DiscordBot {
+    async fn message(&self, context: Context, message: Message) {
+        if message.author.bot {
+            return;
+        }
+        let Some(command) = DiscordCommand::parse(&message.content) else {
+            return;
+        };
+        // Ranking the guesses takes a while, during which the runtime can go on with other work.
+        let text = tokio::task::block_in_place(|| self.answer(message.channel_id, command));
+        if let Err(e) = message.channel_id.say(&context.http, text).await {
+            eprintln!("could not answer in channel {}: {}", message.channel_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_discord() {
+        assert_eq!(DiscordCommand::parse("!wordle crane BBYGB"),
+                   Some(DiscordCommand::Guess("crane".to_string(), "bbygb".to_string())));
+        assert_eq!(DiscordCommand::parse(" !wordle "), Some(DiscordCommand::Suggest));
+        assert_eq!(DiscordCommand::parse("!wordle what is this"), Some(DiscordCommand::Help));
+        assert_eq!(DiscordCommand::parse("!wordles"), None);
+        assert_eq!(DiscordCommand::parse("hello"), None);
+
+        let words: &'static [Word] = Vec::leak(sample_words());
+        let bot = DiscordBot::new(Solver::new(words));
+        let (first, second) = (ChannelId::new(1), ChannelId::new(2));
+        let answer = bot.answer(first, DiscordCommand::Guess("crane".to_string(), "bygby".to_string()));
+        assert!(answer.starts_with("⬛🟨🟩⬛🟨 `crane`\n3 possible solutions."), "{}", answer);
+        assert!(bot.answer(second, DiscordCommand::Suggest).starts_with("5 possible solutions."));
+        assert!(bot.answer(first, DiscordCommand::Reset).starts_with("New game. 5 possible"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::io;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use thiserror::Error;
+use crate::game::GameState;
+use crate::pattern::PatternParseError;
+use crate::phrase::PhraseError;
+use crate::share::GridError;
+use crate::word::{Word, WordError, WORD_LENGTH};
+
+/// The error type of this crate, which all other errors can be converted into.
+///
+/// # Variants
+/// * `Io` - Reading input or writing output failed.
+/// * `EndOfInput` - Standard input ended while the game was still asking for input.
+/// * `Word`, `Pattern` - A word or pattern is malformed.
+/// * `WordList` - A word list could not be loaded, e.g. because it is empty.
+/// * `Phrase` - A phrase or phrase pattern is malformed.
+/// * `History` - A game history such as `crane:bgybb,slimy:bbgyb` is malformed.
+/// * `Grid` - A pasted share grid is malformed.
+/// * `Argument` - A command line option that should be a word is not a valid word.
+/// * `InconsistentFeedback` - No word in the list fits the feedback entered so far.
+/// * `MissingBundle` - A bundled word list was requested that is not part of the program.
+/// * `MissingDictionary` - A dictionary was requested that is not compiled into the program.
+/// * `InvalidTree` - A decision tree failed verification.
+/// * `UnguessableSolutions` - A decision tree was requested for solutions that are not all
+///   allowed guesses.
+/// * `InvalidWordList` - A word list failed validation.
+/// * `Fetch` - A word list could not be downloaded (only with the `fetch` feature).
+/// * `Clipboard` - The system clipboard is not available (only with the `clipboard` feature).
+/// * `Database` - The results database cannot be read or written (only with the `sqlite`
+///   feature).
+/// * `MissingToken` - A chat bot was started without its token in the environment.
+/// * `Discord` - The connection to Discord failed (only with the `discord` feature).
+/// * `Telegram` - A request to the Telegram Bot API failed (only with the `telegram` feature).
+#[derive(Debug, Error, Diagnostic)]
+pub enum WordleError {
+    #[error("input/output error")]
+    Io(#[from] io::Error),
+    #[error("input ended before the game was over")]
+    EndOfInput,
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Word(#[from] WordError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Pattern(#[from] PatternParseError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    WordList(#[from] WordListError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Phrase(#[from] PhraseError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    History(#[from] HistoryError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Grid(#[from] GridError),
+    #[error("invalid value for --{argument}")]
+    Argument { argument: String, #[source] #[diagnostic_source] source: WordError },
+    #[error("no word in the list fits the feedback {state}")]
+    #[diagnostic(help("check the guesses and patterns for typos, and whether the word list \
+                       contains the solution"))]
+    InconsistentFeedback { state: GameState },
+    #[error("word list bundled:{name} is not bundled with this program")]
+    #[diagnostic(help("bundled lists: {available}"))]
+    MissingBundle { name: String, available: String },
+    #[error("dictionary {name} is not compiled into this program")]
+    #[diagnostic(help("available dictionaries: {available}"))]
+    MissingDictionary { name: String, available: String },
+    #[error("decision tree failed verification with {problems} problems")]
+    #[diagnostic(help("rebuild the tree with build-tree for this word list"))]
+    InvalidTree { problems: usize },
+    #[error("{count} solutions are not in the word list, e.g. {example}")]
+    #[diagnostic(help("a decision tree must be able to guess every solution; add them to the word list"))]
+    UnguessableSolutions { count: usize, example: Word },
+    #[error("word list {name} has {issues} problems")]
+    #[diagnostic(help("pass --out to write a cleaned copy of the list"))]
+    InvalidWordList { name: String, issues: usize },
+    #[cfg(feature = "fetch")]
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Fetch(#[from] FetchError),
+    #[cfg(feature = "clipboard")]
+    #[error("could not access the system clipboard")]
+    #[diagnostic(help("on Linux, copying needs a running X11 or Wayland session"))]
+    Clipboard(#[from] arboard::Error),
+    #[cfg(feature = "sqlite")]
+    #[error("could not access the results database")]
+    Database(#[from] rusqlite::Error),
+    #[error("environment variable {variable} is not set")]
+    #[diagnostic(help("set it to the token of the bot"))]
+    MissingToken { variable: String },
+    #[cfg(feature = "discord")]
+    #[error("could not talk to Discord")]
+    Discord(#[source] Box<serenity::Error>),
+    #[cfg(feature = "telegram")]
+    #[error("Telegram request {method} failed: {reason}")]
+    Telegram { method: String, reason: String },
+}
+
+/// An error while loading a word list.
+///
+/// # Variants
+/// * `Io` - The file could not be read.
+/// * `Empty` - The file does not contain a single word.
+/// * `Entry` - A line of the file is not a valid word. The diagnostic points at the
+///   offending line and column of the file.
+/// * `Frequency` - The frequency given after the word on a line is not a number.
+#[derive(Debug, Error, Diagnostic)]
+pub enum WordListError {
+    #[error("could not read word list {name}")]
+    Io { name: String, #[source] source: io::Error },
+    #[error("word list {name} contains no words")]
+    #[diagnostic(help("each line of a word list must contain one {WORD_LENGTH}-letter word"))]
+    Empty { name: String },
+    #[error("bad entry in word list on line {line}, column {column}")]
+    #[diagnostic(help("each line of a word list must contain one {WORD_LENGTH}-letter word \
+                       made of letters only; fix or remove the line"))]
+    Entry {
+        #[source_code] src: NamedSource<String>,
+        #[label("{source}")] span: SourceSpan,
+        line: usize,
+        column: usize,
+        source: Box<WordError>,
+    },
+    #[error("bad frequency <{value}> in word list {name} on line {line}")]
+    #[diagnostic(help("a line may give the frequency of its word after a tab, as a number \
+                       that is not negative, e.g. `crane<TAB>1350`"))]
+    Frequency { name: String, line: usize, value: String },
+}
+
+impl WordListError {
+    /// Creates an [`WordListError::Entry`] for the word on `line` (counting from 1) that
+    /// starts at byte `offset` of `text`.
+    pub fn entry(name: &str, text: &str, line: usize, offset: usize, source: WordError) -> WordListError {
+        let word = text[offset..].lines().next().unwrap_or("").split('\t').next().unwrap_or("").trim_end();
+        let (start, length) = match &source {
+            WordError::Letter { position, letter, .. } | WordError::Alphabet { position, letter, .. } => {
+                let start = word.char_indices().nth(*position).map_or(0, |(i, _)| i);
+                (offset + start, letter.len_utf8())
+            }
+            WordError::Length { .. } => (offset, word.len()),
+        };
+        let column = text[..start].rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        WordListError::Entry {
+            src: NamedSource::new(name, text.to_string()),
+            span: (start, length).into(),
+            line,
+            column,
+            source: Box::new(source),
+        }
+    }
+}
+
+/// An error while downloading a word list.
+///
+/// # Variants
+/// * `InvalidUrl` - The URL is not an `http://` or `https://` URL.
+/// * `Io` - The server could not be reached, the connection failed or the certificate is
+///   not valid.
+/// * `Status` - The server answered with a status other than success.
+/// * `TooManyRedirects` - The server redirected too often.
+/// * `Response` - The response is not valid HTTP or its body is not UTF-8.
+#[cfg(feature = "fetch")]
+#[derive(Debug, Error, Diagnostic)]
+pub enum FetchError {
+    #[error("{url} is not a valid http:// or https:// URL")]
+    InvalidUrl { url: String },
+    #[error("could not download {url}")]
+    Io { url: String, #[source] source: io::Error },
+    #[error("downloading {url} failed with status {status} {reason}")]
+    Status { url: String, status: u16, reason: String },
+    #[error("downloading {url} was redirected too often")]
+    TooManyRedirects { url: String },
+    #[error("the server sent a malformed response for {url}")]
+    Response { url: String },
+}
+
+/// A malformed entry in a game history such as `crane:bgybb,slimy:bbgyb`. The diagnostic
+/// points at the entry.
+#[derive(Debug, Error, Diagnostic)]
+#[error("invalid game history")]
+#[diagnostic(help("write the history as guess:pattern pairs separated by commas, \
+                   e.g. crane:bgybb,slimy:bbgyb, using g = green, y = yellow, b = black"))]
+pub struct HistoryError {
+    #[source_code] pub src: NamedSource<String>,
+    #[label("{reason}")] pub span: SourceSpan,
+    pub reason: String,
+}
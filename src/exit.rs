@@ -0,0 +1,126 @@
+use std::process::ExitCode;
+use crate::error::WordleError;
+
+/// The exit status of the program, so that scripts wrapping it can tell how a game ended
+/// without parsing its output.
+///
+/// # Variants
+/// * `Success` (0) - The command finished, and a game it ran was solved.
+/// * `Failure` (1) - Any error not covered below, e.g. an output file could not be written.
+/// * `Unsolved` (2) - A game ended without finding the solution: the rounds ran out or the
+///   input ended first.
+/// * `InconsistentFeedback` (3) - No word in the list fits the feedback that was entered.
+/// * `BadInput` (4) - A word list, decision tree, history or other input is malformed.
+/// * `Usage` (5) - The command line is invalid, e.g. an unknown option or a malformed word
+///   given as an option.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::exit::ExitStatus;
+///
+/// assert_eq!(ExitStatus::game(true, 1).code(), 0);
+/// assert_eq!(ExitStatus::game(false, 12), ExitStatus::Unsolved);
+/// assert_eq!(ExitStatus::game(false, 0).name(), "inconsistent_feedback");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ExitStatus {
+    Success = 0,
+    Failure = 1,
+    Unsolved = 2,
+    InconsistentFeedback = 3,
+    BadInput = 4,
+    Usage = 5,
+}
+
+impl ExitStatus {
+    /// The status of a game that ended with `remaining` words still fitting the feedback.
+    pub fn game(solved: bool, remaining: usize) -> ExitStatus {
+        match (solved, remaining) {
+            (true, _) => ExitStatus::Success,
+            (false, 0) => ExitStatus::InconsistentFeedback,
+            (false, _) => ExitStatus::Unsolved,
+        }
+    }
+
+    /// The number the process exits with.
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// The name of the status in machine-readable output, e.g. `bad_input`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExitStatus::Success => "success",
+            ExitStatus::Failure => "failure",
+            ExitStatus::Unsolved => "unsolved",
+            ExitStatus::InconsistentFeedback => "inconsistent_feedback",
+            ExitStatus::BadInput => "bad_input",
+            ExitStatus::Usage => "usage",
+        }
+    }
+}
+
+impl From<&WordleError> for ExitStatus {
+    fn from(error: &WordleError) -> ExitStatus {
+        match error {
+            WordleError::InconsistentFeedback { .. } => ExitStatus::InconsistentFeedback,
+            WordleError::Word(_) | WordleError::Pattern(_) | WordleError::WordList(_)
+            | WordleError::Phrase(_) | WordleError::History(_) | WordleError::Grid(_)
+            | WordleError::InvalidTree { .. } | WordleError::InvalidWordList { .. } => ExitStatus::BadInput,
+            WordleError::Argument { .. } | WordleError::MissingBundle { .. }
+            | WordleError::MissingDictionary { .. } => ExitStatus::Usage,
+            _ => ExitStatus::Failure,
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> ExitCode {
+        ExitCode::from(status.code())
+    }
+}
+
+/// `error` as a single JSON object for programs wrapping this one, e.g.
+/// `{"error":"…","status":"inconsistent_feedback","code":3,"help":"…","causes":[]}`. The
+/// `help` is `null` if the error has none, and `causes` lists the messages of the errors
+/// that led to it.
+#[cfg(feature = "serde")]
+pub fn error_json(error: &WordleError) -> serde_json::Value {
+    use miette::Diagnostic;
+    use std::error::Error;
+
+    let status = ExitStatus::from(error);
+    let mut causes = Vec::new();
+    let mut cause = error.source();
+    while let Some(inner) = cause {
+        causes.push(inner.to_string());
+        cause = inner.source();
+    }
+    serde_json::json!({
+        "error": error.to_string(),
+        "status": status.name(),
+        "code": status.code(),
+        "help": error.help().map(|help| help.to_string()),
+        "causes": causes,
+    })
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+    use crate::game::GameState;
+
+    #[test]
+    fn test_error_json() {
+        let state = GameState::parse("crane:ggggb").unwrap();
+        let json = error_json(&WordleError::InconsistentFeedback { state });
+        assert_eq!(json["status"], "inconsistent_feedback");
+        assert_eq!(json["code"], 3);
+        assert!(json["help"].as_str().unwrap().starts_with("check the guesses"));
+        let json = error_json(&WordleError::EndOfInput);
+        assert_eq!(json["code"], 1);
+        assert!(json["help"].is_null());
+    }
+}
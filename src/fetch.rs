@@ -0,0 +1,120 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::time::Duration;
+use sha2::{Digest, Sha256};
+use ureq::ErrorKind;
+use crate::error::FetchError;
+
+/// The most redirects followed for a single download.
+const MAX_REDIRECTS: u32 = 5;
+
+/// The largest response read, in bytes. Word lists are a few hundred kilobytes at most.
+const MAX_RESPONSE: u64 = 16 * 1024 * 1024;
+
+/// How long to wait for the server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether a command line argument names a URL rather than a file.
+pub fn is_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+/// Downloads `url`, an `http://` or `https://` URL, and returns the body of the response,
+/// following redirects. Certificates are checked against the Mozilla root certificates.
+///
+/// # Errors
+/// If the URL is not valid, the server cannot be reached, it answers with a status other
+/// than success or redirects too often, or the body is not UTF-8.
+pub fn get(url: &str) -> Result<String, FetchError> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(TIMEOUT)
+        .redirects(MAX_REDIRECTS)
+        .user_agent("wordl-rust-bot")
+        .build();
+    let response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::Status(status, response) => {
+            FetchError::Status { url: url.to_string(), status, reason: response.status_text().to_string() }
+        }
+        ureq::Error::Transport(transport) => match transport.kind() {
+            ErrorKind::InvalidUrl | ErrorKind::UnknownScheme => FetchError::InvalidUrl { url: url.to_string() },
+            ErrorKind::TooManyRedirects => FetchError::TooManyRedirects { url: url.to_string() },
+            _ => FetchError::Io { url: url.to_string(), source: io::Error::other(transport) },
+        },
+    })?;
+    let mut body = Vec::new();
+    response.into_reader().take(MAX_RESPONSE).read_to_end(&mut body)
+        .map_err(|source| FetchError::Io { url: url.to_string(), source })?;
+    String::from_utf8(body).map_err(|_| FetchError::Response { url: url.to_string() })
+}
+
+/// The directory downloaded word lists are kept in: `$XDG_CACHE_HOME/wordl-rust-bot`, or
+/// `~/.cache/wordl-rust-bot` if `XDG_CACHE_HOME` is not set.
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(base.join("wordl-rust-bot"))
+}
+
+/// Like [`get`], but keeps the response in the [`cache_dir`] and reads it from there on later
+/// calls, so a list is only downloaded once. Delete the cached file to download it again.
+///
+/// # Errors
+/// As for [`get`]. Failing to write the cache is not an error.
+pub fn get_cached(url: &str) -> Result<String, FetchError> {
+    let hash: String = Sha256::digest(url.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+    let path = cache_dir().map(|dir| dir.join(format!("list-{}.txt", &hash[..16])));
+    if let Some(text) = path.as_ref().and_then(|path| fs::read_to_string(path).ok()) {
+        return Ok(text);
+    }
+    let text = get(url)?;
+    if let Some(path) = path {
+        // Write to a temporary file first, so that an interrupted run leaves no truncated list.
+        let temporary = path.with_extension(format!("{}.tmp", std::process::id()));
+        let written = path.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&temporary, &text))
+            .and_then(|_| fs::rename(&temporary, &path));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temporary);
+            eprintln!("warning: could not cache {} in {}: {}", url, path.display(), e);
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_get() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let responses = [
+                "HTTP/1.1 301 Moved Permanently\r\nLocation: /words.txt\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n6\r\ncrane\n\r\n6\r\nslimy\n\r\n0\r\n\r\n",
+                "HTTP/1.0 404 Not Found\r\n\r\n",
+            ];
+            let mut paths = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let head: Vec<String> = BufReader::new(&stream).lines()
+                    .map(Result::unwrap)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                paths.push(head[0].split(' ').nth(1).unwrap().to_string());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            paths
+        });
+        assert_eq!(get(&format!("http://127.0.0.1:{}/list", port)).unwrap(), "crane\nslimy\n");
+        assert!(matches!(get(&format!("http://127.0.0.1:{}/gone", port)),
+                         Err(FetchError::Status { status: 404, .. })));
+        assert_eq!(server.join().unwrap(), ["/list", "/words.txt", "/gone"]);
+        assert!(matches!(get("ftp://example.com/list"), Err(FetchError::InvalidUrl { .. })));
+    }
+}
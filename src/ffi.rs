@@ -0,0 +1,197 @@
+use std::ffi::{c_char, c_int, CStr};
+use std::ptr;
+use crate::game::Solver;
+use crate::pattern::Pattern;
+use crate::word::{read_words, Word, WORD_LENGTH};
+
+/// The number of letters of a word, [`WORD_LENGTH`] for the C interface.
+pub const WORDLE_WORD_LENGTH: usize = 5;
+const _: () = assert!(WORDLE_WORD_LENGTH == WORD_LENGTH);
+
+/// A game of the C interface, declared in `include/wordle.h`, for embedding the solver in C,
+/// C++ or Swift apps. Build the crate as a static or dynamic library and link against it.
+/// Every function takes NUL-terminated UTF-8 strings and reports invalid input with a negative
+/// return value or `NULL`.
+///
+/// A game owns its word lists, which the solver borrows for as long as the game lives.
+///
+/// # Example
+///
+/// ```c
+/// #include "wordle.h"
+///
+/// Wordle *wordle = wordle_new("tears\nbears\ngears\ncrane\nslimy\n", NULL);
+/// char guess[WORDLE_WORD_LENGTH + 1];
+/// wordle_best_guess(wordle, guess);
+/// wordle_feedback(wordle, "crane", "bygby");
+/// wordle_free(wordle);
+/// ```
+pub struct Wordle {
+    // Declared first, so that it is dropped before the lists it borrows.
+    solver: Solver<'static>,
+    _lists: WordLists,
+}
+
+/// A word list and a solution list that stay in place until they are dropped, so that a
+/// `Solver<'static>` can borrow them where the owner of the solver has no lifetime to tie
+/// them to, as in the bindings to other languages.
+pub(crate) struct WordLists {
+    words: *mut [Word],
+    solutions: *mut [Word],
+}
+
+impl WordLists {
+    pub(crate) fn new(words: Vec<Word>, solutions: Vec<Word>) -> WordLists {
+        WordLists {
+            words: Box::into_raw(words.into_boxed_slice()),
+            solutions: Box::into_raw(solutions.into_boxed_slice()),
+        }
+    }
+
+    /// A solver with the words and solutions of the lists.
+    ///
+    /// # Safety
+    /// The solver, and every solver cloned from it, must be dropped before the lists.
+    pub(crate) unsafe fn solver(&self) -> Solver<'static> {
+        Solver::with_solutions(&*self.words, &*self.solutions)
+    }
+}
+
+impl Drop for WordLists {
+    fn drop(&mut self) {
+        // SAFETY: both lists come from `Box::into_raw`, and the solvers borrowing them are gone.
+        unsafe {
+            drop(Box::from_raw(self.words));
+            drop(Box::from_raw(self.solutions));
+        }
+    }
+}
+
+/// Reads the word list `list`, one word per line, unless it is empty.
+///
+/// # Safety
+/// `list` must be a valid NUL-terminated string.
+unsafe fn read_list(list: *const c_char) -> Option<Vec<Word>> {
+    let text = CStr::from_ptr(list).to_str().ok()?;
+    read_words("list", text.as_bytes()).ok().filter(|words| !words.is_empty())
+}
+
+/// Starts a game where the guesses are the words of `words` and the solutions are the words
+/// of `solutions`, or `words` again if `solutions` is `NULL`. Both lists have one word per line.
+///
+/// # Returns
+/// The game, to be released with [`wordle_free`], or `NULL` if a list is missing, empty or
+/// contains a line that is not a word.
+///
+/// # Safety
+/// `words` and `solutions`, unless `NULL`, must be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_new(words: *const c_char, solutions: *const c_char) -> *mut Wordle {
+    if words.is_null() {
+        return ptr::null_mut();
+    }
+    let Some(words) = read_list(words) else {
+        return ptr::null_mut();
+    };
+    let solutions = if solutions.is_null() {
+        words.clone()
+    } else {
+        match read_list(solutions) {
+            Some(solutions) => solutions,
+            None => return ptr::null_mut(),
+        }
+    };
+    let lists = WordLists::new(words, solutions);
+    // The lists stay in place until `wordle_free` drops the solver before them.
+    let solver = lists.solver();
+    Box::into_raw(Box::new(Wordle { solver, _lists: lists }))
+}
+
+/// Enters the feedback `pattern`, e.g. `"bygby"`, that the game gave for `guess`.
+///
+/// # Returns
+/// The number of solutions that are still possible, or -1 if `guess` is not a word or
+/// `pattern` is not a pattern.
+///
+/// # Safety
+/// `wordle` must come from [`wordle_new`], and `guess` and `pattern` must be valid
+/// NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_feedback(wordle: *mut Wordle, guess: *const c_char,
+                                         pattern: *const c_char) -> c_int {
+    if wordle.is_null() || guess.is_null() || pattern.is_null() {
+        return -1;
+    }
+    let guess = CStr::from_ptr(guess).to_str().ok().and_then(|guess| Word::try_from_str_ignore_case(guess).ok());
+    let pattern = CStr::from_ptr(pattern).to_str().ok()
+        .and_then(|pattern| Pattern::try_from_str(pattern).ok());
+    match (guess, pattern) {
+        (Some(guess), Some(pattern)) => {
+            let solver = &mut (*wordle).solver;
+            solver.feedback(guess, pattern);
+            solver.solution_space().len().try_into().unwrap_or(c_int::MAX)
+        }
+        _ => -1,
+    }
+}
+
+/// Writes the guess with the highest entropy, or the solution once it is known, to `out` as
+/// a NUL-terminated string.
+///
+/// # Returns
+/// 0, or -1 if no word fits the feedback so far or the guess has letters outside ASCII, which
+/// take more than one byte each. In both cases `out` is left unchanged.
+///
+/// # Safety
+/// `wordle` must come from [`wordle_new`], and `out` must have room for [`WORDLE_WORD_LENGTH`] + 1 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_best_guess(wordle: *const Wordle, out: *mut c_char) -> c_int {
+    if wordle.is_null() || out.is_null() || (*wordle).solver.solution_space().is_empty() {
+        return -1;
+    }
+    let guess = (*wordle).solver.best_guess();
+    if !guess.as_bytes().is_ascii() {
+        return -1;
+    }
+    ptr::copy_nonoverlapping(guess.as_bytes().as_ptr().cast::<c_char>(), out, WORD_LENGTH);
+    *out.add(WORD_LENGTH) = 0;
+    0
+}
+
+/// Releases a game created by [`wordle_new`]. Does nothing for `NULL`.
+///
+/// # Safety
+/// `wordle` must come from [`wordle_new`] and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_free(wordle: *mut Wordle) {
+    if wordle.is_null() {
+        return;
+    }
+    drop(Box::from_raw(wordle));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ffi() {
+        unsafe {
+            assert!(wordle_new(c"tears\nnope\n".as_ptr(), ptr::null()).is_null());
+            assert!(wordle_new(c"tears\n".as_ptr(), c"".as_ptr()).is_null());
+            let wordle = wordle_new(c"tears\nbears\ngears\ncrane\nslimy\n".as_ptr(), ptr::null());
+            assert!(!wordle.is_null());
+            assert_eq!(wordle_feedback(wordle, c"crane".as_ptr(), c"BYGBY".as_ptr()), 3);
+            assert_eq!(wordle_feedback(wordle, c"crane".as_ptr(), c"xx".as_ptr()), -1);
+            let mut guess = [1 as c_char; WORD_LENGTH + 1];
+            assert_eq!(wordle_best_guess(wordle, guess.as_mut_ptr()), 0);
+            assert!(Word::try_from_str(CStr::from_ptr(guess.as_ptr()).to_str().unwrap()).is_ok());
+            assert_eq!(wordle_feedback(wordle, c"gears".as_ptr(), c"ggggg".as_ptr()), 1);
+            assert_eq!(wordle_best_guess(wordle, guess.as_mut_ptr()), 0);
+            assert_eq!(CStr::from_ptr(guess.as_ptr()), c"gears");
+            assert_eq!(wordle_feedback(wordle, c"tears".as_ptr(), c"bbbbb".as_ptr()), 0);
+            assert_eq!(wordle_best_guess(wordle, guess.as_mut_ptr()), -1);
+            wordle_free(wordle);
+        }
+    }
+}
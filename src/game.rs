@@ -1,10 +1,36 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
-use std::io::{stdout, Write};
-use rand::Rng;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use rayon::prelude::*;
+use miette::NamedSource;
+use ordered_float::OrderedFloat;
+use sha2::{Digest, Sha256};
+use crate::alphabet::Alphabet;
+use crate::batch::{GameRecord, Manifest};
+use crate::cache::ScoreCache;
+#[cfg(feature = "clipboard")]
+use crate::clipboard::Clipboard;
+use crate::error::{HistoryError, WordleError};
+use crate::input;
+use crate::interrupt::{self, Evaluation};
+use crate::keyboard::Keyboard;
+use crate::matrix::PatternMatrix;
+use crate::output::Verbosity;
+use crate::packed::{score_packed, PackedWord};
 use crate::pattern::{Color, Pattern};
-use crate::word::{Word, WORD_LENGTH};
+use crate::share::{Board, Share};
+use crate::style;
+use crate::transcript::{Mode, Round, Transcript};
+use crate::tree::{DecisionTree, TreeCursor};
+use crate::word::{Word, WordError, WORD_LENGTH};
+use crate::wordlist::{Sharpening, WeightedList};
 
 /// Computes the score of a word given a solution. The rules are as follows:
 /// 1. All positions where the letters of guess and solution are the same,
@@ -13,6 +39,9 @@ use crate::word::{Word, WORD_LENGTH};
 ///    solution. If it does, mark erase it and mark it as yellow.
 /// 3. All remaining position remain black.
 ///
+/// Words of the letters `a` to `z` are scored with the bit operations of [`score_packed`],
+/// words of other alphabets letter by letter.
+///
 /// # Arguments
 ///
 /// * `guess`: The word the user has guessed.
@@ -24,51 +53,387 @@ use crate::word::{Word, WORD_LENGTH};
 /// # Examples
 ///
 /// ```
-/// assert_equals!(
-///     score(Word::from_str("tears"), Word::from_str("bears")),
+/// use wordl_rust_bot::{score, Pattern, Word};
+///
+/// assert_eq!(
+///     score(&Word::from_str("tears"), &Word::from_str("bears")),
 ///     Pattern::from_string("bgggg"));
-///  assert_equals!(
-///     score(Word::from_str("tears"), Word::from_str("stear")),
+/// assert_eq!(
+///     score(&Word::from_str("tears"), &Word::from_str("stear")),
 ///     Pattern::from_string("yyyyy"));
-///  assert_equals!(
-///     score(Word::from_str("atttt"), Word::from_str("txxxx")),
+/// assert_eq!(
+///     score(&Word::from_str("aattt"), &Word::from_str("txxxx")),
 ///     Pattern::from_string("bbybb"));
 /// ```
-fn score(guess: &Word, solution: &Word) -> Pattern {
+pub fn score(guess: &Word, solution: &Word) -> Pattern {
+    if let (Some(guess), Some(solution)) = (PackedWord::ascii(guess), PackedWord::ascii(solution)) {
+        return score_packed(guess, solution);
+    }
     let mut pattern = Pattern::all_black();
-    let mut letter_count: HashMap<char, u8> = HashMap::with_capacity(WORD_LENGTH);
-    for i in 0..WORD_LENGTH {
+    score_codes(guess.as_bytes(), solution.as_bytes(), |i, color| pattern.set(i, color));
+    pattern
+}
+
+/// Computes the feedback of [`score`] for `guess` and `solution` given as the codes of their
+/// letters, see [`Word::as_bytes`], but of any length. `set` is called with the position and
+/// color of every letter that is not black.
+///
+/// # Panics
+/// If `guess` and `solution` have different lengths.
+pub(crate) fn score_codes(guess: &[u8], solution: &[u8], mut set: impl FnMut(usize, Color)) {
+    assert_eq!(guess.len(), solution.len(), "guess and solution must have the same length");
+    // Indexed by the code of the letter.
+    let mut letter_count = [0_u8; 256];
+    for i in 0..guess.len() {
         if guess[i] == solution[i] {
-            pattern.set(i, Color::Green)
+            set(i, Color::Green)
         } else {
-            letter_count.entry(solution[i])
-                .and_modify(|count|{ *count += 1 })
-                .or_insert(1);
+            letter_count[solution[i] as usize] += 1;
         }
     }
 
-    for i in 0..WORD_LENGTH {
-        let count = *letter_count.get(&guess[i]).unwrap_or(&0);
-        let is_yellow = pattern[i] != Color::Green
-            && count > 0;
+    for i in 0..guess.len() {
+        let count = &mut letter_count[guess[i] as usize];
+        let is_yellow = guess[i] != solution[i]
+            && *count > 0;
         if is_yellow {
-            pattern.set(i, Color::Yellow);
-            letter_count.entry(guess[i])
-                .and_modify(|count| {*count -= 1});
+            set(i, Color::Yellow);
+            *count -= 1;
         }
     }
+}
 
-    pattern
+/// A suggested guess together with its entropy, i.e. the expected information (in bits)
+/// the feedback to this guess reveals about the solution, and the number of solutions it
+/// leaves, which is often easier to picture.
+///
+/// # Fields
+/// * `word` - The word that was evaluated.
+/// * `entropy` - The entropy in bits.
+/// * `expected` - The expected number of solutions left after the guess.
+/// * `worst` - The number of solutions left in the worst case, i.e. the largest bucket.
+/// * `probability` - The probability that the word is the solution itself, with all remaining
+///   solutions equally likely unless the game has weights, see [`Solver::with_weights`]. It is
+///   0 for words outside the solution space.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Eval<'a> {
+    pub word: &'a Word,
+    pub entropy: f64,
+    pub expected: f64,
+    pub worst: usize,
+    pub probability: f64,
 }
 
-struct Eval<'a> {
-    word: &'a Word,
-    entropy: f64,
+impl<'a> Eval<'a> {
+    /// Evaluates `word` from the bucket sizes `pattern_count` of its feedback over a solution
+    /// space of `total` words, see [`pattern_counts`].
+    fn from_counts(word: &'a Word, pattern_count: &[u32; Pattern::MAX], total: usize) -> Eval<'a> {
+        let squares = pattern_count.iter().map(|count| *count as f64 * *count as f64).sum::<f64>();
+        Eval {
+            word,
+            entropy: entropy_of_counts(pattern_count, total),
+            expected: squares / total.max(1) as f64,
+            worst: pattern_count.iter().max().copied().unwrap_or(0) as usize,
+            // Only the word itself is all green, so this bucket says whether it is a solution.
+            probability: pattern_count[Pattern::all_green().index()] as f64 / total.max(1) as f64,
+        }
+    }
+
+    /// Like [`Eval::from_counts`], but with the solutions weighted by how likely they are:
+    /// `bucket_weights` holds the total weight of the solutions in each bucket. The entropy and
+    /// the probability follow the weights, the expected number of solutions left is the bucket
+    /// size averaged by them.
+    fn from_weights(word: &'a Word, pattern_count: &[u32; Pattern::MAX],
+                    bucket_weights: &[f64; Pattern::MAX]) -> Eval<'a> {
+        let total = bucket_weights.iter().sum::<f64>();
+        let share = |weight: f64| if total > 0.0 { weight / total } else { 0.0 };
+        Eval {
+            word,
+            entropy: -bucket_weights.iter()
+                .map(|weight| share(*weight))
+                .filter(|p| *p > 0.0)
+                .map(|p| p * p.log2())
+                .sum::<f64>(),
+            expected: pattern_count.iter().zip(bucket_weights).map(|(count, weight)| *count as f64 * share(*weight)).sum(),
+            worst: pattern_count.iter().max().copied().unwrap_or(0) as usize,
+            probability: share(bucket_weights[Pattern::all_green().index()]),
+        }
+    }
+
+    /// The objective of [`Strategy::Composite`]: the entropy plus `weight` times the
+    /// probability of winning with this guess right away.
+    pub fn composite(&self, weight: f64) -> f64 {
+        self.entropy + weight * self.probability
+    }
 }
 
 impl Display for Eval<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} ({:.3})", self.word, self.entropy)
+        write!(f, "{} ({:.3}, ~{:.1} left, max {}", self.word, self.entropy, self.expected, self.worst)?;
+        match self.probability {
+            0.0 => write!(f, ")"),
+            p if p < 0.001 => write!(f, ", <0.1% answer)"),
+            p => write!(f, ", {:.1}% answer)", 100.0 * p),
+        }
+    }
+}
+
+/// A guess whose entropy was estimated from a sample of the solution space, see
+/// [`Solver::estimates`].
+///
+/// # Fields
+/// * `word` - The word that was evaluated.
+/// * `entropy` - The estimated entropy in bits.
+/// * `margin` - The half-width of the 95% confidence interval around `entropy`. It is 0 if
+///   the whole solution space was scored.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Estimate<'a> {
+    pub word: &'a Word,
+    pub entropy: f64,
+    pub margin: f64,
+}
+
+impl Display for Estimate<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.margin > 0.0 {
+            write!(f, "{} ({:.3} ± {:.3})", self.word, self.entropy, self.margin)
+        } else {
+            write!(f, "{} ({:.3})", self.word, self.entropy)
+        }
+    }
+}
+
+/// How much of the word list a ranking within a time budget covers, see
+/// [`Solver::suggestions_within`].
+///
+/// # Variants
+/// * `All` - Every allowed word was ranked.
+/// * `Candidates` - Ranking every word would have taken too long, so only the words that can
+///   still be the solution were ranked, along with a first chunk of the words covering the
+///   most common letters.
+/// * `Partial` - The time ran out, or the scan was interrupted, after `scored` words, those
+///   covering the most common letters first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coverage {
+    All,
+    Candidates,
+    Partial { scored: usize },
+}
+
+/// How the solver picks its next guess.
+///
+/// # Variants
+/// * `Entropy` - The guess with the highest entropy, i.e. the most expected information.
+/// * `Minimax` - The guess whose largest feedback bucket is smallest, which does best in the
+///   worst case.
+/// * `Frequency` - The remaining candidate covering the most common letters among the
+///   remaining candidates, a strategy close to how many people play.
+/// * `MonteCarlo` - Among the guesses with the highest entropy, the one after which
+///   `Frequency` needs the fewest guesses on average, estimated by playing `rollouts` games
+///   against sampled solutions. More rollouts estimate better, but take longer.
+/// * `Beam` - The guess with the fewest expected guesses, found by a search `depth` guesses
+///   deep that only follows the `width` guesses with the highest entropy after each feedback,
+///   and estimates the guesses needed beyond. Wider and deeper searches find better guesses,
+///   but take longer.
+/// * `Composite` - The guess with the highest entropy plus `weight` times the probability that
+///   it is the solution, see [`Eval::composite`]. Pure entropy ignores the chance to win right
+///   away, and so keeps splitting the last few solutions instead of guessing one of them.
+/// * `Partitions` - The guess splitting the remaining solutions into the most groups with
+///   different feedback, then the one with the smallest largest group. Counting the groups
+///   skips the logarithms of the entropy, which makes it a cheap baseline.
+/// * `Positional` - The remaining candidate whose letters are most common at their positions
+///   among the remaining candidates. It takes time linear in the number of candidates, so it
+///   suits word lists too large for the other strategies.
+/// * `Random` - A remaining candidate drawn uniformly at random, as a baseline for the others.
+///   The draw is seeded by the remaining candidates, and in simulations also by the seed of
+///   the game (see [`SimulatedGame::with_seed`]), so that results can be reproduced.
+/// * `Noisy` - Like a human player, who mostly finds a good guess but sometimes goes with a
+///   plausible one: with probability `error_rate`, one of the remaining candidates covering
+///   the most common letters, and otherwise the guess with the highest entropy. Like
+///   `Random`, it is seeded by the remaining candidates and the seed of the game, so within a
+///   game it makes the same choice whenever the same candidates remain, but every game of a
+///   batch run errs independently.
+///
+/// Parameters that are numbers with a fraction are kept as [`OrderedFloat`]s, so that
+/// strategies can be compared and hashed, e.g. to key results by strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum Strategy {
+    Entropy,
+    Minimax,
+    Frequency,
+    MonteCarlo { rollouts: usize },
+    Beam { width: usize, depth: usize },
+    Composite { weight: OrderedFloat<f64> },
+    Partitions,
+    Positional,
+    Random,
+    Noisy { error_rate: OrderedFloat<f64> },
+}
+
+impl Strategy {
+    /// The number of rollouts per guess of [`Strategy::MonteCarlo`] unless set otherwise.
+    pub const ROLLOUTS: usize = 100;
+
+    /// The number of guesses per feedback [`Strategy::Beam`] follows unless set otherwise.
+    pub const BEAM_WIDTH: usize = 8;
+
+    /// The number of guesses [`Strategy::Beam`] looks ahead unless set otherwise.
+    pub const BEAM_DEPTH: usize = 2;
+
+    /// The weight of winning right away of [`Strategy::Composite`] unless set otherwise.
+    pub const WIN_WEIGHT: f64 = 1.0;
+
+    /// The probability of a plausible instead of a good guess of [`Strategy::Noisy`] unless
+    /// set otherwise.
+    pub const ERROR_RATE: f64 = 0.2;
+
+    /// All strategies, in the order they are listed in the command line help.
+    pub const ALL: [Strategy; 10] = [Strategy::Entropy, Strategy::Minimax, Strategy::Frequency,
+                                    Strategy::MonteCarlo { rollouts: Strategy::ROLLOUTS },
+                                    Strategy::Beam { width: Strategy::BEAM_WIDTH, depth: Strategy::BEAM_DEPTH },
+                                    Strategy::Composite { weight: OrderedFloat(Strategy::WIN_WEIGHT) }, Strategy::Partitions,
+                                    Strategy::Positional, Strategy::Random,
+                                    Strategy::Noisy { error_rate: OrderedFloat(Strategy::ERROR_RATE) }];
+
+    /// This strategy with `rollouts` rollouts per guess if it is [`Strategy::MonteCarlo`].
+    pub fn with_rollouts(self, rollouts: usize) -> Strategy {
+        match self {
+            Strategy::MonteCarlo { .. } => Strategy::MonteCarlo { rollouts },
+            strategy => strategy,
+        }
+    }
+
+    /// This strategy following `width` guesses `depth` guesses deep if it is
+    /// [`Strategy::Beam`].
+    pub fn with_beam(self, width: usize, depth: usize) -> Strategy {
+        match self {
+            Strategy::Beam { .. } => Strategy::Beam { width, depth },
+            strategy => strategy,
+        }
+    }
+
+    /// This strategy weighing winning right away with `weight` if it is
+    /// [`Strategy::Composite`].
+    pub fn with_win_weight(self, weight: f64) -> Strategy {
+        match self {
+            Strategy::Composite { .. } => Strategy::Composite { weight: OrderedFloat(weight) },
+            strategy => strategy,
+        }
+    }
+
+    /// This strategy making a plausible instead of a good guess with probability
+    /// `error_rate` if it is [`Strategy::Noisy`].
+    pub fn with_error_rate(self, error_rate: f64) -> Strategy {
+        match self {
+            Strategy::Noisy { .. } => Strategy::Noisy { error_rate: OrderedFloat(error_rate) },
+            strategy => strategy,
+        }
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Strategy::Entropy => "entropy",
+            Strategy::Minimax => "minimax",
+            Strategy::Frequency => "frequency",
+            Strategy::MonteCarlo { .. } => "montecarlo",
+            Strategy::Beam { .. } => "beam",
+            Strategy::Composite { .. } => "composite",
+            Strategy::Partitions => "partitions",
+            Strategy::Positional => "positional",
+            Strategy::Random => "random",
+            Strategy::Noisy { .. } => "noisy",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Strategy, String> {
+        Strategy::ALL.into_iter()
+            .find(|strategy| strategy.to_string() == name.trim().to_lowercase())
+            .ok_or_else(|| {
+                let names = Strategy::ALL.map(|s| s.to_string());
+                format!("unknown strategy <{}>, expected one of {}", name, names.join(", "))
+            })
+    }
+}
+
+/// What a game does with a guess that is not in its word list.
+///
+/// # Variants
+/// * `Accept` - Take it without comment.
+/// * `Warn` - Take it, but point out that the real game would refuse it.
+/// * `Reject` - Ask for another guess, as the real game does.
+///
+/// Unless the check is `Accept`, guesses with letters that no word of the list has are always
+/// refused: in a Russian list, a Latin `a` typed by mistake looks like a Cyrillic `а`, but
+/// would be scored as a different letter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuessCheck {
+    Accept,
+    Warn,
+    Reject,
+}
+
+impl GuessCheck {
+    /// Checks `guess` against `words` and tells the user if it is not in the list.
+    ///
+    /// # Returns
+    /// Whether the guess is taken.
+    fn check(self, words: &[Word], guess: &Word) -> bool {
+        if self == GuessCheck::Accept || words.contains(guess) {
+            return true;
+        }
+        let alphabet = Alphabet::of(words);
+        if let Some(letter) = alphabet.missing(guess) {
+            println!("{} contains '{}', which is not in the alphabet of the word list ({}).", style::bold(guess),
+                     letter, alphabet);
+            return false;
+        }
+        println!("{} is not in the word list{}", style::bold(guess), match self {
+            GuessCheck::Reject => ".",
+            _ => ", the real game would refuse it.",
+        });
+        self != GuessCheck::Reject
+    }
+}
+
+/// Estimates the entropy of `word` over a solution space of `population` words from the
+/// patterns it produces on `sample`, a uniform sample without replacement of that space.
+///
+/// The plug-in entropy of a sample underestimates the true entropy, because rare patterns
+/// are likely to be missed. This is corrected for by the Miller–Madow term `(k - 1) / 2m`,
+/// where `k` is the number of patterns seen and `m` the sample size. The confidence interval
+/// uses the normal approximation of the plug-in estimator, whose variance is
+/// `(Σ p·log2(p)² - H²) / m`, shrunk by the finite population correction.
+fn estimate<'a>(word: &'a Word, sample: &[&Word], population: usize) -> Estimate<'a> {
+    let pattern_count = pattern_counts(word, sample);
+    let m = sample.len();
+    let plug_in = entropy_of_counts(&pattern_count, m);
+    if m >= population {
+        return Estimate { word, entropy: plug_in, margin: 0.0 };
+    }
+    let seen = pattern_count.iter().filter(|count| **count > 0).count();
+    let bias = (seen - 1) as f64 / (2.0 * m as f64 * std::f64::consts::LN_2);
+    let maximum = (population.min(Pattern::MAX) as f64).log2();
+    let second_moment = pattern_count.iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / m as f64;
+            p * p.log2() * p.log2()
+        })
+        .sum::<f64>();
+    let correction = (population - m) as f64 / (population - 1) as f64;
+    let variance = (second_moment - plug_in * plug_in).max(0.0) / m as f64 * correction;
+    Estimate {
+        word,
+        entropy: (plug_in + bias).min(maximum),
+        margin: 1.96 * variance.sqrt(),
     }
 }
 
@@ -89,11 +454,12 @@ impl Display for Eval<'_> {
 /// Returns an `Eval` struct containing:
 /// * `word` - The word that was evaluated.
 /// * `entropy` - A `f64` representing the entropy (information gain) of the word.
+/// * `expected`, `worst` - The expected and the largest number of solutions left.
 ///
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// let word = "crane";
 /// let solution_space = vec![&"apple", &"grape", &"flint"];
 /// let evaluation = entropy(word, &solution_space);
@@ -105,19 +471,99 @@ impl Display for Eval<'_> {
 /// # See Also
 ///
 /// * [`score`] - Function that computes the result pattern between two words.
-fn entropy<'a>(word: &'a Word, solution_space: &Vec<&Word>) -> Eval<'a> {
+fn entropy<'a>(word: &'a Word, solution_space: &[&Word]) -> Eval<'a> {
+    Eval::from_counts(word, &pattern_counts(word, solution_space), solution_space.len())
+}
+
+/// Calculates the entropy of the distribution given by the bucket sizes `pattern_count`,
+/// which add up to `total`.
+fn entropy_of_counts(pattern_count: &[u32; Pattern::MAX], total: usize) -> f64 {
+    -pattern_count.iter().map(
+        |count| if *count > 0 {
+            let p = *count as f64 / total as f64;
+            p * p.log2()
+        } else { 0.0 }
+    ).sum::<f64>()
+}
+
+/// Partitions the solution space by the pattern each solution would produce for `word`,
+/// and counts the size of every bucket. The array is indexed by [`Pattern::index`].
+fn pattern_counts(word: &Word, solution_space: &[&Word]) -> [u32; Pattern::MAX] {
     let mut pattern_count = [0_u32; Pattern::MAX];
     for solution in solution_space {
-        let result = score(&word, solution);
+        let result = score(word, solution);
         pattern_count[result.index()] += 1;
     }
-    let entropy = -pattern_count.par_iter().map(
-        |count| if *count > 0 {
-            let p = *count as f64 / solution_space.len() as f64;
-            p * p.log2()
-        } else { 0.0 }
-    ).sum::<f64>();
-    Eval{word, entropy}
+    pattern_count
+}
+
+/// The letter-frequency heuristic of [`Strategy::Frequency`]: for a word, the number of
+/// `candidates` containing each of its distinct letters, summed up.
+fn letter_coverage(candidates: &[&Word]) -> impl Fn(&Word) -> u32 {
+    /// The letters of `word` that do not occur earlier in it.
+    fn distinct_letters(word: &Word) -> impl Iterator<Item = char> {
+        let letters = word.letters();
+        (0..WORD_LENGTH).filter(move |&i| !letters[..i].contains(&letters[i])).map(move |i| letters[i])
+    }
+    let alphabet = Alphabet::of(candidates.iter().copied());
+    let mut frequency = vec![0_u32; alphabet.len()];
+    for word in candidates {
+        for letter in distinct_letters(word) {
+            frequency[alphabet.index(letter).unwrap()] += 1;
+        }
+    }
+    move |word: &Word| {
+        distinct_letters(word).filter_map(|letter| alphabet.index(letter)).map(|l| frequency[l]).sum::<u32>()
+    }
+}
+
+/// The positional letter-frequency heuristic of [`Strategy::Positional`]: for a word, the
+/// number of `candidates` with the same letter at each of its positions, summed up.
+fn positional_frequency(candidates: &[&Word]) -> impl Fn(&Word) -> u32 {
+    let alphabet = Alphabet::of(candidates.iter().copied());
+    let mut frequency = vec![[0_u32; WORD_LENGTH]; alphabet.len()];
+    for word in candidates {
+        for (i, letter) in word.letters().iter().enumerate() {
+            frequency[alphabet.index(*letter).unwrap()][i] += 1;
+        }
+    }
+    move |word: &Word| {
+        word.letters().iter().enumerate()
+            .filter_map(|(i, letter)| alphabet.index(*letter).map(|l| frequency[l][i]))
+            .sum::<u32>()
+    }
+}
+
+/// The information a guess gains on average, in bits, for [`estimated_guesses`]. Good
+/// guesses gain more early on, and less near the end of a game.
+const BITS_PER_GUESS: f64 = 4.0;
+
+/// An estimate of the number of guesses it takes to find the solution among `candidates`
+/// possible solutions: at least the expected number when guessing them one by one and every
+/// guess tells the others apart, which is exact for up to two candidates, and otherwise
+/// the guesses needed to gain the missing information at [`BITS_PER_GUESS`].
+fn estimated_guesses(candidates: usize) -> f64 {
+    let n = candidates as f64;
+    f64::max((2.0 * n - 1.0) / n, 1.0 + n.log2() / BITS_PER_GUESS)
+}
+
+/// Plays a game against `solution`, which must be one of `candidates`, guessing `guess`
+/// first and then always the remaining candidate covering the most common letters.
+///
+/// # Returns
+/// The number of guesses it took to find the solution.
+fn rollout(guess: &Word, solution: &Word, candidates: &[&Word]) -> usize {
+    let mut guess = *guess;
+    let mut candidates = candidates.to_vec();
+    let mut guesses = 1;
+    while guess != *solution {
+        let pattern = score(&guess, solution);
+        candidates.retain(|word| score(&guess, word) == pattern);
+        let coverage = letter_coverage(&candidates);
+        guess = **candidates.iter().max_by_key(|word| coverage(word)).unwrap();
+        guesses += 1;
+    }
+    guesses
 }
 
 /// Prints the first few elements of a vector, along with the total number of entries.
@@ -135,7 +581,7 @@ fn entropy<'a>(word: &'a Word, solution_space: &Vec<&Word>) -> Eval<'a> {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// let numbers = vec![1, 2, 3, 4, 5, 6];
 /// print_start("Numbers", &numbers, 3);
 /// ```
@@ -147,16 +593,30 @@ fn entropy<'a>(word: &'a Word, solution_space: &Vec<&Word>) -> Eval<'a> {
 ///
 /// In this example, the function prints the first 3 elements of the `numbers` vector, followed by an ellipsis
 /// to indicate that the vector contains more elements.
-fn print_start<T>(name: &str, vector: &Vec<T>, max_length: usize) where T: Display {
+fn print_start<T>(name: &str, vector: &[T], max_length: usize) where T: Display {
+    println!("{}", format_start(name, vector, max_length));
+}
+
+/// Formats a line like [`print_start`] without printing it, e.g. to buffer the output of
+/// games that run in parallel.
+fn format_start<T>(name: &str, vector: &[T], max_length: usize) -> String where T: Display {
     let length = usize::min(max_length, vector.len());
-    print!("\x1b[1m{} ({} entries):\x1b[0m ", name, vector.len());
-    for i in 0..length {
-        print!("{}, ", vector[i]);
+    let mut line = format!("{} ", style::bold(format_args!("{} ({} entries):", name, vector.len())));
+    for element in &vector[..length] {
+        line.push_str(&format!("{}, ", element));
     }
     if length < vector.len() {
-        print!("...");
+        line.push_str("...");
     }
-    println!();
+    line
+}
+
+/// The solution space of a [`Game`] at some round, see [`Game::snapshot`].
+#[derive(Clone)]
+struct Snapshot<'a> {
+    solution_space: Vec<&'a Word>,
+    solution_ids: Vec<u32>,
+    round: u8,
 }
 
 /// Represents the state of a Wordle game.
@@ -172,28 +632,47 @@ fn print_start<T>(name: &str, vector: &Vec<T>, max_length: usize) where T: Displ
 ///   as the game progresses, based on the feedback from guesses.
 /// * `round` - The current round of the game. A round corresponds to a single guess and its feedback.
 ///   Typically, Wordle games last up to six rounds, see [Game::MAX_ROUNDS].
+/// * `buckets` - If enabled with [`Game::cache_buckets`], the [`pattern_counts`] of every word
+///   in `words` over the current `solution_space`, kept up to date by [`Game::filter`].
+/// * `matrix` - If set with [`Game::with_matrix`], the precomputed feedback of every word for
+///   every solution, which is looked up instead of calling [`score`].
+/// * `solution_ids` - The ids of the words in `solution_space` in `matrix`, in the same order.
+///   Empty if there is no matrix.
+/// * `interruptible` - Whether scans over the word list stop early when interrupted, see
+///   [`Game::interruptible`].
+/// * `sharpening` - If set with [`Game::with_sharpening`], how the weights are sharpened
+///   toward common words from round to round.
 ///
 /// # Lifetime Parameters
 ///
 /// * `'a` - The lifetime of the word reference `words`. The references in `solution_space` refer
-///    into `words` and has the same lifetime.
+///   into `words` and has the same lifetime.
 ///
 /// # Example
 ///
-/// ```rust
-/// let game = Game::new(&read_file(File::open("wordle.txt")));
+/// ```ignore
+/// let words = read_words("wordle.txt", File::open("wordle.txt")?)?;
+/// let game = Game::new(&words);
 /// ```
 ///
 /// # See Also
-/// * [crate::read_file] - to obtain word lists for a game.
+/// * [crate::read_words] - to obtain word lists for a game.
 /// * [PlayGame], [SimulatedGame] - structs that use this one.
+#[derive(Clone)]
 struct Game<'a> {
-    words: &'a Vec<Word>,
+    words: &'a [Word],
     solution_space: Vec<&'a Word>,
     round: u8,
+    buckets: Option<Vec<[u32; Pattern::MAX]>>,
+    matrix: Option<Arc<PatternMatrix>>,
+    solution_ids: Vec<u32>,
+    weights: Option<Arc<WeightedList>>,
+    sharpening: Option<Sharpening>,
+    seed: Option<(u64, u64)>,
+    interruptible: bool,
 }
 
-impl Game<'_> {
+impl<'a> Game<'a> {
 
     /// The maximum number of rounds allowed in a Wordle game.
     ///
@@ -205,6 +684,20 @@ impl Game<'_> {
     /// * [`Game::round`] - The current round of the game, which is compared against `MAX_ROUNDS`.
     const MAX_ROUNDS: u8 = 6;
 
+    /// The smallest sample of the solution space [`Game::estimate_words`] estimates an entropy
+    /// from. Below it, the confidence intervals become too wide to rank guesses by.
+    const MIN_SAMPLE: usize = 64;
+
+    /// The number of guesses with the highest entropy [`Strategy::MonteCarlo`] compares.
+    const ROLLOUT_CANDIDATES: usize = 16;
+
+    /// The number of plausible guesses [`Strategy::Noisy`] picks from when it errs.
+    const NOISY_CHOICES: usize = 20;
+
+    /// The number of words [`Game::top_words`] evaluates between checks of its bound, and
+    /// interruptible scans between checks for an interruption.
+    const PRUNE_CHUNK: usize = 256;
+
     /// Creates a new `Game` instance with the given list of words.
     ///
     /// # Arguments
@@ -216,218 +709,3156 @@ impl Game<'_> {
     /// Returns a new instance of `Game` with:
     /// * `words` - Set to the input vector of words.
     /// * `solution_space` - Initially set to include all words from the `words` vector. As the game
-    /// progresses, this solution space can be reduced based on feedback from guesses.
+    ///   progresses, this solution space can be reduced based on feedback from guesses.
     /// * `round` - Initialized to 0.
     ///
     /// # Example
     ///
-    /// ```rust
-    /// let word_list = read_file("wordle.txt");
+    /// ```ignore
+    /// let word_list = read_words("wordle.txt", File::open("wordle.txt")?)?;
     /// let game = Game::new(&word_list);
     /// ```
-    fn new(words: &Vec<Word>) -> Game {
+    fn new(words: &[Word]) -> Game<'_> {
         Game {
             words,
             solution_space: words.iter().collect(),
-            round: 0
+            round: 0,
+            buckets: None,
+            matrix: None,
+            solution_ids: Vec::new(),
+            weights: None,
+            sharpening: None,
+            seed: None,
+            interruptible: false,
         }
     }
 
-    fn evaluate_words(&self) -> Vec<Eval> {
-        let mut evaluation = self.words.par_iter().map(|w| {
-            entropy(w, &self.solution_space)
-        }).collect::<Vec<Eval>>();
-        evaluation.sort_unstable_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
-        evaluation
+    /// Creates a new `Game` where guesses are taken from `words`, but only the words in
+    /// `solutions` are candidates for the solution.
+    fn with_solutions(words: &'a [Word], solutions: &'a [Word]) -> Game<'a> {
+        Game {
+            words,
+            solution_space: solutions.iter().collect(),
+            round: 0,
+            buckets: None,
+            matrix: None,
+            solution_ids: Vec::new(),
+            weights: None,
+            sharpening: None,
+            seed: None,
+            interruptible: false,
+        }
     }
 
-    /// Filters the solution space based on the result of a guess.
+    /// Looks up the feedback of guesses in `matrix` from now on, instead of scoring them.
     ///
-    /// This function refines the game's solution space by eliminating words that do not match the
-    /// feedback pattern from a given guess. After a guess is made and a feedback pattern (green, yellow, gray)
-    /// is received, this function removes words from the solution space that would not produce the same
-    /// feedback pattern if they were the correct solution. The remaining words in the solution space are
-    /// the ones still consistent with the given feedback.
-    ///
-    /// # Arguments
-    /// * `guess` - A reference to the word that was guessed.
-    /// * `result` - The `Pattern` representing the feedback received from the guess (e.g., which letters are
-    ///   correct and in the right position, which are correct but in the wrong position, and which are incorrect).
-    ///
-    /// # See Also
-    /// * [`score`] - Function that compares two words and returns the feedback pattern.
-    fn filter(&mut self, guess: &Word, result: Pattern) {
-        self.solution_space = self.solution_space.par_iter().filter_map(|w| {
-            if score(guess, w) == result {
-                Some(*w)
-            } else {
-                None
-            }
-        }).collect()
+    /// # Panics
+    /// If `matrix` was not built with `words` as guesses, or lacks a word of the solution space.
+    fn with_matrix(mut self, matrix: Arc<PatternMatrix>) -> Game<'a> {
+        assert_eq!(matrix.guesses(), self.words.len(), "the pattern matrix is for a different word list");
+        self.solution_ids = self.solution_space.iter()
+            .map(|w| matrix.solution_id(w).expect("the pattern matrix lacks a solution"))
+            .collect();
+        self.matrix = Some(matrix);
+        self
     }
 
-}
-
-pub struct HelpGame<'a> {
-    game: Game<'a>
-}
+    /// Weighs the solutions by their weight in `weights` from now on, so that common words
+    /// count as more likely answers, see [`Eval::from_weights`]. Solutions missing from the
+    /// list get weight 1, like words without a frequency in it. A uniform list changes nothing.
+    fn with_weights(mut self, weights: Arc<WeightedList>) -> Game<'a> {
+        self.weights = (!weights.is_uniform()).then_some(weights);
+        self
+    }
 
-impl HelpGame<'_> {
-    pub fn new<'a>(words: &'a Vec<Word>) -> HelpGame<'a> {
-        HelpGame { game: Game::new(words) }
+    /// Sharpens the weights toward common words by `sharpening` from round to round. Does
+    /// nothing without weights.
+    fn with_sharpening(mut self, sharpening: Sharpening) -> Game<'a> {
+        self.sharpening = Some(sharpening);
+        self
     }
 
-    fn read() -> (Word, Pattern) {
-        print!("\x1b[1mEnter guessed word:\x1b[0m ");
-        stdout().flush().expect("Could not flush stdout");
-        let guess = Word::read();
-        print!("\x1b[1mEnter resulting pattern:\x1b[0m ");
-        stdout().flush().expect("Could not flush stdout");
-        let pattern = Pattern::read();
-        println!("You have guessed \x1b[1m{}\x1b[0m with result \x1b[1m{}\x1b[0m", guess, pattern);
-        (guess, pattern)
+    /// Lets Ctrl+C cut scans over the word list short, see [`crate::interrupt`]. An interrupted
+    /// ranking only holds the words scored so far, and an interrupted [`Game::cache_buckets`]
+    /// leaves the game without a cache.
+    fn interruptible(mut self) -> Game<'a> {
+        self.interruptible = true;
+        self
     }
 
-    fn round(&mut self) {
-        print_start("Solution Space", &self.game.solution_space, 5);
-        let eval = self.game.evaluate_words();
-        print_start("Suggested Guesses", &eval, 5);
-        let (guess, result) = Self::read();
-        self.game.filter(&guess, result);
-        self.game.round += 1
+    /// Starts a scan over the word list, which can be interrupted if the game is interruptible.
+    fn evaluation(&self) -> Option<Evaluation> {
+        self.interruptible.then(|| Evaluation::start(None))
     }
 
-    pub fn run_game(&mut self) {
-        loop {
-            self.round();
-            if self.game.solution_space.len() == 1 {
-                print!("\x1b[1mSuccess!   →{}.\x1b[0m", self.game.solution_space[0]);
-                break;
-            } else if self.game.solution_space.len() == 0 {
-                print!("\x1b[1mFailure!\x1b[0m   No fitting Word in the list!");
-                break;
-            } else if self.game.round > Game::MAX_ROUNDS {
-                print!("\x1b[1mFailure!\x1b[0m   Rounds exhausted!");
-                break;
-            }
+    /// The [`pattern_counts`] of `guess` over the solution space, read from the pattern
+    /// matrix if there is one and `guess` is in it.
+    fn guess_counts(&self, guess: &Word) -> [u32; Pattern::MAX] {
+        let Some(row) = self.matrix.as_ref().and_then(|m| m.guess_id(guess).map(|g| m.row(g))) else {
+            return pattern_counts(guess, &self.solution_space);
+        };
+        let mut pattern_count = [0_u32; Pattern::MAX];
+        for id in &self.solution_ids {
+            pattern_count[row[*id as usize] as usize] += 1;
         }
-        println!("Score {}", self.game.round);
+        pattern_count
     }
-}
 
-pub struct PlayGame {
-    solution: Word,
-    round: u8,
-}
+    /// The weight of every word of the solution space, in its order and sharpened for the
+    /// current round, or `None` if the game has no weights. Computed once per scan and passed
+    /// on to [`Game::eval`].
+    fn solution_weights(&self) -> Option<Vec<f64>> {
+        self.weights.as_ref().map(|list| self.solution_space.iter().map(|w| {
+            let weight = list.weight(w).unwrap_or(1.0);
+            match self.sharpening {
+                Some(sharpening) => sharpening.sharpen(weight, self.round as usize),
+                None => weight,
+            }
+        }).collect())
+    }
 
-impl PlayGame {
+    /// Like [`Game::guess_counts`], but adds up the `weights` of the solutions in each bucket
+    /// instead of counting them.
+    fn guess_weights(&self, guess: &Word, weights: &[f64]) -> [f64; Pattern::MAX] {
+        let mut bucket_weights = [0.0; Pattern::MAX];
+        match self.matrix.as_ref().and_then(|m| m.guess_id(guess).map(|g| m.row(g))) {
+            Some(row) => for (id, weight) in self.solution_ids.iter().zip(weights) {
+                bucket_weights[row[*id as usize] as usize] += weight;
+            },
+            None => for (solution, weight) in self.solution_space.iter().zip(weights) {
+                bucket_weights[score(guess, solution).index()] += weight;
+            },
+        }
+        bucket_weights
+    }
 
-    pub fn new(words: &Vec<Word>) -> Self {
-        let index = rand::thread_rng().gen_range(0..words.len());
-        PlayGame {
-            solution: words[index],
-            round: 0 }
+    /// Evaluates `word` over the solution space, with the solutions weighted by `weights`
+    /// from [`Game::solution_weights`] if the game has weights.
+    fn eval<'w>(&self, word: &'w Word, weights: Option<&[f64]>) -> Eval<'w> {
+        let counts = self.guess_counts(word);
+        match weights {
+            Some(weights) => Eval::from_weights(word, &counts, &self.guess_weights(word, weights)),
+            None => Eval::from_counts(word, &counts, self.solution_space.len()),
+        }
     }
 
-    fn read() -> Word {
-        print!("\x1b[1mGuess a word:\x1b[0m ");
-        stdout().flush().expect("Could not flush stdout");
-        Word::read()
+    /// Computes the bucket counts of all words once, and from then on updates them
+    /// incrementally whenever the solution space shrinks only by a few words. This trades
+    /// memory (one [`Pattern::MAX`] array per word) for lower latency deep in the game,
+    /// where a filter typically removes far fewer words than it keeps.
+    fn cache_buckets(&mut self) {
+        let scan = self.evaluation();
+        let buckets = self.words.par_iter()
+            .map(|w| (!scan.as_ref().is_some_and(Evaluation::should_stop)).then(|| self.guess_counts(w)))
+            .while_some()
+            .collect::<Vec<[u32; Pattern::MAX]>>();
+        // An interrupted cache lacks words, so the rounds score the words instead.
+        self.buckets = (buckets.len() == self.words.len()).then_some(buckets);
     }
 
-    fn round(&mut self) -> Word {
-        self.round += 1;
-        let guess = Self::read();
-        let result = score(&guess, &self.solution);
-        print!("\x1b[1m→ {}\x1b[0m ", result);
-        guess
+    /// Scores all words, best first. If the game is interruptible, the words are scored in
+    /// chunks, and an interruption returns the ranking of the chunks scored so far.
+    fn evaluate_words(&self) -> Vec<Eval<'a>> {
+        let weights = self.solution_weights();
+        let mut evaluation = match (&self.buckets, &weights) {
+            // The cached buckets only hold counts, which is all an unweighted evaluation needs.
+            (Some(buckets), None) => self.words.par_iter().zip(buckets.par_iter()).map(|(w, counts)| {
+                Eval::from_counts(w, counts, self.solution_space.len())
+            }).collect::<Vec<Eval>>(),
+            _ => {
+                let scan = self.evaluation();
+                let chunk_size = if scan.is_some() { Self::PRUNE_CHUNK } else { self.words.len().max(1) };
+                let mut evaluation = Vec::with_capacity(self.words.len());
+                for chunk in self.words.chunks(chunk_size) {
+                    evaluation.par_extend(chunk.par_iter().map(|w| self.eval(w, weights.as_deref())));
+                    if scan.as_ref().is_some_and(Evaluation::should_stop) {
+                        break;
+                    }
+                }
+                evaluation
+            }
+        };
+        evaluation.sort_unstable_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
+        evaluation
     }
 
-    pub fn run_game(&mut self) {
-        loop {
-            let guess = self.round();
-            if guess == self.solution {
-                println!("\x1b[1mSuccess!   →{}.\x1b[0m", self.solution);
-                break;
-            } else if self.round > Game::MAX_ROUNDS {
-                println!("\x1b[1mFailure!\x1b[0m   Rounds exhausted!");
-                println!("\x1b[1mThe word was {}.\x1b[0m", self.solution);
+    /// Like [`Game::evaluate_words`], but only returns the `count` best words, and skips the
+    /// full evaluation of words that cannot make it among them.
+    ///
+    /// Words are evaluated in chunks, in the order of an upper bound on their entropy (see
+    /// [`Game::entropy_bound`]). Once the bound of the next word is below the entropy of the
+    /// `count`-th best word so far, no remaining word can beat it, and the search stops. The
+    /// bound only holds for unweighted solutions, so with weights all words are evaluated.
+    fn top_words(&self, count: usize) -> Vec<Eval<'a>> {
+        if self.buckets.is_some() || self.weights.is_some() || count >= self.words.len() {
+            let mut evaluation = self.evaluate_words();
+            evaluation.truncate(count);
+            return evaluation;
+        }
+        let scan = self.evaluation();
+        let bound = self.entropy_bound();
+        let mut order = self.words.par_iter().map(|w| (w, bound(w))).collect::<Vec<(&Word, f64)>>();
+        order.sort_unstable_by(|a, b| f64::total_cmp(&b.1, &a.1));
+        let mut evaluation: Vec<Eval<'a>> = Vec::with_capacity(count + Self::PRUNE_CHUNK);
+        for chunk in order.chunks(Self::PRUNE_CHUNK) {
+            if let Some(worst) = evaluation.get(count.saturating_sub(1)) {
+                // Leave some room for rounding, the bound is computed differently.
+                if chunk[0].1 < worst.entropy - 1e-9 {
+                    break;
+                }
+            }
+            evaluation.par_extend(chunk.par_iter().map(|(w, _)| self.eval(w, None)));
+            evaluation.sort_unstable_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
+            evaluation.truncate(count);
+            if scan.as_ref().is_some_and(Evaluation::should_stop) {
                 break;
             }
         }
-        println!("Score {}", self.round);
+        evaluation
     }
 
-}
+    /// An upper bound on the entropy of a word that is much cheaper to compute than the
+    /// entropy itself, for [`Game::top_words`].
+    ///
+    /// The entropy of the feedback is at most the sum of the entropies of its colors at each
+    /// position. For a word without repeated letters, the color at a position only depends on
+    /// whether the solution has the letter there (green), elsewhere (yellow) or not at all
+    /// (black), so these entropies follow from letter counts over the solution space. For words
+    /// with repeated letters, only the trivial bound of `log2` of the number of possible
+    /// feedbacks is used.
+    fn entropy_bound(&self) -> impl Fn(&Word) -> f64 {
+        let total = self.solution_space.len();
+        let alphabet = Alphabet::of(self.solution_space.iter().copied());
+        let mut at_position = vec![[0_u32; WORD_LENGTH]; alphabet.len()];
+        let mut containing = vec![0_u32; alphabet.len()];
+        for word in &self.solution_space {
+            let letters = word.letters();
+            for (i, letter) in letters.iter().enumerate() {
+                let letter = alphabet.index(*letter).unwrap();
+                at_position[letter][i] += 1;
+                if !letters[..i].contains(&letters[i]) {
+                    containing[letter] += 1;
+                }
+            }
+        }
+        let limit = (total.min(Pattern::MAX) as f64).log2();
+        let h = move |count: u32| if count > 0 {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        } else { 0.0 };
+        move |word: &Word| {
+            let letters = word.letters();
+            if (1..WORD_LENGTH).any(|i| letters[..i].contains(&letters[i])) {
+                return limit;
+            }
+            let bound = letters.iter().enumerate().map(|(i, letter)| {
+                let (green, yellow) = match alphabet.index(*letter) {
+                    Some(letter) => (at_position[letter][i], containing[letter] - at_position[letter][i]),
+                    None => (0, 0),
+                };
+                h(green) + h(yellow) + h(total as u32 - green - yellow)
+            }).sum::<f64>();
+            bound.min(limit)
+        }
+    }
 
+    /// Orders all words by a cheap letter-frequency heuristic: a word scores the number of
+    /// remaining candidates containing each of its distinct letters. Words that cover common
+    /// letters tend to split the solution space well, so scoring them first lets the top of
+    /// the ranking settle early.
+    fn heuristic_order(&self) -> Vec<&'a Word> {
+        let heuristic = self.letter_coverage();
+        let mut order = self.words.iter().collect::<Vec<&Word>>();
+        order.sort_by_cached_key(|w| std::cmp::Reverse(heuristic(w)));
+        order
+    }
 
-pub struct SimulatedGame<'a> {
-    game: Game<'a>,
-    guesses: Vec<Word>,
-    solution: Word,
-    first_guess: Word
-}
+    /// The letter-frequency heuristic of [`Game::heuristic_order`] over the remaining
+    /// candidates, see [`letter_coverage`].
+    fn letter_coverage(&self) -> impl Fn(&Word) -> u32 {
+        letter_coverage(&self.solution_space)
+    }
 
-impl SimulatedGame<'_> {
-    pub fn new<'a>(words: &'a Vec<Word>, solution: Word, first_guess: Word) -> SimulatedGame<'a> {
-        SimulatedGame {
-            game: Game::new(words),
-            guesses: Vec::with_capacity(Game::MAX_ROUNDS as usize),
-            solution,
-            first_guess,
+    /// The remaining candidate with the highest [`Game::letter_coverage`].
+    fn frequency_guess(&self) -> Word {
+        let heuristic = self.letter_coverage();
+        **self.solution_space.iter().max_by_key(|w| heuristic(w)).unwrap()
+    }
+
+    /// The next guess according to `strategy`, or the solution itself once it is known.
+    fn guess_with(&self, strategy: Strategy) -> Word {
+        if self.solution_space.len() == 1 {
+            return *self.solution_space[0];
+        }
+        match strategy {
+            Strategy::Entropy => self.best_guess(),
+            Strategy::Minimax => self.minimax_guess(),
+            Strategy::Frequency => self.frequency_guess(),
+            Strategy::MonteCarlo { rollouts } => {
+                let candidates = self.top_words(Self::ROLLOUT_CANDIDATES).into_iter().map(|e| e.word).collect();
+                *self.rollout_ranking(candidates, rollouts)[0].0
+            }
+            Strategy::Beam { width, depth } => *self.beam_ranking(self.beam(width), width, depth)[0].0,
+            Strategy::Composite { weight } => self.composite_guess(weight.0),
+            Strategy::Partitions => self.partitions_guess(),
+            Strategy::Positional => {
+                let heuristic = positional_frequency(&self.solution_space);
+                **self.solution_space.iter().min_by_key(|w| std::cmp::Reverse(heuristic(w))).unwrap()
+            }
+            Strategy::Random => *self.shuffled_solutions()[0],
+            Strategy::Noisy { error_rate } => match self.noisy_error(error_rate.0) {
+                Some(plausible) => *plausible[0],
+                None => self.best_guess(),
+            },
         }
     }
 
-    fn guess(&mut self) -> Word {
-        self.game.round += 1;
-        if self.game.round == 1 {
-            self.first_guess
-        } else if self.game.solution_space.len() == 1 {
-            self.game.solution_space[0].clone()
-        } else {
-            let eval = self.game.evaluate_words();
-            eval.par_iter()
-                .max_by(|a, b| f64::total_cmp(&a.entropy, &b.entropy))
-                .unwrap().word.clone()
+    /// A random number generator seeded by the solution space and the seed of the game, so
+    /// that the same solutions always lead to the same draws within a game, for
+    /// [`Strategy::Random`] and [`Strategy::Noisy`]. The seed is the SHA-256 hash of the seed
+    /// of the game and the solutions, one per line like in [`Manifest::hash`], which unlike
+    /// the hashers of the standard library stays the same between releases.
+    fn solution_rng(&self) -> StdRng {
+        let mut hasher = Sha256::new();
+        if let Some((seed, game)) = self.seed {
+            hasher.update(seed.to_le_bytes());
+            hasher.update(game.to_le_bytes());
         }
+        for word in &self.solution_space {
+            hasher.update(format!("{}\n", word).as_bytes());
+        }
+        StdRng::from_seed(hasher.finalize().into())
     }
 
-    pub fn run_game(&mut self) -> u8 {
-        loop {
-            let guess = self.guess();
-            let result = score(&guess, &self.solution);
-            self.game.filter(&guess, result);
-            self.guesses.push(guess);
-            if guess == self.solution {
-                print_start(format!("Game ({})",
-                                    self.solution).as_str(), &self.guesses, self.guesses.len());
-                return self.game.round;
-            } else if self.game.round > Game::MAX_ROUNDS {
-                print_start(format!("Game ({})",
-                                    self.solution).as_str(), &self.guesses, self.guesses.len());
-                return  Game::MAX_ROUNDS + 1
+    /// The solution space in a random order for [`Strategy::Random`].
+    fn shuffled_solutions(&self) -> Vec<&'a Word> {
+        let mut shuffled = self.solution_space.clone();
+        shuffled.shuffle(&mut self.solution_rng());
+        shuffled
+    }
+
+    /// Whether [`Strategy::Noisy`] errs, with probability `error_rate`, and if so, the
+    /// [`Game::NOISY_CHOICES`] candidates covering the most common letters in a random order
+    /// to pick from instead of the guesses with the highest entropy.
+    fn noisy_error(&self, error_rate: f64) -> Option<Vec<&'a Word>> {
+        let mut rng = self.solution_rng();
+        rng.gen_bool(error_rate.clamp(0.0, 1.0)).then(|| {
+            let heuristic = self.letter_coverage();
+            let mut plausible = self.solution_space.clone();
+            plausible.sort_by_cached_key(|w| std::cmp::Reverse(heuristic(w)));
+            plausible.truncate(Self::NOISY_CHOICES);
+            plausible.shuffle(&mut rng);
+            plausible
+        })
+    }
+
+    /// The guess with the highest [`Eval::composite`] for a non-negative `weight`. Only the
+    /// solutions can win right away, so this is either the guess with the highest entropy or
+    /// the solution with the highest entropy, which is preferred if they are equally good.
+    fn composite_guess(&self, weight: f64) -> Word {
+        let weights = self.solution_weights();
+        let best = self.top_words(1).remove(0);
+        let solution = self.solution_space.par_iter()
+            .map(|w| self.eval(w, weights.as_deref()))
+            .max_by(|a, b| f64::total_cmp(&a.entropy, &b.entropy))
+            .unwrap();
+        if solution.composite(weight) >= best.composite(weight) { *solution.word } else { *best.word }
+    }
+
+    /// If at most `count` solutions are left, adds those not among `candidates` to them, as
+    /// guessing a solution may end the game right away.
+    fn add_last_solutions(&self, candidates: &mut Vec<&'a Word>, count: usize) {
+        if self.solution_space.len() <= count {
+            for solution in &self.solution_space {
+                if !candidates.contains(solution) {
+                    candidates.push(solution);
+                }
             }
         }
+    }
 
+    /// The `width` guesses with the highest entropy, and the last solutions, see
+    /// [`Game::add_last_solutions`].
+    fn beam(&self, width: usize) -> Vec<&'a Word> {
+        let mut beam = self.top_words(width).into_iter().map(|e| e.word).collect();
+        self.add_last_solutions(&mut beam, width);
+        beam
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// Ranks `candidates` by [`Game::expected_guesses`], best first. Ties keep the order of
+    /// `candidates`.
+    fn beam_ranking(&self, candidates: Vec<&'a Word>, width: usize, depth: usize) -> Vec<(&'a Word, f64)> {
+        let mut ranking = candidates.into_par_iter()
+            .map(|guess| (guess, self.expected_guesses(guess, width, depth.max(1))))
+            .collect::<Vec<(&Word, f64)>>();
+        ranking.sort_by(|a, b| f64::total_cmp(&a.1, &b.1));
+        ranking
+    }
 
-    fn assert_score(solution: &str, guess: &str, pattern: &str) {
-        assert_eq!(
-            score(&Word::from_str(solution), &Word::from_str(guess)),
-            Pattern::from_string(pattern)
-        );
+    /// The expected number of guesses to find the solution by guessing `guess` next, from a
+    /// search `depth` guesses deep: after every feedback, the best of the [`Game::beam`] of
+    /// the remaining solutions is guessed. Once the search is `depth` guesses deep, the
+    /// guesses still needed are estimated by [`estimated_guesses`].
+    fn expected_guesses(&self, guess: &Word, width: usize, depth: usize) -> f64 {
+        let total = self.solution_space.len() as f64;
+        let solved = Pattern::all_green().index();
+        let counts = self.guess_counts(guess);
+        1.0 + counts.iter().enumerate().filter(|(pattern, count)| *pattern != solved && **count > 0)
+            .map(|(pattern, &count)| {
+                let guesses = if count <= 2 || depth <= 1 {
+                    estimated_guesses(count as usize)
+                } else {
+                    // Without the cached buckets, which are expensive to copy and update.
+                    let mut next = Game {
+                        words: self.words,
+                        solution_space: self.solution_space.clone(),
+                        round: self.round + 1,
+                        buckets: None,
+                        matrix: self.matrix.clone(),
+                        solution_ids: self.solution_ids.clone(),
+                        weights: self.weights.clone(),
+                        sharpening: self.sharpening,
+                        seed: self.seed,
+                        interruptible: false,
+                    };
+                    next.filter(guess, Pattern::from_index(pattern));
+                    next.beam(width).into_iter()
+                        .map(|guess| next.expected_guesses(guess, width, depth - 1))
+                        .min_by(f64::total_cmp)
+                        .unwrap()
+                };
+                count as f64 / total * guesses
+            }).sum::<f64>()
     }
 
-    #[test]
-    fn test_score() {
-        assert_score("tears", "bears", "bgggg");
-        assert_score("tears", "stear", "yyyyy");
-        assert_score("atttt", "xaaaa", "ybbbb");
-        assert_score("aattt", "txxxx", "bbybb");
+    /// Ranks `candidates` by the average number of guesses it takes to find the solution by
+    /// guessing them first and then always the candidate covering the most common letters, as
+    /// [`Strategy::Frequency`] does. The average is taken over `rollouts` solutions sampled
+    /// from the solution space with a fixed seed, the same for every candidate so that their
+    /// averages are comparable, or over the whole solution space if it is not larger. If
+    /// only a few solutions are left, they are ranked as well.
+    ///
+    /// # Returns
+    /// The candidates with their average number of guesses, best first. Ties keep the order
+    /// of `candidates`.
+    fn rollout_ranking(&self, mut candidates: Vec<&'a Word>, rollouts: usize) -> Vec<(&'a Word, f64)> {
+        let total = self.solution_space.len();
+        self.add_last_solutions(&mut candidates, Self::ROLLOUT_CANDIDATES);
+        let samples = if rollouts >= total {
+            self.solution_space.clone()
+        } else {
+            let mut rng = StdRng::seed_from_u64(total as u64);
+            (0..rollouts.max(1)).map(|_| *self.solution_space.choose(&mut rng).unwrap()).collect()
+        };
+        let mut ranking = candidates.into_par_iter().map(|guess| {
+            let guesses = samples.iter().map(|solution| rollout(guess, solution, &self.solution_space)).sum::<usize>();
+            (guess, guesses as f64 / samples.len() as f64)
+        }).collect::<Vec<(&Word, f64)>>();
+        ranking.sort_by(|a, b| f64::total_cmp(&a.1, &b.1));
+        ranking
+    }
+
+    /// Like [`Game::evaluate_words`], but evaluates the words in chunks of `chunk_size`, in
+    /// [`Game::heuristic_order`]. After each chunk, `progress` is called with the ranking of
+    /// all words scored so far and the number of words scored, so that callers can show a
+    /// provisional result long before the full scan completes. Each chunk is scored in
+    /// parallel, so smaller chunks give more frequent updates at the cost of throughput.
+    ///
+    /// # Returns
+    /// The full ranking, identical to the one of [`Game::evaluate_words`], or if the game is
+    /// interrupted, the ranking of the words scored so far.
+    fn evaluate_progressively<F>(&self, chunk_size: usize, mut progress: F) -> Vec<Eval<'a>>
+    where F: FnMut(&[Eval<'a>], usize) {
+        let scan = self.evaluation();
+        let weights = self.solution_weights();
+        let order = self.heuristic_order();
+        let mut evaluation: Vec<Eval<'a>> = Vec::with_capacity(order.len());
+        for chunk in order.chunks(chunk_size.max(1)) {
+            evaluation.par_extend(chunk.par_iter().map(|w| self.eval(w, weights.as_deref())));
+            evaluation.sort_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
+            progress(&evaluation, evaluation.len());
+            if scan.as_ref().is_some_and(Evaluation::should_stop) {
+                break;
+            }
+        }
+        evaluation
+    }
+
+    /// Like [`Game::evaluate_words`], but computes at most about `budget` scores, so that the
+    /// cost of a suggestion is bounded regardless of the size of the word list.
+    ///
+    /// If the budget allows for scoring every word against the whole solution space, the
+    /// result is exact. Otherwise every word is scored against the same uniform sample of the
+    /// solution space, so that the estimates of different words are comparable. If the budget
+    /// does not even allow for [`Game::MIN_SAMPLE`] scores per word, only the most promising
+    /// words in [`Game::heuristic_order`] are evaluated.
+    ///
+    /// # Returns
+    /// The estimates of the evaluated words, best first.
+    fn estimate_words<R: Rng + ?Sized>(&self, budget: usize, rng: &mut R) -> Vec<Estimate<'a>> {
+        let population = self.solution_space.len();
+        let per_word = budget / self.words.len().max(1);
+        let sample_size = per_word.max(Self::MIN_SAMPLE).min(population);
+        let guesses = if sample_size > per_word {
+            let mut order = self.heuristic_order();
+            order.truncate((budget / sample_size.max(1)).max(1));
+            order
+        } else {
+            self.words.iter().collect()
+        };
+        let sample = self.solution_space.choose_multiple(rng, sample_size).copied().collect::<Vec<&Word>>();
+        let mut estimates = guesses.par_iter()
+            .map(|w| estimate(w, &sample, population))
+            .collect::<Vec<Estimate>>();
+        estimates.sort_unstable_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
+        estimates
+    }
+
+    /// Like [`Game::evaluate_words`], but returns after about `budget`, falling back to fewer
+    /// words if ranking all of them would take longer.
+    ///
+    /// The first chunk of words in [`Game::heuristic_order`] is always scored, and the time it
+    /// takes predicts how many more words fit into the budget: all of them, else only the
+    /// remaining candidates, else the words in heuristic order until the time runs out.
+    ///
+    /// # Returns
+    /// The ranking of the words scored, best first, and which words these are.
+    fn evaluate_within(&self, budget: Duration) -> (Vec<Eval<'a>>, Coverage) {
+        let start = Instant::now();
+        let scan = Evaluation::start(Some(start + budget));
+        let weights = self.solution_weights();
+        let eval = |w: &&'a Word| self.eval(w, weights.as_deref());
+        let order = self.heuristic_order();
+        let (first, rest) = order.split_at(Self::PRUNE_CHUNK.min(order.len()));
+        let mut evaluation = first.par_iter().map(eval).collect::<Vec<Eval>>();
+        let per_word = start.elapsed().as_secs_f64() / first.len().max(1) as f64;
+        let affordable = budget.saturating_sub(start.elapsed()).as_secs_f64() / per_word.max(f64::EPSILON);
+        let (rest, mut coverage) = if rest.len() as f64 <= affordable {
+            (rest.to_vec(), Coverage::All)
+        } else {
+            let scored = first.iter().copied().collect::<HashSet<&Word>>();
+            let candidates = self.solution_space.iter().copied().filter(|w| !scored.contains(w)).collect::<Vec<_>>();
+            match candidates.len() as f64 <= affordable {
+                true => (candidates, Coverage::Candidates),
+                false => (rest.to_vec(), Coverage::Partial { scored: 0 }),
+            }
+        };
+        for chunk in rest.chunks(Self::PRUNE_CHUNK) {
+            if scan.should_stop() {
+                coverage = Coverage::Partial { scored: 0 };
+                break;
+            }
+            evaluation.par_extend(chunk.par_iter().map(eval));
+        }
+        if let Coverage::Partial { scored } = &mut coverage {
+            *scored = evaluation.len();
+        }
+        evaluation.sort_unstable_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
+        (evaluation, coverage)
+    }
+
+    /// The guess with the highest entropy, or the solution itself once it is known.
+    fn best_guess(&self) -> Word {
+        if self.solution_space.len() == 1 {
+            *self.solution_space[0]
+        } else {
+            *self.top_words(1)[0].word
+        }
+    }
+
+    /// Prints how `guess` partitions the solution space: every pattern it can get, with the
+    /// number and share of the solutions that would remain and a few of them, largest first.
+    fn explain(&self, guess: &Word) {
+        let mut buckets = vec![Vec::new(); Pattern::MAX];
+        for solution in &self.solution_space {
+            buckets[score(guess, solution).index()].push(*solution);
+        }
+        let mut buckets = buckets.into_iter().enumerate()
+            .filter(|(_, bucket)| !bucket.is_empty())
+            .collect::<Vec<(usize, Vec<&Word>)>>();
+        buckets.sort_by_key(|(_, bucket)| std::cmp::Reverse(bucket.len()));
+        let total = self.solution_space.len();
+        println!("{}", style::bold(format_args!("{} splits {} solutions into {} patterns ({:.3} bits):",
+                                                guess, total, buckets.len(),
+                                                entropy(guess, &self.solution_space).entropy)));
+        for (index, bucket) in buckets {
+            let examples = bucket.iter().take(5).map(|w| w.to_string()).collect::<Vec<String>>();
+            println!("{} {:>5} {:>5.1}%  {}{}", Pattern::from_index(index), bucket.len(),
+                     100.0 * bucket.len() as f64 / total as f64, examples.join(", "),
+                     if bucket.len() > examples.len() { ", ..." } else { "" });
+        }
+    }
+
+    /// Filters the solution space based on the result of a guess.
+    ///
+    /// This function refines the game's solution space by eliminating words that do not match the
+    /// feedback pattern from a given guess. After a guess is made and a feedback pattern (green, yellow, gray)
+    /// is received, this function removes words from the solution space that would not produce the same
+    /// feedback pattern if they were the correct solution. The remaining words in the solution space are
+    /// the ones still consistent with the given feedback.
+    ///
+    /// # Arguments
+    /// * `guess` - A reference to the word that was guessed.
+    /// * `result` - The `Pattern` representing the feedback received from the guess (e.g., which letters are
+    ///   correct and in the right position, which are correct but in the wrong position, and which are incorrect).
+    ///
+    /// # See Also
+    /// * [`score`] - Function that compares two words and returns the feedback pattern.
+    fn filter(&mut self, guess: &Word, result: Pattern) {
+        let removed = match self.matrix.clone() {
+            Some(matrix) => {
+                let row = matrix.guess_id(guess).map(|g| matrix.row(g));
+                let (kept, removed): (Vec<_>, Vec<_>) = self.solution_space.par_iter()
+                    .copied()
+                    .zip(self.solution_ids.par_iter().copied())
+                    .partition(|(w, id)| match row {
+                        Some(row) => row[*id as usize] as usize == result.index(),
+                        None => score(guess, w) == result,
+                    });
+                (self.solution_space, self.solution_ids) = kept.into_iter().unzip();
+                removed.into_iter().map(|(w, _)| w).collect()
+            }
+            None => {
+                let (kept, removed): (Vec<&Word>, Vec<&Word>) = self.solution_space.par_iter()
+                    .partition(|w| score(guess, w) == result);
+                self.solution_space = kept;
+                removed
+            }
+        };
+        if let Some(buckets) = &mut self.buckets {
+            if removed.len() <= self.solution_space.len() {
+                buckets.par_iter_mut().zip(self.words.par_iter()).for_each(|(counts, w)| {
+                    for r in &removed {
+                        counts[score(w, r).index()] -= 1;
+                    }
+                });
+            } else {
+                self.cache_buckets();
+            }
+        }
+    }
+
+    /// The current solution space, to go back to with [`Game::restore`].
+    fn snapshot(&self) -> Snapshot<'a> {
+        Snapshot {
+            solution_space: self.solution_space.clone(),
+            solution_ids: self.solution_ids.clone(),
+            round: self.round,
+        }
+    }
+
+    /// Goes back to the solution space of `snapshot`, which must have been taken earlier in
+    /// this game, and adds the solutions that come back to the cached buckets.
+    fn restore(&mut self, snapshot: Snapshot<'a>) {
+        // Filtering keeps the order, so the current space is a subsequence of the snapshot.
+        let mut current = self.solution_space.iter().peekable();
+        let added: Vec<&Word> = snapshot.solution_space.iter().copied().filter(|word| {
+            let kept = current.peek().is_some_and(|kept| std::ptr::eq(**kept, *word));
+            if kept {
+                current.next();
+            }
+            !kept
+        }).collect();
+        let Snapshot { solution_space, solution_ids, round } = snapshot;
+        (self.solution_space, self.solution_ids, self.round) = (solution_space, solution_ids, round);
+        if let Some(buckets) = &mut self.buckets {
+            buckets.par_iter_mut().zip(self.words.par_iter()).for_each(|(counts, w)| {
+                for a in &added {
+                    counts[score(w, a).index()] += 1;
+                }
+            });
+        }
+    }
+
+    /// Finds the guess whose largest pattern bucket is smallest, i.e. the guess that does
+    /// best against an adversarial host. Among equally good guesses, words that may still
+    /// be the solution are preferred.
+    fn minimax_guess(&self) -> Word {
+        *self.words.par_iter().min_by_key(|w| {
+            let largest = *self.guess_counts(w).iter().max().unwrap();
+            (largest, !self.solution_space.contains(w))
+        }).unwrap()
+    }
+
+    /// Finds the guess whose feedback splits the solution space into the most groups, then
+    /// the one with the smallest largest group. Among equally good guesses, words that may
+    /// still be the solution are preferred.
+    fn partitions_guess(&self) -> Word {
+        *self.words.par_iter().min_by_key(|w| {
+            let counts = self.guess_counts(w);
+            let partitions = counts.iter().filter(|count| **count > 0).count();
+            (std::cmp::Reverse(partitions), *counts.iter().max().unwrap(), !self.solution_space.contains(w))
+        }).unwrap()
+    }
+
+    /// The `count` best guesses according to `strategy`, best first. With `history`, only
+    /// guesses consistent with all of its feedback are considered, i.e. words that could be
+    /// the solution as far as the history is concerned, as required in hard mode.
+    fn ranked_guesses(&self, strategy: Strategy, history: Option<&[(Word, Pattern)]>, count: usize) -> Vec<Word> {
+        if self.solution_space.len() == 1 {
+            return vec![*self.solution_space[0]];
+        }
+        let mut allowed = self.words.par_iter()
+            .filter(|w| history.is_none_or(|history| history.iter().all(|(g, p)| score(g, w) == *p)))
+            .collect::<Vec<&Word>>();
+        if allowed.is_empty() {
+            // The remaining solutions are always consistent, even if they are no allowed guesses.
+            allowed = self.solution_space.clone();
+        }
+        let total = self.solution_space.len();
+        let weights = self.solution_weights();
+        let entropy = |w: &&'a Word| self.eval(w, weights.as_deref()).entropy;
+        // Lower keys are better.
+        let mut ranked = match strategy {
+            Strategy::Entropy => allowed.par_iter()
+                .map(|w| (*w, -entropy(w)))
+                .collect::<Vec<(&Word, f64)>>(),
+            Strategy::Minimax => allowed.par_iter().map(|w| {
+                let largest = *self.guess_counts(w).iter().max().unwrap() as f64;
+                (*w, if self.solution_space.contains(w) { largest } else { largest + 0.5 })
+            }).collect(),
+            Strategy::Frequency => {
+                let heuristic = self.letter_coverage();
+                self.solution_space.iter().map(|w| (*w, -(heuristic(w) as f64))).collect()
+            }
+            Strategy::MonteCarlo { rollouts } => {
+                self.rollout_ranking(self.highest_entropy(&allowed, Self::ROLLOUT_CANDIDATES.max(count)), rollouts)
+            }
+            Strategy::Beam { width, depth } => {
+                let mut beam = self.highest_entropy(&allowed, width.max(count));
+                self.add_last_solutions(&mut beam, width);
+                self.beam_ranking(beam, width, depth)
+            }
+            Strategy::Composite { weight } => allowed.par_iter()
+                .map(|w| (*w, -self.eval(w, weights.as_deref()).composite(weight.0)))
+                .collect(),
+            Strategy::Partitions => allowed.par_iter().map(|w| {
+                let counts = self.guess_counts(w);
+                let partitions = counts.iter().filter(|count| **count > 0).count() as f64;
+                // The tie breakers are below 1, so they never outweigh a partition.
+                let largest = *counts.iter().max().unwrap() as f64;
+                let largest = if self.solution_space.contains(w) { largest } else { largest + 0.5 };
+                (*w, -partitions + largest / (total + 1) as f64)
+            }).collect(),
+            Strategy::Positional => {
+                let heuristic = positional_frequency(&self.solution_space);
+                self.solution_space.iter().map(|w| (*w, -(heuristic(w) as f64))).collect()
+            }
+            Strategy::Random => self.shuffled_solutions().into_iter().enumerate().map(|(i, w)| (w, i as f64)).collect(),
+            Strategy::Noisy { error_rate } => match self.noisy_error(error_rate.0) {
+                // The remaining candidates fit all feedback, so they are fine in hard mode.
+                Some(plausible) => plausible.into_iter().enumerate().map(|(i, w)| (w, i as f64)).collect(),
+                None => allowed.par_iter()
+                    .map(|w| (*w, -entropy(w)))
+                    .collect(),
+            },
+        };
+        ranked.sort_by(|a, b| f64::total_cmp(&a.1, &b.1));
+        ranked.into_iter().take(count).map(|(w, _)| *w).collect()
+    }
+
+    /// The `count` words of `allowed` with the highest entropy, best first.
+    fn highest_entropy(&self, allowed: &[&'a Word], count: usize) -> Vec<&'a Word> {
+        let weights = self.solution_weights();
+        let mut by_entropy = allowed.par_iter()
+            .map(|w| (*w, self.eval(w, weights.as_deref()).entropy))
+            .collect::<Vec<(&Word, f64)>>();
+        by_entropy.sort_by(|a, b| f64::total_cmp(&b.1, &a.1));
+        by_entropy.into_iter().take(count).map(|(w, _)| w).collect()
+    }
+
+    /// Finds the guess that narrows the solution space down the least, i.e. the one with the
+    /// most solutions left on average after it, for Antiwordle. Guesses are only taken from
+    /// the solution space, which is exactly the set of words consistent with all feedback so
+    /// far, as hard mode requires.
+    fn slowest_guess(&self) -> Word {
+        **self.solution_space.par_iter().max_by_key(|w| {
+            self.guess_counts(w).iter().map(|count| u64::from(*count) * u64::from(*count)).sum::<u64>()
+        }).unwrap()
+    }
+
+    /// Finds the feedback that keeps the most solutions alive, which is the answer an
+    /// adversarial host (as in Absurdle) gives. Ties are broken in favour of the pattern
+    /// with the lower index, i.e. the one revealing less.
+    fn worst_pattern(&self, guess: &Word) -> Pattern {
+        let counts = self.guess_counts(guess);
+        let (index, _) = counts.iter().enumerate()
+            .rev()
+            .max_by_key(|(_, count)| **count)
+            .unwrap();
+        Pattern::from_index(index)
+    }
+
+}
+
+/// A snapshot of a game in progress: the guesses made so far, in order, together with the
+/// feedback each of them got. It is written as `guess:pattern` pairs separated by commas,
+/// e.g. `crane:bgybb,slimy:bbgyb`, see [`GameState::parse`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameState {
+    pub history: Vec<(Word, Pattern)>,
+}
+
+impl GameState {
+    pub fn new() -> GameState {
+        GameState { history: Vec::new() }
+    }
+
+    /// The number of guesses made so far.
+    pub fn round(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn push(&mut self, guess: Word, pattern: Pattern) {
+        self.history.push((guess, pattern));
+    }
+
+    /// Parses a compact game history of the form `guess:pattern,guess:pattern,...`, e.g.
+    /// `crane:bgybb,slimy:bbgyb`. An empty string is an empty history.
+    ///
+    /// # Errors
+    /// If an entry is not of the form `guess:pattern`, or if a guess or pattern is malformed.
+    /// The error points at the offending entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::GameState;
+    ///
+    /// let state = GameState::parse("crane:bgybb,slimy:bbgyb").unwrap();
+    /// assert_eq!(state.round(), 2);
+    /// assert_eq!(state.to_string(), "crane:bgybb,slimy:bbgyb");
+    /// assert!(GameState::parse("crane").is_err());
+    /// ```
+    pub fn parse(history: &str) -> Result<GameState, HistoryError> {
+        let error = |entry: &str, reason: String| {
+            let start = entry.as_ptr() as usize - history.as_ptr() as usize;
+            HistoryError {
+                src: NamedSource::new("history", history.to_string()),
+                span: (start, entry.len()).into(),
+                reason,
+            }
+        };
+        let mut state = GameState::new();
+        for entry in history.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (guess, pattern) = entry.split_once(':')
+                .ok_or_else(|| error(entry, "not of the form guess:pattern".to_string()))?;
+            let guess = Word::try_from_str_ignore_case(guess).map_err(|e| error(entry, e.to_string()))?;
+            let pattern = Pattern::try_from_str(pattern).map_err(|e| error(entry, e.to_string()))?;
+            state.push(guess, pattern);
+        }
+        Ok(state)
+    }
+
+    /// Summarizes what the feedback so far says about the solution, letter by letter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::GameState;
+    ///
+    /// let constraints = GameState::parse("crane:bgybb").unwrap().constraints();
+    /// assert_eq!(constraints.fixed[1], Some('r'));
+    /// assert_eq!(constraints.misplaced[&'a'], vec![2]);
+    /// assert!(constraints.absent.contains(&'c'));
+    /// ```
+    pub fn constraints(&self) -> Constraints {
+        let mut constraints = Constraints::default();
+        for (guess, pattern) in &self.history {
+            for i in 0..WORD_LENGTH {
+                match pattern[i] {
+                    Color::Green => constraints.fixed[i] = Some(guess.letter(i)),
+                    Color::Yellow => constraints.misplaced.entry(guess.letter(i)).or_default().push(i),
+                    Color::Black => {}
+                }
+            }
+        }
+        for (guess, pattern) in &self.history {
+            for i in 0..WORD_LENGTH {
+                let letter = guess.letter(i);
+                if pattern[i] == Color::Black
+                    && !constraints.fixed.contains(&Some(letter))
+                    && !constraints.misplaced.contains_key(&letter) {
+                    constraints.absent.insert(letter);
+                }
+            }
+        }
+        for positions in constraints.misplaced.values_mut() {
+            positions.sort_unstable();
+            positions.dedup();
+        }
+        constraints
+    }
+}
+
+/// What the feedback of a game says about the solution, see [`GameState::constraints`].
+///
+/// # Fields
+/// * `fixed` - The letters known for each position (green feedback).
+/// * `misplaced` - Letters known to be in the solution (yellow feedback), together with the
+///   positions they are known not to be in.
+/// * `absent` - Letters known not to be in the solution at all. A letter that got black
+///   feedback next to a green or yellow copy of itself only occurs fewer times than guessed,
+///   and is not listed here.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Constraints {
+    pub fixed: [Option<char>; WORD_LENGTH],
+    pub misplaced: BTreeMap<char, Vec<usize>>,
+    pub absent: BTreeSet<char>,
+}
+
+impl Display for GameState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entries = self.history.iter()
+            .map(|(guess, pattern)| format!("{}:{}", guess, pattern.letters()))
+            .collect::<Vec<String>>();
+        write!(f, "{}", entries.join(","))
+    }
+}
+
+/// The entropy-based solver, for embedding in other programs. Feed it the guesses and the
+/// feedback they got, and ask it for suggestions.
+///
+/// # Lifetime Parameters
+///
+/// * `'a` - The lifetime of the word list the solver was created with.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::{Pattern, Solver, Word};
+///
+/// let words = ["tears", "bears", "gears", "crane"].map(Word::from_str);
+/// let mut solver = Solver::new(&words);
+/// solver.feedback(Word::from_str("crane"), Pattern::from_string("bygby"));
+/// assert_eq!(solver.solution_space().len(), 3);
+/// let best = solver.suggestions(1);
+/// assert_eq!(best.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct Solver<'a> {
+    game: Game<'a>,
+    state: GameState,
+    hard_mode: bool,
+}
+
+impl<'a> Solver<'a> {
+    /// Creates a solver where every word of `words` is both an allowed guess and a
+    /// possible solution.
+    pub fn new(words: &'a [Word]) -> Solver<'a> {
+        Solver { game: Game::new(words), state: GameState::new(), hard_mode: false }
+    }
+
+    /// Creates a solver where guesses are taken from `words`, but only the words in
+    /// `solutions` are candidates for the solution.
+    pub fn with_solutions(words: &'a [Word], solutions: &'a [Word]) -> Solver<'a> {
+        Solver { game: Game::with_solutions(words, solutions), state: GameState::new(), hard_mode: false }
+    }
+
+    /// Only picks guesses that are consistent with all feedback so far, as in hard mode, in
+    /// [`Solver::guess_with`] and [`Solver::ranked_guesses`].
+    pub fn hard_mode(mut self) -> Solver<'a> {
+        self.hard_mode = true;
+        self
+    }
+
+    /// Looks up feedback in `matrix` instead of computing it, which is much faster when many
+    /// solvers run on the same word list, e.g. in batch simulations.
+    ///
+    /// # Panics
+    /// If `matrix` was not built from the word lists of this solver.
+    pub fn with_matrix(mut self, matrix: Arc<PatternMatrix>) -> Solver<'a> {
+        self.game = self.game.with_matrix(matrix);
+        self
+    }
+
+    /// Weighs the solutions by `weights`, e.g. word frequencies read with
+    /// [`WeightedList::read`]: the entropies and the probabilities of the suggestions count
+    /// common words as more likely answers than rare ones.
+    pub fn with_weights(mut self, weights: Arc<WeightedList>) -> Solver<'a> {
+        self.game = self.game.with_weights(weights);
+        self
+    }
+
+    /// Sharpens the weights toward common words as the game goes on, see [`Sharpening`].
+    /// Does nothing without weights.
+    pub fn with_sharpening(mut self, sharpening: Sharpening) -> Solver<'a> {
+        self.game = self.game.with_sharpening(sharpening);
+        self
+    }
+
+    /// Creates a solver and replays the guesses of `state`.
+    pub fn from_state(words: &'a [Word], state: &GameState) -> Solver<'a> {
+        let mut solver = Solver::new(words);
+        for (guess, pattern) in &state.history {
+            solver.feedback(*guess, *pattern);
+        }
+        solver
+    }
+
+    /// Enters the feedback `pattern` that the game gave for `guess`, removing all words from
+    /// the solution space that would have produced a different pattern.
+    pub fn feedback(&mut self, guess: Word, pattern: Pattern) {
+        self.game.filter(&guess, pattern);
+        self.game.round += 1;
+        self.state.push(guess, pattern);
+    }
+
+    /// The words that are still consistent with all feedback so far.
+    pub fn solution_space(&self) -> &[&'a Word] {
+        &self.game.solution_space
+    }
+
+    /// The `count` guesses with the highest entropy, best first.
+    pub fn suggestions(&self, count: usize) -> Vec<Eval<'a>> {
+        self.game.top_words(count)
+    }
+
+    /// The entropy of `guess` over the current solution space, also for words that are not
+    /// in the word list.
+    pub fn entropy(&self, guess: &Word) -> f64 {
+        self.game.eval(guess, self.game.solution_weights().as_deref()).entropy
+    }
+
+    /// The total weight of the solution space, i.e. its size unless the solver has weights,
+    /// see [`Solver::with_weights`].
+    pub fn solution_weight(&self) -> f64 {
+        match self.game.solution_weights() {
+            Some(weights) => weights.iter().sum(),
+            None => self.game.solution_space.len() as f64,
+        }
+    }
+
+    /// Like [`Solver::suggestions`], but scores the most promising guesses first, in chunks of
+    /// `chunk_size` words, and calls `progress` with the ranking so far and the number of
+    /// words scored after every chunk, e.g. to show a provisional ranking.
+    ///
+    /// # Returns
+    /// The ranking of all words, best first.
+    pub fn suggestions_progressively<F>(&self, chunk_size: usize, progress: F) -> Vec<Eval<'a>>
+    where F: FnMut(&[Eval<'a>], usize) {
+        self.game.evaluate_progressively(chunk_size, progress)
+    }
+
+    /// The allowed guesses.
+    pub fn words(&self) -> &'a [Word] {
+        self.game.words
+    }
+
+    /// Like [`Solver::suggestions`], but computes at most about `budget` scores, e.g. to stay
+    /// responsive on slow devices. Entropies are estimated from a random sample of the
+    /// solution space and come with a 95% confidence interval. If the budget is too small to
+    /// sample every word, only the words covering the most common letters are considered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::{Solver, Word};
+    ///
+    /// let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+    /// let solver = Solver::new(&words);
+    /// let exact = solver.estimates(1, 1000, &mut rand::thread_rng());
+    /// assert_eq!(exact[0].margin, 0.0);
+    /// ```
+    pub fn estimates<R: Rng + ?Sized>(&self, count: usize, budget: usize, rng: &mut R) -> Vec<Estimate<'a>> {
+        let mut estimates = self.game.estimate_words(budget, rng);
+        estimates.truncate(count);
+        estimates
+    }
+
+    /// Like [`Solver::suggestions`], but returns after about `budget`, ranking fewer words if
+    /// ranking all of them would take longer: only the candidates, or as many of the words
+    /// covering the most common letters as fit into the budget. Ctrl+C also cuts the ranking
+    /// short, see [`crate::interrupt`].
+    ///
+    /// # Returns
+    /// The `count` best guesses among those ranked, and which words were ranked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use wordl_rust_bot::game::Coverage;
+    /// use wordl_rust_bot::{Solver, Word};
+    ///
+    /// let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+    /// let solver = Solver::new(&words);
+    /// let (suggestions, coverage) = solver.suggestions_within(1, Duration::from_secs(10));
+    /// assert_eq!(coverage, Coverage::All);
+    /// assert_eq!(suggestions[0].entropy, solver.suggestions(1)[0].entropy);
+    /// ```
+    pub fn suggestions_within(&self, count: usize, budget: Duration) -> (Vec<Eval<'a>>, Coverage) {
+        let (mut evaluation, coverage) = self.game.evaluate_within(budget);
+        evaluation.truncate(count);
+        (evaluation, coverage)
+    }
+
+    /// The guess with the highest entropy, or the solution itself once it is known.
+    pub fn best_guess(&self) -> Word {
+        self.game.best_guess()
+    }
+
+    /// The next guess according to `strategy`, or the solution itself once it is known.
+    pub fn guess_with(&self, strategy: Strategy) -> Word {
+        if self.hard_mode {
+            self.ranked_guesses(strategy, 1)[0]
+        } else {
+            self.game.guess_with(strategy)
+        }
+    }
+
+    /// The `count` best guesses according to `strategy`, best first, or just the solution
+    /// once it is known.
+    pub fn ranked_guesses(&self, strategy: Strategy, count: usize) -> Vec<Word> {
+        let history = self.hard_mode.then_some(self.state.history.as_slice());
+        self.game.ranked_guesses(strategy, history, count)
+    }
+
+    /// The guesses and feedback entered so far.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+}
+
+pub struct HelpGame<'a> {
+    game: Game<'a>,
+    chunk_size: Option<usize>,
+    score_budget: Option<usize>,
+    time_budget: Option<Duration>,
+    opening: Option<Vec<(u32, f64)>>,
+    tree: Option<TreeCursor>,
+    state: GameState,
+    /// The solution space and tree position before every round entered, for `undo`.
+    undo: Vec<(Snapshot<'a>, Option<TreeCursor>)>,
+    /// The suggestions of the current round, best first, to rank the guess entered.
+    suggestions: Vec<Word>,
+    /// The rank of every guess entered among the suggestions of its round, see
+    /// [`HelpGame::transcript`].
+    ranks: Vec<Option<usize>>,
+    guess_check: GuessCheck,
+    json: bool,
+    script: bool,
+    explain: bool,
+    #[cfg(feature = "clipboard")]
+    clipboard: Option<Clipboard>,
+}
+
+/// A line entered at the guess prompt of [`HelpGame`]: either a guess, or a command.
+enum HelpCommand {
+    Guess(Word),
+    /// `copy suggestions` (or just `copy`): copies the top suggestions to the clipboard.
+    CopySuggestions,
+    /// `explain WORD` (or just `explain` for the top suggestion): shows how the word splits
+    /// the solution space.
+    Explain(Option<Word>),
+    /// `:save FILE`: saves the game so far, see [`HelpGame::save`].
+    Save(PathBuf),
+    /// `undo`: takes back the last guess and its pattern.
+    Undo,
+}
+
+impl HelpCommand {
+    fn parse(line: &str) -> Result<HelpCommand, WordError> {
+        match line.trim() {
+            "copy" | "copy suggestions" => Ok(HelpCommand::CopySuggestions),
+            "explain" => Ok(HelpCommand::Explain(None)),
+            "undo" => Ok(HelpCommand::Undo),
+            line => match (line.strip_prefix("explain "), line.strip_prefix(":save ")) {
+                (Some(word), _) => Word::try_from_str_ignore_case(word).map(|word| HelpCommand::Explain(Some(word))),
+                (_, Some(path)) => Ok(HelpCommand::Save(PathBuf::from(path.trim()))),
+                _ => Word::try_from_str_ignore_case(line).map(HelpCommand::Guess),
+            },
+        }
+    }
+}
+
+impl HelpGame<'_> {
+    pub fn new<'a>(words: &'a [Word]) -> HelpGame<'a> {
+        let mut game = Game::new(words).interruptible();
+        game.cache_buckets();
+        HelpGame {
+            game,
+            chunk_size: None,
+            score_budget: None,
+            time_budget: None,
+            opening: None,
+            tree: None,
+            state: GameState::new(),
+            undo: Vec::new(),
+            suggestions: Vec::new(),
+            ranks: Vec::new(),
+            guess_check: GuessCheck::Warn,
+            json: false,
+            script: false,
+            explain: false,
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
+        }
+    }
+
+    /// Creates a game that favours latency over throughput: instead of computing the bucket
+    /// cache up front, every round scores the words in chunks of `chunk_size`, most promising
+    /// first, and keeps a provisional ranking on screen while the scan is running.
+    ///
+    /// # Arguments
+    /// * `words` - The list of allowed words.
+    /// * `chunk_size` - The number of words scored in parallel between two updates.
+    pub fn low_latency(words: &[Word], chunk_size: usize) -> HelpGame<'_> {
+        HelpGame {
+            game: Game::new(words).interruptible(),
+            chunk_size: Some(chunk_size),
+            score_budget: None,
+            time_budget: None,
+            opening: None,
+            tree: None,
+            state: GameState::new(),
+            undo: Vec::new(),
+            suggestions: Vec::new(),
+            ranks: Vec::new(),
+            guess_check: GuessCheck::Warn,
+            json: false,
+            script: false,
+            explain: false,
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
+        }
+    }
+
+    /// Creates a game that starts instantly from a [`ScoreCache`] computed for `words` (as both
+    /// guesses and solutions): the suggestions of the first round are read from the cache,
+    /// and later rounds look up feedback in its pattern matrix instead of scoring words.
+    ///
+    /// # Panics
+    /// If `cache` was computed for another word list.
+    pub fn cached<'a>(words: &'a [Word], cache: &ScoreCache) -> HelpGame<'a> {
+        HelpGame {
+            game: Game::new(words).with_matrix(Arc::clone(&cache.matrix)).interruptible(),
+            chunk_size: None,
+            score_budget: None,
+            time_budget: None,
+            opening: Some(cache.ranking.clone()),
+            tree: None,
+            state: GameState::new(),
+            undo: Vec::new(),
+            suggestions: Vec::new(),
+            ranks: Vec::new(),
+            guess_check: GuessCheck::Warn,
+            json: false,
+            script: false,
+            explain: false,
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
+        }
+    }
+
+    /// Creates a game for slow devices, where every round computes at most about
+    /// `score_budget` scores: entropies are estimated from a sample of the solution space and
+    /// shown with their 95% confidence interval, see [`Solver::estimates`].
+    ///
+    /// # Arguments
+    /// * `words` - The list of allowed words.
+    /// * `score_budget` - The number of score computations allowed per round.
+    pub fn budgeted(words: &[Word], score_budget: usize) -> HelpGame<'_> {
+        HelpGame {
+            game: Game::new(words),
+            chunk_size: None,
+            score_budget: Some(score_budget),
+            time_budget: None,
+            opening: None,
+            tree: None,
+            state: GameState::new(),
+            undo: Vec::new(),
+            suggestions: Vec::new(),
+            ranks: Vec::new(),
+            guess_check: GuessCheck::Warn,
+            json: false,
+            script: false,
+            explain: false,
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
+        }
+    }
+
+    /// Creates a game in which computing the suggestions takes about `time_budget` per round
+    /// at most. If ranking all words would take longer, only the candidates, or the words
+    /// covering the most common letters, are ranked, see [`Solver::suggestions_within`].
+    pub fn timed(words: &[Word], time_budget: Duration) -> HelpGame<'_> {
+        HelpGame {
+            chunk_size: None,
+            time_budget: Some(time_budget),
+            ..HelpGame::low_latency(words, Game::PRUNE_CHUNK)
+        }
+    }
+
+    /// Switches to machine-readable output: instead of prompts and colored text, every round
+    /// prints one line with a JSON object holding the solution space size, the top suggestions
+    /// and the current [`Constraints`], and the game ends with a JSON status line. Guesses and
+    /// patterns are still read from standard input, one per line.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// {"round":0,"solution_space":14855,"candidates":["aahed",...],"suggestions":[{"word":"tares","entropy":6.16,"expected":357.7,"worst":1022,"probability":0.00007},...],"constraints":{...}}
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn with_json_output(mut self) -> Self {
+        self.json = true;
+        self
+    }
+
+    /// Switches to plain output for scripts, e.g. when standard input is not a terminal:
+    /// no prompts and no colors. Every round prints one line with the top suggestions,
+    /// separated by spaces, and then reads one line with the guess and its pattern, e.g.
+    /// `crane bgybb`. Malformed lines are reported on standard error and skipped. The game
+    /// ends with one of the lines `solved WORD ROUNDS`, `no_solution ROUNDS`,
+    /// `rounds_exhausted ROUNDS` or, if standard input ends first, `input_ended ROUNDS`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// $ printf 'tares bybbb\n' | wordle-rust-bot assist wordle.txt
+    /// tares lares rales rates ranes
+    /// colin liana aloin pilon pinol
+    /// ```
+    pub fn with_script_output(mut self) -> Self {
+        self.script = true;
+        self
+    }
+
+    /// Shows how the top suggestion splits the solution space every round: how many
+    /// solutions remain for each pattern it can get, see [`Game::explain`]. Any other word
+    /// can be explained by entering `explain WORD` at the guess prompt.
+    pub fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Sets what to do with a guess that is not in the word list. By default, the game warns
+    /// about it.
+    pub fn with_guess_check(mut self, guess_check: GuessCheck) -> Self {
+        self.guess_check = guess_check;
+        self
+    }
+
+    /// Weighs the solutions by `weights`, see [`Solver::with_weights`]. A cached ranking of
+    /// the first round assumes all solutions equally likely, so it is not used then.
+    pub fn with_weights(mut self, weights: Arc<WeightedList>) -> Self {
+        self.game = self.game.with_weights(weights);
+        if self.game.weights.is_some() {
+            self.opening = None;
+        }
+        self
+    }
+
+    /// Sharpens the weights toward common words as the game goes on, see
+    /// [`Solver::with_sharpening`].
+    pub fn with_sharpening(mut self, sharpening: Sharpening) -> Self {
+        self.game = self.game.with_sharpening(sharpening);
+        self
+    }
+
+    /// Suggests the guesses of `tree` as long as the game follows it, instead of computing
+    /// suggestions. Once a guess other than the tree's is made, or the tree has no branch for
+    /// the feedback, the game falls back to its usual evaluation.
+    pub fn with_tree(mut self, tree: Arc<DecisionTree>) -> Self {
+        self.tree = Some(TreeCursor::new(tree));
+        self
+    }
+
+    /// Ranks the words for the suggestions of this round. If the scan is interrupted with
+    /// Ctrl+C or runs out of time, says so and returns the ranking of the words scored so far.
+    fn evaluate_words(&self) -> Vec<Eval<'_>> {
+        let (eval, coverage) = match self.time_budget {
+            Some(budget) => self.game.evaluate_within(budget),
+            None => (self.scan_words(), Coverage::All),
+        };
+        let budget = self.time_budget.unwrap_or_default();
+        let note = match coverage {
+            _ if interrupt::interrupted() => "Interrupted: these are the best of the words scored so far.".to_string(),
+            Coverage::All => return eval,
+            Coverage::Candidates => format!("Over the {:?} budget: ranked only the {} candidates.",
+                                            budget, self.game.solution_space.len()),
+            Coverage::Partial { scored } => format!("Over the {:?} budget: ranked {} of {} words, most common letters first.",
+                                                    budget, scored, self.game.words.len()),
+        };
+        match self.json || self.script {
+            true => eprintln!("{}", note),
+            false => println!("{}", style::dim(note)),
+        }
+        eval
+    }
+
+    fn scan_words(&self) -> Vec<Eval<'_>> {
+        if let Some(ranking) = self.opening.as_ref().filter(|_| self.game.round == 0) {
+            // The cache only keeps the entropies, so the buckets of the words shown are counted.
+            return ranking.iter().take(Verbosity::current().suggestions_shown()).map(|(id, _)| {
+                self.game.eval(&self.game.words[*id as usize], None)
+            }).collect();
+        }
+        let Some(chunk_size) = self.chunk_size.filter(|_| !self.json && !self.script) else {
+            return self.game.top_words(Verbosity::current().suggestions_shown());
+        };
+        let total = self.game.words.len();
+        let eval = self.game.evaluate_progressively(chunk_size, |ranking, scored| {
+            // Without styling, the line could not be overwritten, so there is no progress.
+            if style::enabled() {
+                let top = ranking.iter().take(3).map(|e| e.to_string()).collect::<Vec<String>>();
+                print!("{}{}", style::clear_line(),
+                       style::dim(format_args!("Scored {}/{}: {}", scored, total, top.join(", "))));
+                let _ = std::io::stdout().flush();
+            }
+        });
+        print!("{}", style::clear_line());
+        eval
+    }
+
+    /// Asks for the guess and the pattern of this round, handling the commands entered at the
+    /// guess prompt instead of a guess, see [`HelpCommand`]: `copy suggestions` copies
+    /// `suggestions` to the clipboard, `explain WORD` shows how a word splits the solution
+    /// space, `undo` takes back the last round and `:save FILE` saves the game. If `--explain`
+    /// is on or the output is verbose, the first suggestion is explained right away.
+    ///
+    /// # Returns
+    /// The guess and its pattern, or `None` if the last round was undone instead.
+    fn read(&mut self, suggestions: &[Word]) -> Result<Option<(Word, Pattern)>, WordleError> {
+        if let Some(best) = suggestions.first().filter(|_| self.explain || Verbosity::current().is_verbose()) {
+            self.game.explain(best);
+        }
+        let guess = loop {
+            input::prompt("Enter guessed word:")?;
+            match input::read_parsed(HelpCommand::parse)? {
+                HelpCommand::Guess(guess) if self.guess_check.check(self.game.words, &guess) => break guess,
+                HelpCommand::Guess(_) => {}
+                HelpCommand::CopySuggestions => {
+                    let text = suggestions.iter().map(Word::to_string).collect::<Vec<String>>();
+                    self.copy(&text.join(", "))?
+                }
+                HelpCommand::Explain(word) => match word.or(suggestions.first().copied()) {
+                    Some(word) => self.game.explain(&word),
+                    None => println!("There is no suggestion to explain."),
+                },
+                HelpCommand::Undo => match self.undo() {
+                    Some((guess, pattern)) => {
+                        println!("Took back {} with result {}.", style::bold(guess), style::bold(pattern));
+                        return Ok(None);
+                    }
+                    None => println!("There is no guess to take back."),
+                },
+                HelpCommand::Save(path) => match self.save(&path) {
+                    Ok(()) => println!("Saved the game to {}.", style::bold(path.display())),
+                    Err(e) => println!("Could not save the game: {}.", e),
+                },
+            }
+        };
+        input::prompt("Enter resulting pattern:")?;
+        let pattern = Pattern::read()?;
+        println!("You have guessed {} with result {}", style::bold(guess), style::bold(pattern));
+        if !self.game.solution_space.iter().any(|word| score(&guess, word) == pattern) {
+            return self.resolve_contradiction(guess, pattern);
+        }
+        Ok(Some((guess, pattern)))
+    }
+
+    /// The rounds entered so far, counting from 0, that no word in the list can satisfy
+    /// together with `guess` getting `pattern`: every single round that contradicts it, or
+    /// else the first rounds that do so together. Empty if `guess` cannot get `pattern` at all.
+    fn contradictions(&self, guess: &Word, pattern: Pattern) -> Vec<usize> {
+        let solutions = self.undo.first().map_or(&self.game.solution_space, |(snapshot, _)| &snapshot.solution_space);
+        let candidates: Vec<&Word> = solutions.iter().copied().filter(|w| score(guess, w) == pattern).collect();
+        let fits = |rounds: &[(Word, Pattern)]| {
+            candidates.iter().any(|w| rounds.iter().all(|(guess, pattern)| score(guess, w) == *pattern))
+        };
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+        let history = &self.state.history;
+        let single: Vec<usize> = (0..history.len()).filter(|&i| !fits(&history[i..=i])).collect();
+        if !single.is_empty() {
+            return single;
+        }
+        let end = (1..=history.len()).find(|&end| !fits(&history[..end])).unwrap_or(history.len());
+        (0..end).collect()
+    }
+
+    /// Tells the user which rounds contradict `guess` getting `pattern`, so that no word in
+    /// the list fits anymore, and offers to enter one of them again.
+    ///
+    /// # Returns
+    /// The guess and pattern if the user keeps them, or `None` if a round was entered again.
+    fn resolve_contradiction(&mut self, guess: Word, pattern: Pattern) -> Result<Option<(Word, Pattern)>, WordleError> {
+        let rounds = self.contradictions(&guess, pattern);
+        let this_round = self.state.history.len() + 1;
+        if rounds.is_empty() {
+            println!("No word in the list gets {} for {}.", style::bold(pattern), style::bold(guess));
+        } else {
+            println!("No word in the list fits {} together with",
+                     style::bold(format_args!("{}:{}", guess, pattern.letters())));
+            for &i in &rounds {
+                let (guess, pattern) = self.state.history[i];
+                println!("  round {}: {}:{}", i + 1, guess, pattern.letters());
+            }
+        }
+        let choices = rounds.iter().map(|i| i + 1).chain([this_round]).map(|i| i.to_string()).collect::<Vec<_>>();
+        input::prompt(&format!("Enter a round to enter again ({}), or nothing to keep it:", choices.join(", ")))?;
+        let round = match input::read_line()?.trim().parse::<usize>() {
+            Ok(round) if choices.contains(&round.to_string()) => round,
+            _ => return Ok(Some((guess, pattern))),
+        };
+        if round == this_round {
+            return Ok(None);
+        }
+        let mut later = self.state.history[round..].to_vec();
+        later.push((guess, pattern));
+        while self.state.history.len() >= round {
+            self.undo();
+        }
+        println!("Enter round {} again.", round);
+        input::prompt("Enter guessed word:")?;
+        let guess = input::read_parsed(Word::try_from_str_ignore_case)?;
+        input::prompt("Enter resulting pattern:")?;
+        let pattern = Pattern::read()?;
+        self.record(guess, pattern);
+        for (guess, pattern) in later {
+            if !self.game.solution_space.iter().any(|word| score(&guess, word) == pattern) {
+                println!("{} still fits no word, enter it and the rounds after it again.",
+                         style::bold(format_args!("{}:{}", guess, pattern.letters())));
+                break;
+            }
+            self.record(guess, pattern);
+        }
+        Ok(None)
+    }
+
+    /// Copies `text` to the clipboard. Failing to do so is reported, but does not end the game.
+    #[cfg(feature = "clipboard")]
+    fn copy(&mut self, text: &str) -> Result<(), WordleError> {
+        let result = match &mut self.clipboard {
+            Some(clipboard) => clipboard.copy(text),
+            None => Clipboard::new().and_then(|mut clipboard| {
+                clipboard.copy(text)?;
+                self.clipboard = Some(clipboard);
+                Ok(())
+            }),
+        };
+        match result {
+            Ok(()) => println!("Copied {} to the clipboard.", style::bold(text)),
+            Err(e) => println!("{}.", e),
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    fn copy(&mut self, _text: &str) -> Result<(), WordleError> {
+        println!("Copying needs the `clipboard` feature.");
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    fn read_json() -> Result<(Word, Pattern), WordleError> {
+        fn read<T, E: Display>(parse: impl Fn(&str) -> Result<T, E>) -> Result<T, WordleError> {
+            loop {
+                match parse(input::read_line()?.trim()) {
+                    Ok(value) => return Ok(value),
+                    Err(e) => println!("{}", serde_json::json!({ "error": e.to_string() })),
+                }
+            }
+        }
+        Ok((read(Word::try_from_str_ignore_case)?, read(Pattern::try_from_str)?))
+    }
+
+    #[cfg(feature = "serde")]
+    fn print_json<T: serde::Serialize>(&self, eval: &[T]) {
+        let report = serde_json::json!({
+            "round": self.game.round,
+            "solution_space": self.game.solution_space.len(),
+            "candidates": self.game.solution_space.iter().take(5).collect::<Vec<_>>(),
+            "suggestions": eval.iter().take(5).collect::<Vec<_>>(),
+            "constraints": self.state.constraints(),
+        });
+        println!("{}", report);
+    }
+
+    fn round(&mut self) -> Result<(), WordleError> {
+        if let Some(guess) = self.tree.as_ref().and_then(TreeCursor::guess) {
+            return self.tree_round(guess);
+        }
+        if let Some(budget) = self.score_budget {
+            return self.budgeted_round(budget);
+        }
+        if self.script {
+            let eval = self.evaluate_words();
+            self.suggestions = eval.iter().map(|e| *e.word).collect();
+            return self.script_round(&self.suggestions.clone());
+        }
+        #[cfg(feature = "serde")]
+        if self.json {
+            let eval = self.evaluate_words();
+            let suggestions = eval.iter().map(|e| *e.word).collect();
+            self.print_json(&eval);
+            self.suggestions = suggestions;
+            let (guess, result) = Self::read_json()?;
+            self.record(guess, result);
+            return Ok(());
+        }
+        self.print_solution_space();
+        let eval = self.evaluate_words();
+        print_start("Suggested Guesses", &eval, Verbosity::current().suggestions_shown());
+        self.suggestions = eval.iter().map(|e| *e.word).collect();
+        let suggestions = self.suggestions.iter().take(5).copied().collect::<Vec<Word>>();
+        if let Some((guess, result)) = self.read(&suggestions)? {
+            self.record(guess, result);
+        }
+        Ok(())
+    }
+
+    /// Lists the remaining solutions, as many as the [`Verbosity`] asks for.
+    fn print_solution_space(&self) {
+        let verbosity = Verbosity::current();
+        if !verbosity.is_quiet() {
+            print_start("Solution Space", &self.game.solution_space, verbosity.candidates_shown());
+        }
+    }
+
+    /// A round in which the decision tree of [`HelpGame::with_tree`] suggests `guess`.
+    fn tree_round(&mut self, guess: Word) -> Result<(), WordleError> {
+        self.suggestions = vec![guess];
+        if self.script {
+            return self.script_round(&[guess]);
+        }
+        #[cfg(feature = "serde")]
+        if self.json {
+            self.print_json(&[serde_json::json!({ "word": guess, "source": "tree" })]);
+            let (guess, result) = Self::read_json()?;
+            self.record(guess, result);
+            return Ok(());
+        }
+        self.print_solution_space();
+        println!("{} {}", style::bold("Decision tree:"), guess);
+        if let Some((guess, result)) = self.read(&[guess])? {
+            self.record(guess, result);
+        }
+        Ok(())
+    }
+
+    /// A round of a [`HelpGame::budgeted`] game.
+    fn budgeted_round(&mut self, budget: usize) -> Result<(), WordleError> {
+        let estimates = self.game.estimate_words(budget, &mut rand::thread_rng());
+        self.suggestions = estimates.iter().map(|e| *e.word).collect();
+        if self.script {
+            let suggestions = estimates.iter().take(5).map(|e| *e.word).collect::<Vec<Word>>();
+            return self.script_round(&suggestions);
+        }
+        #[cfg(feature = "serde")]
+        if self.json {
+            self.print_json(&estimates);
+            let (guess, result) = Self::read_json()?;
+            self.record(guess, result);
+            return Ok(());
+        }
+        self.print_solution_space();
+        print_start("Suggested Guesses", &estimates, Verbosity::current().suggestions_shown());
+        let suggestions = estimates.iter().take(5).map(|e| *e.word).collect::<Vec<Word>>();
+        if let Some((guess, result)) = self.read(&suggestions)? {
+            self.record(guess, result);
+        }
+        Ok(())
+    }
+
+    /// A round of a game with [`HelpGame::with_script_output`].
+    fn script_round(&mut self, suggestions: &[Word]) -> Result<(), WordleError> {
+        fn parse(line: &str) -> Result<(Word, Pattern), String> {
+            let mut parts = line.split_whitespace();
+            let (Some(guess), Some(pattern), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(format!("<{}> is not a guess followed by its pattern", line.trim()));
+            };
+            let guess = Word::try_from_str_ignore_case(guess).map_err(|e| e.to_string())?;
+            Ok((guess, Pattern::try_from_str(pattern).map_err(|e| e.to_string())?))
+        }
+        let line = suggestions.iter().take(5).map(Word::to_string).collect::<Vec<String>>();
+        println!("{}", line.join(" "));
+        std::io::stdout().flush()?;
+        let (guess, result) = loop {
+            match parse(&input::read_line()?) {
+                Ok(entry) => break entry,
+                Err(e) => eprintln!("{}", e),
+            }
+        };
+        self.record(guess, result);
+        Ok(())
+    }
+
+    fn record(&mut self, guess: Word, result: Pattern) {
+        self.ranks.push(self.suggestions.iter().position(|word| *word == guess).map(|i| i + 1));
+        self.suggestions.clear();
+        self.undo.push((self.game.snapshot(), self.tree.clone()));
+        if let Some(tree) = &mut self.tree {
+            tree.advance(&guess, result);
+        }
+        self.game.filter(&guess, result);
+        self.game.round += 1;
+        self.state.push(guess, result);
+    }
+
+    /// Prints the end of the game, either as text or as a JSON status line.
+    #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+    fn finish(&self, status: &str, message: &str) {
+        #[cfg(feature = "serde")]
+        if self.json {
+            let solution = self.game.solution_space.first().filter(|_| status == "solved");
+            println!("{}", serde_json::json!({
+                "status": status,
+                "solution": solution,
+                "rounds": self.game.round,
+                "history": self.state.history,
+            }));
+            return;
+        }
+        if self.script {
+            match self.game.solution_space.first().filter(|_| status == "solved") {
+                Some(solution) => println!("{} {} {}", status, solution, self.game.round),
+                None => println!("{} {}", status, self.game.round),
+            }
+            return;
+        }
+        print!("{}", message);
+        println!("Score {}", self.game.round);
+    }
+
+    /// Takes back the last guess and its pattern, going back to the solution space before it.
+    ///
+    /// # Returns
+    /// The guess and pattern taken back, or `None` if no guess was entered yet.
+    pub fn undo(&mut self) -> Option<(Word, Pattern)> {
+        let (snapshot, tree) = self.undo.pop()?;
+        self.ranks.pop();
+        self.game.restore(snapshot);
+        self.tree = tree;
+        self.state.history.pop()
+    }
+
+    /// Writes the game so far to `path`, so that it can be continued later, possibly on
+    /// another machine, with [`HelpGame::resume`]. The file lists the history, e.g.
+    /// `history: crane:bgybb,slimy:bbgyb`, the [`Manifest::hash`] of the word list and the
+    /// solutions that remain.
+    ///
+    /// # Errors
+    /// If the file cannot be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "history: {}", self.state)?;
+        writeln!(out, "word list: {}", Manifest::hash(self.game.words))?;
+        let remaining = self.game.solution_space.iter().map(|word| word.to_string()).collect::<Vec<String>>();
+        writeln!(out, "remaining: {}", remaining.join(" "))?;
+        out.flush()
+    }
+
+    /// Continues a game written by [`HelpGame::save`] by replaying its history. A file with
+    /// just a history such as `crane:bgybb,slimy:bbgyb` works as well. Warns if the game was
+    /// saved with a different word list.
+    ///
+    /// # Errors
+    /// If the file cannot be read or its history is malformed.
+    pub fn resume(mut self, path: &Path) -> Result<Self, WordleError> {
+        let text = fs::read_to_string(path)?;
+        let field = |name: &str| {
+            text.lines().find_map(|line| line.strip_prefix(name)?.strip_prefix(':')).map(str::trim)
+        };
+        let state = GameState::parse(field("history").unwrap_or(text.trim()))?;
+        if field("word list").is_some_and(|hash| hash != Manifest::hash(self.game.words)) {
+            eprintln!("warning: {} was saved with a different word list", path.display());
+        }
+        for &(guess, pattern) in &state.history {
+            self.record(guess, pattern);
+        }
+        Ok(self)
+    }
+
+    /// The solution, once it is the only word left that fits the feedback.
+    pub fn solution(&self) -> Option<Word> {
+        match self.game.solution_space.as_slice() {
+            [solution] => Some(**solution),
+            _ => None,
+        }
+    }
+
+    /// The number of words that still fit the feedback, `0` if no word in the list does.
+    pub fn remaining(&self) -> usize {
+        self.game.solution_space.len()
+    }
+
+    /// The game so far, with the rank of every guess among the suggestions of its round.
+    pub fn transcript(&self) -> Transcript {
+        let rounds = self.state.history.iter().zip(&self.undo).zip(&self.ranks)
+            .map(|((&(guess, pattern), (snapshot, _)), &rank)| Round {
+                guess,
+                pattern,
+                remaining: snapshot.solution_space.len(),
+                rank,
+            })
+            .collect();
+        Transcript { mode: Mode::Assist, solution: self.solution(), solved: self.solution().is_some(), rounds }
+    }
+
+    pub fn run_game(&mut self) -> Result<(), WordleError> {
+        loop {
+            if let Err(error) = self.round() {
+                let message = format!("\n{}   {}\n", style::bold("Input ended."),
+                                      format_start("Solution Space", &self.game.solution_space, 5));
+                return input::end_game(error, || self.finish("input_ended", &message));
+            }
+            if self.game.solution_space.len() == 1 {
+                let message = style::bold(format_args!("Success!   →{}.", self.game.solution_space[0])).to_string();
+                self.finish("solved", &message);
+                break;
+            } else if self.game.solution_space.is_empty() {
+                self.finish("no_solution", &format!("{}   No fitting Word in the list!", style::bold("Failure!")));
+                break;
+            } else if self.game.round > Game::MAX_ROUNDS {
+                self.finish("rounds_exhausted", &format!("{}   Rounds exhausted!", style::bold("Failure!")));
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A line entered at the guess prompt of [`PlayGame`]: either a guess, or a command.
+enum PlayCommand {
+    Guess(Word),
+    /// `hint`: gives some help, more with every hint in the same round.
+    Hint,
+}
+
+impl PlayCommand {
+    fn parse(line: &str) -> Result<PlayCommand, WordError> {
+        match line.trim() {
+            "hint" => Ok(PlayCommand::Hint),
+            line => Word::try_from_str_ignore_case(line).map(PlayCommand::Guess),
+        }
+    }
+}
+
+/// The guess times of a timed game, see [`PlayGame::with_timer`].
+///
+/// # Fields
+/// * `limit` - The longest time a guess may take, if any.
+/// * `times` - The time every guess took, from the prompt to the guess being taken, including
+///   a last guess that took too long.
+/// * `timed_out` - Whether the last guess took longer than `limit`.
+struct Timer {
+    limit: Option<Duration>,
+    times: Vec<Duration>,
+    timed_out: bool,
+}
+
+/// Hosts a normal game of Wordle against a solution picked by the program.
+///
+/// Alongside the player, a [`Game`] is fed the same guesses and feedback, so that the
+/// player can ask for hints at the guess prompt, see [`PlayGame::hint`].
+///
+/// # Fields
+/// * `game` - The solver's view of the game: the word list and the words that still fit.
+/// * `hints` - The number of hints given so far.
+/// * `revealed` - The positions whose letter the player knows, from green feedback or hints.
+/// * `timer` - The guess times, for timed games.
+/// * `transcript` - The rounds played, see [`PlayGame::transcript`].
+pub struct PlayGame<'a> {
+    solution: Word,
+    seed: Option<u64>,
+    game: Game<'a>,
+    guess_check: GuessCheck,
+    practice: bool,
+    round: u32,
+    patterns: Vec<Pattern>,
+    keyboard: Keyboard,
+    hints: u32,
+    revealed: [bool; WORD_LENGTH],
+    timer: Option<Timer>,
+    transcript: Vec<Round>,
+    #[cfg(feature = "clipboard")]
+    copy: bool,
+}
+
+impl PlayGame<'_> {
+
+    pub fn new(words: &[Word]) -> PlayGame<'_> {
+        Self::with_solution(words, words[rand::thread_rng().gen_range(0..words.len())], None)
+    }
+
+    /// A game whose solution is picked by `seed`, so that everyone who plays with the same seed
+    /// and word list gets the same solution, e.g. to share a puzzle with friends.
+    pub fn seeded(words: &[Word], seed: u64) -> PlayGame<'_> {
+        let index = StdRng::seed_from_u64(seed).gen_range(0..words.len());
+        Self::with_solution(words, words[index], Some(seed))
+    }
+
+    fn with_solution(words: &[Word], solution: Word, seed: Option<u64>) -> PlayGame<'_> {
+        PlayGame {
+            solution,
+            seed,
+            game: Game::new(words),
+            guess_check: GuessCheck::Reject,
+            practice: false,
+            round: 0,
+            patterns: Vec::with_capacity(Game::MAX_ROUNDS as usize),
+            keyboard: Keyboard::new(),
+            hints: 0,
+            revealed: [false; WORD_LENGTH],
+            timer: None,
+            transcript: Vec::new(),
+            #[cfg(feature = "clipboard")]
+            copy: false,
+        }
+    }
+
+    /// Copies the share grid to the clipboard at the end of the game, so it can be pasted
+    /// directly into a chat.
+    #[cfg(feature = "clipboard")]
+    pub fn with_copy(mut self) -> Self {
+        self.copy = true;
+        self
+    }
+
+    /// Sets what to do with a guess that is not in the word list. By default, the game asks
+    /// for another guess, as the real game does.
+    pub fn with_guess_check(mut self, guess_check: GuessCheck) -> Self {
+        self.guess_check = guess_check;
+        self
+    }
+
+    /// Lets the game go on until the word is found, however many guesses that takes, for
+    /// practice. The game then reports the number of guesses instead of a share grid.
+    pub fn with_practice(mut self) -> Self {
+        self.practice = true;
+        self
+    }
+
+    /// Times every guess, for speed play, and ends the game if a guess takes longer than
+    /// `limit`. The times are shown after every guess and at the end of the game.
+    ///
+    /// The limit is checked once the guess is entered, as reading input cannot be interrupted:
+    /// a guess that took too long is then discarded, without feedback, and does not count as a
+    /// round.
+    pub fn with_timer(mut self, limit: Option<Duration>) -> Self {
+        self.timer = Some(Timer { limit, times: Vec::new(), timed_out: false });
+        self
+    }
+
+    /// Whether the last guess took longer than the time limit.
+    fn timed_out(&self) -> bool {
+        self.timer.as_ref().is_some_and(|timer| timer.timed_out)
+    }
+
+    /// Whether the last allowed guess has been made. Never the case in practice.
+    fn out_of_rounds(&self) -> bool {
+        !self.practice && self.round >= u32::from(Game::MAX_ROUNDS)
+    }
+
+    /// Reads a guess. Instead of a guess, the user may enter `hint`, as often as they like.
+    fn read(&mut self) -> Result<Word, WordleError> {
+        let mut level = 0;
+        loop {
+            input::prompt("Guess a word:")?;
+            match input::read_parsed(PlayCommand::parse)? {
+                PlayCommand::Guess(guess) if self.guess_check.check(self.game.words, &guess) => return Ok(guess),
+                PlayCommand::Guess(_) => {}
+                PlayCommand::Hint => {
+                    self.hint(level);
+                    level += 1;
+                }
+            }
+        }
+    }
+
+    /// Gives the player a hint, the more revealing the higher `level` is: the first hint of a
+    /// round is the number of words that still fit the feedback, the second one the guess the
+    /// solver would make, and every further one the letter at a position not known yet.
+    fn hint(&mut self, level: u32) {
+        self.hints += 1;
+        match level {
+            0 => println!("{} words still fit the feedback.", self.game.solution_space.len()),
+            1 => println!("The bot would guess {}.", style::bold(self.game.guess_with(Strategy::Entropy))),
+            _ => match self.reveal_letter() {
+                Some(i) => println!("Letter {} is {}.", i + 1, style::bold(self.solution.letter(i))),
+                None => println!("You already know every letter."),
+            },
+        }
+    }
+
+    /// Marks the first position whose letter the player does not know yet as known.
+    ///
+    /// # Returns
+    /// The position, or `None` if the player knows every letter.
+    fn reveal_letter(&mut self) -> Option<usize> {
+        let i = self.revealed.iter().position(|known| !known)?;
+        self.revealed[i] = true;
+        Some(i)
+    }
+
+    /// Scores `guess` and records it: in the transcript, the solver's view of the game, the
+    /// keyboard, and the positions the player knows from green feedback.
+    fn enter(&mut self, guess: Word) -> Pattern {
+        let result = score(&guess, &self.solution);
+        self.transcript.push(Round { guess, pattern: result, remaining: self.game.solution_space.len(), rank: None });
+        self.game.filter(&guess, result);
+        self.patterns.push(result);
+        self.keyboard.update(&guess, result);
+        for (i, known) in self.revealed.iter_mut().enumerate() {
+            *known |= result[i] == Color::Green;
+        }
+        result
+    }
+
+    fn round(&mut self) -> Result<Word, WordleError> {
+        self.round += 1;
+        let start = Instant::now();
+        let guess = self.read()?;
+        if let Some(timer) = &mut self.timer {
+            let time = start.elapsed();
+            timer.times.push(time);
+            if let Some(limit) = timer.limit.filter(|limit| time > *limit) {
+                println!("{}   The guess took {:.1}s, the limit is {:.1}s.", style::bold("Too slow!"),
+                         time.as_secs_f64(), limit.as_secs_f64());
+                timer.timed_out = true;
+                self.round -= 1;
+                return Ok(guess);
+            }
+        }
+        let result = self.enter(guess);
+        print!("{} ", style::bold(format_args!("→ {}", result)));
+        if let Some(time) = self.timer.as_ref().and_then(|timer| timer.times.last()) {
+            print!("({:.1}s) ", time.as_secs_f64());
+        }
+        if guess != self.solution && !self.out_of_rounds() {
+            println!();
+            println!("{}", self.keyboard);
+        }
+        Ok(guess)
+    }
+
+    pub fn solution(&self) -> Word {
+        self.solution
+    }
+
+    /// The number of guesses made so far, not counting one discarded for taking too long.
+    pub fn rounds(&self) -> u32 {
+        self.round
+    }
+
+    /// Whether the last guess was the solution.
+    pub fn solved(&self) -> bool {
+        self.patterns.last() == Some(&Pattern::all_green())
+    }
+
+    /// The game so far. The player gets no suggestions, so no guess has a rank.
+    pub fn transcript(&self) -> Transcript {
+        Transcript { mode: Mode::Play, solution: Some(self.solution), solved: self.solved(), rounds: self.transcript.clone() }
+    }
+
+    /// Whether the game was played to the end, rather than stopped because input ended.
+    pub fn is_over(&self) -> bool {
+        self.solved() || self.out_of_rounds() || self.timed_out()
+    }
+
+    /// The time all guesses took together, for timed games.
+    pub fn total_time(&self) -> Option<Duration> {
+        self.timer.as_ref().map(|timer| timer.times.iter().sum())
+    }
+
+    fn share(&self, solved: bool) -> Share {
+        let board = Board { patterns: self.patterns.clone(), solved };
+        let name = match self.seed {
+            Some(seed) => format!("Wordle (bot, seed {})", seed),
+            None => "Wordle (bot)".to_string(),
+        };
+        Share::new(&name, vec![board], Game::MAX_ROUNDS, false)
+    }
+
+    pub fn run_game(&mut self) -> Result<(), WordleError> {
+        let solved = loop {
+            let guess = match self.round() {
+                Ok(guess) => guess,
+                Err(error) => {
+                    self.round -= 1;
+                    return input::end_game(error, || {
+                        println!();
+                        println!("{}   The word was {}.", style::bold("Input ended."), self.solution);
+                    });
+                }
+            };
+            if self.timed_out() {
+                println!("{}   Time's up!", style::bold("Failure!"));
+                println!("{}", style::bold(format_args!("The word was {}.", self.solution)));
+                break false;
+            } else if guess == self.solution {
+                println!("{}", style::bold(format_args!("Success!   →{}.", self.solution)));
+                break true;
+            } else if self.out_of_rounds() {
+                println!("{}   Rounds exhausted!", style::bold("Failure!"));
+                println!("{}", style::bold(format_args!("The word was {}.", self.solution)));
+                break false;
+            }
+        };
+        if let Some(timer) = &self.timer {
+            let total = timer.times.iter().sum::<Duration>().as_secs_f64();
+            println!("Time {:.1}s, {:.1}s per guess", total, total / timer.times.len().max(1) as f64);
+        }
+        if self.hints > 0 {
+            println!("Hints used: {}", self.hints);
+        }
+        if self.practice {
+            println!("Found in {} guess{}{}.", self.round, if self.round == 1 { "" } else { "es" }, match self.round > u32::from(Game::MAX_ROUNDS) {
+                true => format!(", {} more than the real game allows", self.round - u32::from(Game::MAX_ROUNDS)),
+                false => String::new(),
+            });
+            return Ok(());
+        }
+        println!("Score {}", self.round);
+        println!();
+        let share = self.share(solved).render();
+        println!("{}", share);
+        #[cfg(feature = "clipboard")]
+        if self.copy {
+            println!("{}", style::bold("Copied to the clipboard."));
+            Clipboard::new()?.copy_and_wait(&share, Duration::from_secs(60))?;
+        }
+        Ok(())
+    }
+
+}
+
+
+/// Simulates a game in which the program plays against itself, either against a fixed
+/// `solution`, or, if there is none, against an adversarial host as in Absurdle.
+pub struct SimulatedGame<'a> {
+    game: Game<'a>,
+    guesses: Vec<Word>,
+    space_sizes: Vec<usize>,
+    solution: Option<Word>,
+    first_guess: Word,
+    strategy: Strategy,
+    tree: Option<TreeCursor>,
+    quiet: bool,
+    antiwordle: bool,
+}
+
+impl SimulatedGame<'_> {
+    /// The number of guesses within which a game counts as solved.
+    pub const MAX_ROUNDS: u8 = Game::MAX_ROUNDS;
+
+    pub fn new<'a>(words: &'a [Word], solution: Word, first_guess: Word) -> SimulatedGame<'a> {
+        SimulatedGame {
+            game: Game::new(words),
+            guesses: Vec::with_capacity(Game::MAX_ROUNDS as usize),
+            space_sizes: Vec::with_capacity(Game::MAX_ROUNDS as usize),
+            solution: Some(solution),
+            first_guess,
+            strategy: Strategy::Entropy,
+            tree: None,
+            quiet: false,
+            antiwordle: false,
+        }
+    }
+
+    /// Creates a game against `solution` in which only the words of `solutions` are candidates
+    /// for the solution, which must be one of them.
+    pub fn with_solutions<'a>(words: &'a [Word], solutions: &'a [Word], solution: Word,
+                              first_guess: Word) -> SimulatedGame<'a> {
+        SimulatedGame { game: Game::with_solutions(words, solutions), ..SimulatedGame::new(words, solution, first_guess) }
+    }
+
+    /// Creates a game against an adversarial host that may pick any of `solutions` and
+    /// always answers with the feedback that keeps the most of them alive. The solver
+    /// plays minimax instead of maximising entropy, and there is no round limit.
+    pub fn absurdle<'a>(words: &'a [Word], solutions: &'a [Word], first_guess: Word) -> SimulatedGame<'a> {
+        SimulatedGame {
+            game: Game::with_solutions(words, solutions),
+            guesses: Vec::new(),
+            space_sizes: Vec::new(),
+            solution: None,
+            first_guess,
+            strategy: Strategy::Minimax,
+            tree: None,
+            quiet: false,
+            antiwordle: false,
+        }
+    }
+
+    /// Creates a game of Antiwordle against `solution`: the solver tries to avoid the solution
+    /// for as long as possible, while every guess must fit the feedback of all guesses before,
+    /// as in hard mode. After `first_guess`, it always picks the word that leaves the most
+    /// candidates on average, see [`antiwordle_opener`]. There is no round limit.
+    pub fn antiwordle(words: &[Word], solution: Word, first_guess: Word) -> SimulatedGame<'_> {
+        SimulatedGame {
+            antiwordle: true,
+            guesses: Vec::new(),
+            space_sizes: Vec::new(),
+            ..SimulatedGame::new(words, solution, first_guess)
+        }
+    }
+
+    /// Picks all guesses after the first one with `strategy` instead of the default, which
+    /// is [`Strategy::Entropy`] for games with a fixed solution and [`Strategy::Minimax`]
+    /// against an adversarial host.
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Looks up feedback in `matrix` instead of computing it, see [`PatternMatrix`]. The matrix
+    /// must be built with the word list as guesses, and the solution list (the word list
+    /// unless the game is against an adversarial host) as solutions.
+    pub fn with_matrix(mut self, matrix: Arc<PatternMatrix>) -> Self {
+        self.game = self.game.with_matrix(matrix);
+        self
+    }
+
+    /// Weighs the solutions by `weights`, and sharpens them by `sharpening` if given, see
+    /// [`Solver::with_weights`] and [`Solver::with_sharpening`].
+    pub fn with_weights(mut self, weights: Arc<WeightedList>, sharpening: Option<Sharpening>) -> Self {
+        self.game = self.game.with_weights(weights);
+        self.game.sharpening = sharpening;
+        self
+    }
+
+    /// Makes the guesses of `tree`, including the first one, as long as the game follows it,
+    /// and falls back to the strategy once it leaves the tree.
+    pub fn with_tree(mut self, tree: Arc<DecisionTree>) -> Self {
+        self.tree = Some(TreeCursor::new(tree));
+        self
+    }
+
+    /// Draws the random choices of [`Strategy::Random`] and [`Strategy::Noisy`] for game
+    /// number `game` of a run with the seed `seed`. Without a seed, every game with the same
+    /// candidates left makes the same choice, so the errors of `Noisy` would repeat across the
+    /// games of a run.
+    pub fn with_seed(mut self, seed: u64, game: usize) -> Self {
+        self.game.seed = Some((seed, game as u64));
+        self
+    }
+
+    /// Suppresses the line printed at the end of the game, e.g. when the results are written
+    /// to standard output in a machine-readable format instead.
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    fn guess(&mut self) -> Word {
+        // The round is counted once the guess is picked, so that it is the number of guesses
+        // made so far while picking, as in the other games, see [`Sharpening`].
+        let guess = if let Some(guess) = self.tree.as_ref().and_then(TreeCursor::guess) {
+            guess
+        } else if self.game.round == 0 {
+            self.first_guess
+        } else if self.antiwordle {
+            self.game.slowest_guess()
+        } else {
+            self.game.guess_with(self.strategy)
+        };
+        self.game.round += 1;
+        guess
+    }
+
+    fn name(&self) -> String {
+        match self.solution {
+            Some(solution) => format!("Game ({})", solution),
+            None => "Game (absurdle)".to_string(),
+        }
+    }
+
+    fn print(&self) {
+        if !self.quiet && !Verbosity::current().is_quiet() {
+            println!("{}", self.summary());
+        }
+    }
+
+    /// The line printed at the end of the game: its name and all guesses.
+    pub fn summary(&self) -> String {
+        format_start(self.name().as_str(), &self.guesses, self.guesses.len())
+    }
+
+    /// The outcome of the game so far, for batch analysis. For games against an adversarial
+    /// host, the recorded solution is the word the host was finally forced to.
+    pub fn record(&self) -> GameRecord {
+        let solved = self.guesses.len() <= Game::MAX_ROUNDS as usize
+            && self.solution.is_some_and(|s| self.guesses.last() == Some(&s));
+        GameRecord {
+            solution: self.solution.unwrap_or_else(|| *self.game.solution_space[0]),
+            guesses: self.guesses.clone(),
+            solved,
+            space_sizes: self.space_sizes.clone(),
+        }
+    }
+
+    pub fn run_game(&mut self) -> u8 {
+        loop {
+            self.space_sizes.push(self.game.solution_space.len());
+            let guess = self.guess();
+            let result = match self.solution {
+                Some(solution) => score(&guess, &solution),
+                None => self.game.worst_pattern(&guess),
+            };
+            self.game.filter(&guess, result);
+            if let Some(tree) = &mut self.tree {
+                tree.advance(&guess, result);
+            }
+            self.guesses.push(guess);
+            if result == Pattern::all_green() {
+                self.print();
+                return self.game.round;
+            } else if self.solution.is_some() && !self.antiwordle && self.game.round > Game::MAX_ROUNDS {
+                self.print();
+                return  Game::MAX_ROUNDS + 1
+            }
+        }
+
+    }
+}
+
+/// Generates a compact, human-readable opening book: for every pattern the first guess can
+/// produce, the recommended next guess, and so on up to `depth` guesses. Printed out, it
+/// lets players use the solver's opening theory away from a computer.
+///
+/// # Example
+///
+/// ```text
+/// Cheat sheet for crane (2315 solutions)
+///
+/// bbbbb (263) → spilt
+///   bbbbb (25) → budge
+///   ...
+/// ```
+pub struct CheatSheet<'a> {
+    game: Game<'a>,
+    first_guess: Word,
+    depth: u8,
+}
+
+impl CheatSheet<'_> {
+    /// A cheat sheet starting with `first_guess` and covering `depth` guesses. A depth of 0
+    /// is taken as 1, which lists no patterns, as the first guess counts as one.
+    pub fn new<'a>(words: &'a [Word], first_guess: Word, depth: u8) -> CheatSheet<'a> {
+        CheatSheet { game: Game::new(words), first_guess, depth: depth.max(1) }
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> Result<(), WordleError> {
+        writeln!(out, "Cheat sheet for {} ({} solutions)", self.first_guess,
+                 self.game.solution_space.len())?;
+        writeln!(out)?;
+        Self::write_node(&self.game, &self.first_guess, self.depth - 1, 0, out)?;
+        Ok(())
+    }
+
+    /// Writes one line for every pattern `guess` can produce in `game`, largest buckets first,
+    /// each followed by the lines of the guess recommended after it if `depth` allows.
+    fn write_node<W: Write>(game: &Game, guess: &Word, depth: u8, indent: usize,
+                            out: &mut W) -> std::io::Result<()> {
+        if depth == 0 {
+            return Ok(());
+        }
+        let counts = pattern_counts(guess, &game.solution_space);
+        let mut patterns = (0..Pattern::MAX).filter(|&i| counts[i] > 0).collect::<Vec<usize>>();
+        patterns.sort_by_key(|&i| std::cmp::Reverse(counts[i]));
+        for index in patterns {
+            let pattern = Pattern::from_index(index);
+            if pattern == Pattern::all_green() {
+                continue;
+            }
+            let mut next = game.clone();
+            next.filter(guess, pattern);
+            let next_guess = next.best_guess();
+            let solved = if next.solution_space.len() == 1 { " (solved)" } else { "" };
+            writeln!(out, "{:indent$}{} ({}) → {}{}", "", pattern.letters(), counts[index],
+                     next_guess, solved, indent = indent)?;
+            if next.solution_space.len() > 1 {
+                Self::write_node(&next, &next_guess, depth - 1, indent + 2, out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shows the structure of the remaining solution space after a number of guesses, by grouping
+/// the candidates instead of printing them as a flat list.
+pub struct ClusterView<'a> {
+    game: Game<'a>,
+}
+
+impl ClusterView<'_> {
+
+    /// The maximum number of words printed per group.
+    const GROUP_PREVIEW: usize = 10;
+
+    /// Creates a view on the solution space that remains after the guesses of `state`.
+    pub fn new<'a>(words: &'a [Word], state: &GameState) -> ClusterView<'a> {
+        ClusterView { game: Solver::from_state(words, state).game }
+    }
+
+    /// Groups the candidates by the pattern they would produce if `probe` were guessed next,
+    /// largest groups first. Each group is exactly one of the solution spaces that could
+    /// remain after playing `probe`.
+    fn by_probe(&self, probe: &Word) -> Vec<(Pattern, Vec<&Word>)> {
+        let mut groups: HashMap<usize, Vec<&Word>> = HashMap::new();
+        for w in &self.game.solution_space {
+            groups.entry(score(probe, w).index()).or_default().push(*w);
+        }
+        let mut groups = groups.into_iter()
+            .map(|(index, words)| (Pattern::from_index(index), words))
+            .collect::<Vec<_>>();
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.index().cmp(&b.0.index())));
+        groups
+    }
+
+    /// Groups the candidates into families that agree in all but one position, like
+    /// `_atch` or `sh_re`. These families are what makes some games hard: each guess
+    /// can only rule out a few of their members. Only families with at least two members
+    /// are returned, largest first; a word can be part of several families.
+    fn by_structure(&self) -> Vec<(String, Vec<&Word>)> {
+        let mut groups: HashMap<String, Vec<&Word>> = HashMap::new();
+        for w in &self.game.solution_space {
+            for i in 0..WORD_LENGTH {
+                let key = (0..WORD_LENGTH)
+                    .map(|j| if i == j { '_' } else { w.letter(j) })
+                    .collect::<String>();
+                groups.entry(key).or_default().push(*w);
+            }
+        }
+        let mut groups = groups.into_iter()
+            .filter(|(_, words)| words.len() > 1)
+            .collect::<Vec<_>>();
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+        groups
+    }
+
+    /// Prints the groups, either by their pattern against `probe`, or, if no probe is given,
+    /// by shared letter structure.
+    pub fn print(&self, probe: Option<Word>) {
+        print_start("Solution Space", &self.game.solution_space, 5);
+        match probe {
+            Some(probe) => {
+                let groups = self.by_probe(&probe);
+                println!("{}", style::bold(format_args!("{} groups for probe {}", groups.len(), probe)));
+                for (pattern, words) in groups {
+                    print_start(&pattern.to_string(), &words, Self::GROUP_PREVIEW);
+                }
+            }
+            None => {
+                let groups = self.by_structure();
+                println!("{}", style::bold(format_args!("{} families differing in a single letter", groups.len())));
+                for (key, words) in groups {
+                    print_start(&key, &words, Self::GROUP_PREVIEW);
+                }
+            }
+        }
+    }
+}
+
+/// Hosts a game of Absurdle: instead of fixing a solution up front, the program keeps
+/// the whole word list as candidates and answers every guess with the feedback that
+/// leaves the most candidates alive. The game only ends once the guess is the last
+/// remaining candidate, so there is no round limit.
+pub struct AbsurdleGame<'a> {
+    game: Game<'a>,
+    guess_check: GuessCheck,
+    solved: bool,
+}
+
+impl AbsurdleGame<'_> {
+    pub fn new<'a>(words: &'a [Word]) -> AbsurdleGame<'a> {
+        AbsurdleGame { game: Game::new(words), guess_check: GuessCheck::Reject, solved: false }
+    }
+
+    /// Sets what to do with a guess that is not in the word list, see
+    /// [`PlayGame::with_guess_check`].
+    pub fn with_guess_check(mut self, guess_check: GuessCheck) -> Self {
+        self.guess_check = guess_check;
+        self
+    }
+
+    fn round(&mut self) -> Result<Pattern, WordleError> {
+        self.game.round += 1;
+        let guess = loop {
+            input::prompt("Guess a word:")?;
+            let guess = Word::read()?;
+            if self.guess_check.check(self.game.words, &guess) {
+                break guess;
+            }
+        };
+        let result = self.game.worst_pattern(&guess);
+        self.game.filter(&guess, result);
+        println!("{} ({} words remaining)", style::bold(format_args!("→ {}", result)),
+                 self.game.solution_space.len());
+        Ok(result)
+    }
+
+    /// Whether the word was found before input ended.
+    pub fn solved(&self) -> bool {
+        self.solved
+    }
+
+    /// The number of words that were still possible.
+    pub fn remaining(&self) -> usize {
+        self.game.solution_space.len()
+    }
+
+    pub fn run_game(&mut self) -> Result<(), WordleError> {
+        loop {
+            match self.round() {
+                Ok(pattern) if pattern == Pattern::all_green() => break,
+                Ok(_) => {}
+                Err(error) => return input::end_game(error, || {
+                    println!();
+                    println!("{}   {} words were still possible.", style::bold("Input ended."),
+                             self.game.solution_space.len());
+                }),
+            }
+        }
+        self.solved = true;
+        println!("{}", style::bold(format_args!("Success!   →{}.", self.game.solution_space[0])));
+        println!("Score {}", self.game.round);
+        Ok(())
+    }
+}
+
+/// The best first guess for Antiwordle among `words`: the one that leaves the most words on
+/// average, see [`SimulatedGame::antiwordle`].
+pub fn antiwordle_opener(words: &[Word]) -> Word {
+    Game::new(words).slowest_guess()
+}
+
+/// Hosts a game of Antiwordle, where the goal is inverted: the player tries to avoid the
+/// solution for as long as possible. As in hard mode, every guess must fit the feedback of
+/// all guesses before, i.e. keep the green letters in place, use the yellow letters and
+/// avoid the gray ones, so sooner or later the solution is the only word left. The score is
+/// the number of guesses it took, and higher is better.
+pub struct AntiwordleGame<'a> {
+    game: Game<'a>,
+    solution: Word,
+    history: Vec<(Word, Pattern)>,
+    keyboard: Keyboard,
+    guess_check: GuessCheck,
+}
+
+impl AntiwordleGame<'_> {
+    pub fn new(words: &[Word]) -> AntiwordleGame<'_> {
+        Self::with_solution(words, words[rand::thread_rng().gen_range(0..words.len())])
+    }
+
+    /// A game whose solution is picked by `seed`, see [`PlayGame::seeded`].
+    pub fn seeded(words: &[Word], seed: u64) -> AntiwordleGame<'_> {
+        Self::with_solution(words, words[StdRng::seed_from_u64(seed).gen_range(0..words.len())])
+    }
+
+    fn with_solution(words: &[Word], solution: Word) -> AntiwordleGame<'_> {
+        AntiwordleGame {
+            game: Game::new(words),
+            solution,
+            history: Vec::new(),
+            keyboard: Keyboard::new(),
+            guess_check: GuessCheck::Reject,
+        }
+    }
+
+    /// Sets what to do with a guess that is not in the word list, see
+    /// [`PlayGame::with_guess_check`].
+    pub fn with_guess_check(mut self, guess_check: GuessCheck) -> Self {
+        self.guess_check = guess_check;
+        self
+    }
+
+    /// The first earlier guess whose feedback `guess` does not fit, if any.
+    fn violation(&self, guess: &Word) -> Option<&(Word, Pattern)> {
+        self.history.iter().find(|(earlier, pattern)| score(earlier, guess) != *pattern)
+    }
+
+    fn round(&mut self) -> Result<Pattern, WordleError> {
+        self.game.round += 1;
+        let guess = loop {
+            input::prompt("Guess a word:")?;
+            let guess = Word::read()?;
+            if !self.guess_check.check(self.game.words, &guess) {
+                continue;
+            }
+            match self.violation(&guess) {
+                Some((earlier, pattern)) => println!("{} does not fit the feedback {} of {}.", style::bold(guess),
+                                                     pattern, earlier),
+                None => break guess,
+            }
+        };
+        let result = score(&guess, &self.solution);
+        self.game.filter(&guess, result);
+        self.history.push((guess, result));
+        self.keyboard.update(&guess, result);
+        println!("{} ({} words remaining)", style::bold(format_args!("→ {}", result)),
+                 self.game.solution_space.len());
+        if result != Pattern::all_green() {
+            println!("{}", self.keyboard);
+        }
+        Ok(result)
+    }
+
+    pub fn run_game(&mut self) -> Result<(), WordleError> {
+        loop {
+            match self.round() {
+                Ok(pattern) if pattern == Pattern::all_green() => break,
+                Ok(_) => {}
+                Err(error) => return input::end_game(error, || {
+                    println!();
+                    println!("{}   The word was {}.", style::bold("Input ended."), self.solution);
+                }),
+            }
+        }
+        println!("{}", style::bold(format_args!("Caught!   →{}.", self.solution)));
+        println!("Score {}", self.game.round);
+        Ok(())
+    }
+}
+
+/// Calculates the combined entropy of a word over several boards that are played at the
+/// same time, as in Quordle or Octordle.
+///
+/// Since the solutions of the boards are independent, the information a guess yields is
+/// the sum of the information it yields on every single board. Boards that are already
+/// solved do not contribute. The expected and worst-case numbers of solutions left are
+/// summed over the boards as well, and the probability is that of solving at least one board.
+///
+/// # See Also
+///
+/// * [`entropy`] - The single-board version.
+fn combined_entropy<'a>(word: &'a Word, boards: &[&Game]) -> Eval<'a> {
+    let evals = boards.iter()
+        .map(|board| entropy(word, &board.solution_space))
+        .collect::<Vec<Eval>>();
+    Eval {
+        word,
+        entropy: evals.iter().map(|e| e.entropy).sum(),
+        expected: evals.iter().map(|e| e.expected).sum(),
+        worst: evals.iter().map(|e| e.worst).sum(),
+        probability: 1.0 - evals.iter().map(|e| 1.0 - e.probability).product::<f64>(),
+    }
+}
+
+/// Helps with a multi-board game (Dordle, Quordle, Octordle, Sedecordle), where every guess
+/// is entered on all boards at once and each board gives its own feedback.
+///
+/// # Fields
+///
+/// * `words` - All words that can be used as guesses.
+/// * `boards` - One [`Game`] per board, each tracking its own solution space.
+/// * `solved` - Whether the solution of the board at the same index has been guessed.
+/// * `round` - The number of guesses entered so far.
+pub struct MultiGame<'a> {
+    words: &'a [Word],
+    boards: Vec<Game<'a>>,
+    solved: Vec<bool>,
+    round: u8,
+}
+
+impl MultiGame<'_> {
+    pub fn new<'a>(words: &'a [Word], boards: usize) -> MultiGame<'a> {
+        MultiGame {
+            words,
+            boards: (0..boards).map(|_| Game::new(words)).collect(),
+            solved: vec![false; boards],
+            round: 0,
+        }
+    }
+
+    /// The number of guesses allowed, following the usual variants: five more
+    /// than there are boards (7 for Dordle, 9 for Quordle, 13 for Octordle, 21 for Sedecordle).
+    pub fn max_rounds(&self) -> u8 {
+        self.boards.len() as u8 + 5
+    }
+
+    fn open_boards(&self) -> Vec<usize> {
+        (0..self.boards.len()).filter(|&i| !self.solved[i]).collect()
+    }
+
+    /// Whether every board was solved.
+    pub fn solved(&self) -> bool {
+        self.solved.iter().all(|&solved| solved)
+    }
+
+    /// The fewest words that still fit the feedback of a board that is not solved, `0` if
+    /// no word in the list fits one of them.
+    pub fn remaining(&self) -> usize {
+        self.open_boards().iter().map(|&i| self.boards[i].solution_space.len()).min().unwrap_or(0)
+    }
+
+    fn evaluate_words(&self) -> Vec<Eval<'_>> {
+        let open = self.open_boards().iter().map(|&i| &self.boards[i]).collect::<Vec<&Game>>();
+        let mut evaluation = self.words.par_iter().map(|w| {
+            combined_entropy(w, &open)
+        }).collect::<Vec<Eval>>();
+        evaluation.sort_unstable_by(|a, b| f64::total_cmp(&b.entropy, &a.entropy));
+        evaluation
+    }
+
+    fn round(&mut self) -> Result<(), WordleError> {
+        for i in self.open_boards() {
+            print_start(format!("Board {}", i + 1).as_str(), &self.boards[i].solution_space,
+                        Verbosity::current().candidates_shown());
+        }
+        let eval = self.evaluate_words();
+        print_start("Suggested Guesses", &eval, Verbosity::current().suggestions_shown());
+
+        input::prompt("Enter guessed word:")?;
+        let guess = Word::read()?;
+        for i in self.open_boards() {
+            input::prompt(&format!("Enter resulting pattern for board {}:", i + 1))?;
+            let pattern = Pattern::read()?;
+            self.boards[i].filter(&guess, pattern);
+            self.boards[i].round += 1;
+            self.solved[i] = pattern == Pattern::all_green();
+        }
+        self.round += 1;
+        Ok(())
+    }
+
+    pub fn run_game(&mut self) -> Result<(), WordleError> {
+        loop {
+            if let Err(error) = self.round() {
+                return input::end_game(error, || {
+                    println!();
+                    println!("{}   Score {}", style::bold("Input ended."), self.round);
+                });
+            }
+            if self.solved() {
+                print!("{}   All boards solved.", style::bold("Success!"));
+                break;
+            } else if let Some(i) = self.open_boards().into_iter()
+                .find(|&i| self.boards[i].solution_space.is_empty()) {
+                print!("{}   No fitting Word in the list for board {}!", style::bold("Failure!"), i + 1);
+                break;
+            } else if self.round >= self.max_rounds() {
+                print!("{}   Rounds exhausted!", style::bold("Failure!"));
+                break;
+            }
+        }
+        println!("Score {}", self.round);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::{sample_words, sample_words_with};
+
+    fn assert_score(solution: &str, guess: &str, pattern: &str) {
+        assert_eq!(
+            score(&Word::from_str(solution), &Word::from_str(guess)),
+            Pattern::from_string(pattern)
+        );
+    }
+
+    #[test]
+    fn test_combined_entropy() {
+        let words = ["tears", "bears", "stear", "crane"].map(Word::from_str).to_vec();
+        let mut board = Game::new(&words);
+        board.filter(&Word::from_str("crane"), Pattern::from_string("bbbbb"));
+        let full = Game::new(&words);
+        let word = Word::from_str("tears");
+        let combined = combined_entropy(&word, &[&full, &board]);
+        let separate = entropy(&word, &full.solution_space).entropy
+            + entropy(&word, &board.solution_space).entropy;
+        assert!((combined.entropy - separate).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_worst_pattern() {
+        let words = ["tears", "bears", "gears", "crane"].map(Word::from_str).to_vec();
+        let game = Game::new(&words);
+        assert_eq!(game.worst_pattern(&Word::from_str("fears")), Pattern::from_string("bgggg"));
+        assert_eq!(game.worst_pattern(&Word::from_str("xxxxx")), Pattern::from_string("bbbbb"));
+    }
+
+    #[test]
+    fn test_cheat_sheet() {
+        let words = sample_words();
+        let mut out = Vec::new();
+        CheatSheet::new(&words, Word::from_str("crane"), 2).write(&mut out).unwrap();
+        let sheet = String::from_utf8(out).unwrap();
+        assert!(sheet.starts_with("Cheat sheet for crane (5 solutions)\n\n"));
+        assert!(sheet.contains("bygby (3) → "));
+        for depth in [0, 1] {
+            let mut out = Vec::new();
+            CheatSheet::new(&words, Word::from_str("crane"), depth).write(&mut out).unwrap();
+            assert_eq!(String::from_utf8(out).unwrap(), "Cheat sheet for crane (5 solutions)\n\n");
+        }
+    }
+
+    #[test]
+    fn test_clusters() {
+        let words = ["batch", "catch", "hatch", "crane", "crate"].map(Word::from_str).to_vec();
+        let view = ClusterView::new(&words, &GameState::new());
+        let families = view.by_structure();
+        assert_eq!(families[0].0, "_atch");
+        assert_eq!(families[0].1.len(), 3);
+        assert_eq!(families[1].0, "cra_e");
+        let groups = view.by_probe(&Word::from_str("catch"));
+        assert_eq!(groups[0].0, Pattern::from_string("bgggg"));
+        assert_eq!(groups[0].1.len(), 2);
+    }
+
+    #[test]
+    fn test_incremental_buckets() {
+        let words = sample_words_with(&["stare"]);
+        let mut cached = Game::new(&words);
+        cached.cache_buckets();
+        let guess = Word::from_str("fears");
+        cached.filter(&guess, Pattern::from_string("bgggg"));
+        let mut fresh = cached.clone();
+        fresh.cache_buckets();
+        assert_eq!(cached.buckets, fresh.buckets);
+        let expected = Game { buckets: None, ..cached.clone() }.evaluate_words();
+        for (a, b) in cached.evaluate_words().iter().zip(expected.iter()) {
+            assert!((a.entropy - b.entropy).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_progressively() {
+        let words = sample_words_with(&["stare", "xylyl"]);
+        let game = Game::new(&words);
+        assert_eq!(*game.heuristic_order().last().unwrap(), &Word::from_str("xylyl"));
+        let mut updates = Vec::new();
+        let progressive = game.evaluate_progressively(3, |_, scored| updates.push(scored));
+        assert_eq!(updates, vec![3, 6, 7]);
+        let expected = game.evaluate_words();
+        for (a, b) in progressive.iter().zip(expected.iter()) {
+            assert!((a.entropy - b.entropy).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_rollout_ranking() {
+        let words = sample_words_with(&["stare"]);
+        let game = Game::new(&words);
+        assert_eq!(rollout(&words[0], &words[0], &game.solution_space), 1);
+        let ranking = game.rollout_ranking(words.iter().collect(), Strategy::ROLLOUTS);
+        assert_eq!(ranking.len(), words.len());
+        assert!(ranking.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        for (guess, average) in ranking {
+            let total = words.iter().map(|solution| rollout(guess, solution, &game.solution_space)).sum::<usize>();
+            assert!((average - total as f64 / words.len() as f64).abs() < 1e-9);
+        }
+        let strategy = Strategy::MonteCarlo { rollouts: 3 };
+        assert_eq!(strategy.to_string().parse::<Strategy>().unwrap().with_rollouts(3), strategy);
+    }
+
+    #[test]
+    fn test_beam_ranking() {
+        assert_eq!((estimated_guesses(1), estimated_guesses(2)), (1.0, 1.5));
+        let words = sample_words_with(&["stare"]);
+        let game = Game::new(&words);
+        let ranking = game.beam_ranking(words.iter().collect(), 2, 3);
+        assert!(ranking.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        // No guess does better than telling all the other solutions apart.
+        assert!(ranking[0].1 >= 1.0 + 5.0 / 6.0 - 1e-9);
+    }
+
+    #[test]
+    fn test_weights() {
+        let words = ["bears", "gears", "tears", "crane"].map(Word::from_str);
+        let list = WeightedList::new(words.iter().zip([6.0, 1.0, 1.0, 0.0]).map(|(w, f)| (*w, f)));
+        let game = Game::new(&words).with_weights(Arc::new(list));
+        let weights = game.solution_weights();
+        let eval = game.eval(&words[0], weights.as_deref());
+        assert_eq!(eval.probability, 0.75);
+        assert!((eval.entropy - 0.811).abs() < 1e-3);
+        assert_eq!(eval.worst, 2);
+        assert!(game.eval(&words[0], None).entropy > eval.entropy);
+        // Telling the likely answer apart beats splitting the unlikely ones.
+        assert_eq!(game.top_words(1)[0].word, &words[0]);
+    }
+
+    #[test]
+    fn test_composite() {
+        let words = ["bully", "bears", "gears"].map(Word::from_str);
+        let game = Game::with_solutions(&words, &words[1..]);
+        let strategy = Strategy::Composite { weight: OrderedFloat(Strategy::WIN_WEIGHT) };
+        assert_ne!(game.guess_with(strategy), words[0]);
+        assert_ne!(game.ranked_guesses(strategy, None, 1)[0], words[0]);
+    }
+
+    #[test]
+    fn test_positional() {
+        let words = ["bears", "gears", "fears", "crane"].map(Word::from_str);
+        let game = Game::new(&words);
+        // `_ears` is most common in every position but the first, where all letters are rare.
+        assert_eq!(game.guess_with(Strategy::Positional), words[0]);
+        assert_eq!(positional_frequency(&game.solution_space)(&words[3]), 1 + 1 + 4 + 1 + 1);
+    }
+
+    #[test]
+    fn test_random() {
+        let words = sample_words_with(&["stare"]);
+        let game = Game::new(&words);
+        let guess = game.guess_with(Strategy::Random);
+        assert!(words.contains(&guess));
+        assert_eq!(game.guess_with(Strategy::Random), guess);
+        assert_eq!(game.ranked_guesses(Strategy::Random, None, 1), vec![guess]);
+        // The draw must not change between releases.
+        assert_eq!(guess, Word::from_str("bears"));
+        // Every game of a run draws differently.
+        let (mut first, mut second) = (game.clone(), game.clone());
+        (first.seed, second.seed) = (Some((7, 0)), Some((7, 1)));
+        assert_ne!(first.shuffled_solutions(), second.shuffled_solutions());
+    }
+
+    #[test]
+    fn test_noisy() {
+        let words = sample_words_with(&["stare"]);
+        let game = Game::new(&words);
+        assert_eq!(game.guess_with(Strategy::Noisy { error_rate: OrderedFloat(0.0) }), game.best_guess());
+        let guess = game.guess_with(Strategy::Noisy { error_rate: OrderedFloat(1.0) });
+        assert_eq!(game.ranked_guesses(Strategy::Noisy { error_rate: OrderedFloat(1.0) }, None, 1), vec![guess]);
+    }
+
+    #[test]
+    fn test_partitions() {
+        let words = sample_words_with(&["stare"]);
+        let game = Game::new(&words);
+        let ranked = game.ranked_guesses(Strategy::Partitions, None, words.len());
+        assert_eq!(ranked[0], game.guess_with(Strategy::Partitions));
+        let partitions = |w: &Word| game.guess_counts(w).iter().filter(|count| **count > 0).count();
+        assert!(ranked.windows(2).all(|pair| partitions(&pair[0]) >= partitions(&pair[1])));
+    }
+
+    #[test]
+    fn test_top_words() {
+        let words = (0..1000_u32).map(|i| {
+            let letters = (0..WORD_LENGTH as u32)
+                .map(|j| (b'a' + ((i * 7919 + j * 104_729) / 11_u32.pow(j) % 26) as u8) as char)
+                .collect::<String>();
+            Word::from_str(&letters)
+        }).collect::<Vec<Word>>();
+        let game = Game::with_solutions(&words, &words[..300]);
+        let expected = game.evaluate_words();
+        let bound = game.entropy_bound();
+        assert!(expected.iter().all(|e| bound(e.word) >= e.entropy - 1e-9));
+        let top = game.top_words(5);
+        assert_eq!(top.len(), 5);
+        for (a, b) in top.iter().zip(expected.iter()) {
+            assert!((a.entropy - b.entropy).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_guess_check() {
+        let words = ["tears", "crane"].map(Word::from_str);
+        assert!(GuessCheck::Reject.check(&words, &Word::from_str("crane")));
+        assert!(!GuessCheck::Reject.check(&words, &Word::from_str("zzzzz")));
+        assert!(GuessCheck::Warn.check(&words, &Word::from_str("rates")));
+        assert!(!GuessCheck::Warn.check(&words, &Word::from_str("zzzzz")));
+        assert!(GuessCheck::Accept.check(&words, &Word::from_str("zzzzz")));
+    }
+
+    #[test]
+    fn test_undo() {
+        let words = sample_words_with(&["dears"]);
+        let mut game = HelpGame::new(&words);
+        assert!(game.undo().is_none());
+        game.record(Word::from_str("crane"), Pattern::from_string("bygby"));
+        let buckets = game.game.buckets.clone();
+        game.record(Word::from_str("gears"), Pattern::from_string("bgggg"));
+        assert_eq!(game.game.solution_space.len(), 3);
+        assert_eq!(game.undo(), Some((Word::from_str("gears"), Pattern::from_string("bgggg"))));
+        assert_eq!(game.game.solution_space.len(), 4);
+        assert_eq!(game.game.round, 1);
+        assert_eq!(game.game.buckets, buckets);
+        assert_eq!(game.state.to_string(), "crane:bygby");
+    }
+
+    #[test]
+    fn test_contradictions() {
+        let words = sample_words_with(&["dears"]);
+        let mut game = HelpGame::new(&words);
+        game.record(Word::from_str("slimy"), Pattern::from_string("ybbbb"));
+        game.record(Word::from_str("crane"), Pattern::from_string("bygby"));
+        assert_eq!(game.contradictions(&Word::from_str("crane"), Pattern::from_string("ggggg")), [0, 1]);
+        assert!(game.contradictions(&Word::from_str("tears"), Pattern::from_string("ggggb")).is_empty());
+        game.undo();
+        assert_eq!(game.contradictions(&Word::from_str("crane"), Pattern::from_string("ggggg")), [0]);
+    }
+
+    #[test]
+    fn test_save_resume() {
+        let words = sample_words();
+        let mut game = HelpGame::new(&words);
+        game.record(Word::from_str("crane"), Pattern::from_string("bygby"));
+        let path = std::env::temp_dir().join(format!("wordle-save-{}.txt", std::process::id()));
+        game.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("history: crane:bygby\n"));
+        assert!(text.ends_with("remaining: tears bears gears\n"));
+        let resumed = HelpGame::new(&words).resume(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(resumed.state.to_string(), "crane:bygby");
+        assert_eq!(resumed.game.solution_space.len(), 3);
+    }
+
+    #[test]
+    fn test_play_seeded() {
+        let words = sample_words();
+        let game = PlayGame::seeded(&words, 42);
+        assert!((0..10).all(|_| PlayGame::seeded(&words, 42).solution == game.solution));
+        assert!((0..100).any(|seed| PlayGame::seeded(&words, seed).solution != game.solution));
+        assert!(game.share(true).render().starts_with("Wordle (bot, seed 42)"));
+    }
+
+    #[test]
+    fn test_play_hints() {
+        let words = sample_words_with(&["geese", "eerie", "sheen"]);
+        let mut game = PlayGame::with_solution(&words, Word::from_str("geese"), None);
+        assert!(matches!(PlayCommand::parse(" hint\n"), Ok(PlayCommand::Hint)));
+        assert!(matches!(PlayCommand::parse("Tears"), Ok(PlayCommand::Guess(_))));
+        // Only the green `e`s reveal their positions, not the yellow one.
+        assert_eq!(game.enter(Word::from_str("eerie")), Pattern::from_string("ygbbg"));
+        assert_eq!(game.revealed, [false, true, false, false, true]);
+        assert_eq!(game.game.solution_space.len(), 1);
+        assert_eq!(game.game.guess_with(Strategy::Entropy), Word::from_str("geese"));
+        assert_eq!(game.reveal_letter(), Some(0));
+        assert_eq!(game.reveal_letter(), Some(2));
+        assert_eq!(game.reveal_letter(), Some(3));
+        assert_eq!(game.reveal_letter(), None);
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        // `begot` tells the three remaining solutions apart, but does not fit the feedback.
+        let words = sample_words_with(&["begot"]);
+        let mut solver = Solver::new(&words);
+        solver.feedback(Word::from_str("crane"), Pattern::from_string("bygby"));
+        assert_eq!(solver.guess_with(Strategy::Entropy), Word::from_str("begot"));
+        let mut solver = Solver::new(&words).hard_mode();
+        solver.feedback(Word::from_str("crane"), Pattern::from_string("bygby"));
+        let fits = ["tears", "bears", "gears"].map(Word::from_str);
+        assert!(fits.contains(&solver.guess_with(Strategy::Entropy)));
+        let ranked = solver.ranked_guesses(Strategy::Entropy, 5);
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked.iter().all(|guess| fits.contains(guess)));
+    }
+
+    #[test]
+    fn test_play_practice() {
+        let words = sample_words();
+        let mut game = PlayGame::new(&words);
+        game.round = 6;
+        assert!(game.is_over());
+        let mut game = game.with_practice();
+        assert!(!game.is_over());
+        game.round = 20;
+        game.patterns.push(Pattern::all_green());
+        assert!(game.is_over() && game.solved());
+
+        let mut game = PlayGame::new(&words).with_timer(Some(Duration::from_secs(10)));
+        assert!(!game.is_over());
+        game.timer.as_mut().unwrap().times = vec![Duration::from_secs(3), Duration::from_secs(4)];
+        game.timer.as_mut().unwrap().timed_out = true;
+        assert!(game.is_over() && !game.solved());
+        assert_eq!(game.total_time(), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_antiwordle() {
+        let words = sample_words();
+        let solution = Word::from_str("tears");
+        let mut game = SimulatedGame::antiwordle(&words, solution, Word::from_str("crane")).quiet();
+        assert_eq!(game.run_game(), 4);
+        for (i, guess) in game.guesses.iter().enumerate() {
+            assert!(game.guesses[..i].iter().all(|earlier| score(earlier, guess) == score(earlier, &solution)));
+        }
+        assert!(["crane", "slimy"].map(Word::from_str).contains(&antiwordle_opener(&words)));
+
+        let mut game = AntiwordleGame::with_solution(&words, solution);
+        game.history.push((Word::from_str("crane"), score(&Word::from_str("crane"), &solution)));
+        assert!(game.violation(&Word::from_str("bears")).is_none());
+        assert!(game.violation(&Word::from_str("slimy")).is_some());
+    }
+
+    #[test]
+    fn test_eval_remaining() {
+        let words = sample_words();
+        let space = words.iter().collect::<Vec<&Word>>();
+        let eval = entropy(&words[4], &space);
+        assert!((eval.expected - 11.0 / 5.0).abs() < 1e-9);
+        assert_eq!(eval.worst, 3);
+        assert_eq!(eval.to_string(), "slimy (1.371, ~2.2 left, max 3, 20.0% answer)");
+        assert_eq!(entropy(&Word::from_str("xylyl"), &space).probability, 0.0);
+    }
+
+    #[test]
+    fn test_estimate_words() {
+        let words = sample_words_with(&["stare", "xylyl"]);
+        let game = Game::new(&words);
+        let mut rng = rand::thread_rng();
+        let exact = game.estimate_words(1000, &mut rng);
+        let expected = game.evaluate_words();
+        for (a, b) in exact.iter().zip(expected.iter()) {
+            assert!((a.entropy - b.entropy).abs() < 1e-9);
+            assert_eq!(a.margin, 0.0);
+        }
+        // Too small a budget for every word: only the most promising ones are scored.
+        let budgeted = game.estimate_words(14, &mut rng);
+        assert_eq!(budgeted.len(), 2);
+        assert!(!budgeted.iter().any(|e| e.word == &Word::from_str("xylyl")));
+
+        // A large enough list for sampling, with letters spread like in a real one.
+        let words = (0..2000_u32).map(|i| {
+            let letters = (0..WORD_LENGTH as u32)
+                .map(|j| (b'a' + ((i * 7919 + j * 104_729) / 13_u32.pow(j) % 13) as u8 * 2) as char)
+                .collect::<String>();
+            Word::from_str(&letters)
+        }).collect::<Vec<Word>>();
+        let game = Game::new(&words);
+        let sampled = game.estimate_words(200 * words.len(), &mut rng);
+        assert_eq!(sampled.len(), words.len());
+        let first = sampled.iter().find(|e| e.word == &words[0]).unwrap();
+        let exact = entropy(first.word, &game.solution_space).entropy;
+        assert!(first.margin > 0.0);
+        assert!((first.entropy - exact).abs() < 4.0 * first.margin);
+    }
+
+    #[test]
+    fn test_matrix() {
+        let words = sample_words_with(&["stare", "xylyl"]);
+        let matrix = Arc::new(PatternMatrix::new(&words, &words));
+        let mut plain = Game::new(&words);
+        let mut looked_up = Game::new(&words).with_matrix(matrix);
+        let solution = Word::from_str("gears");
+        for guess in ["crane", "hello"].map(Word::from_str) {
+            let result = score(&guess, &solution);
+            plain.filter(&guess, result);
+            looked_up.filter(&guess, result);
+            assert_eq!(plain.solution_space, looked_up.solution_space);
+            for (a, b) in plain.evaluate_words().iter().zip(looked_up.evaluate_words().iter()) {
+                assert!((a.entropy - b.entropy).abs() < 1e-9);
+            }
+        }
+        let ids = looked_up.solution_space.iter().map(|w| words.iter().position(|v| v == *w).unwrap() as u32);
+        assert_eq!(looked_up.solution_ids, ids.collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_score() {
+        assert_score("tears", "bears", "bgggg");
+        assert_score("tears", "stear", "yyyyy");
+        assert_score("atttt", "xaaaa", "ybbbb");
+        assert_score("aattt", "txxxx", "bbybb");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_state_serde() {
+        let state = GameState::parse("crane:bgybb,slimy:bbgyb").unwrap();
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, r#"{"history":[["crane","bgybb"],["slimy","bbgyb"]]}"#);
+        assert_eq!(serde_json::from_str::<GameState>(&json).unwrap(), state);
+        assert!(serde_json::from_str::<GameState>(r#"{"history":[["cran","bgybb"]]}"#).is_err());
     }
 }
\ No newline at end of file
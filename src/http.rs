@@ -0,0 +1,576 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+use rand::Rng;
+use serde_json::{json, Value};
+use thiserror::Error;
+use crate::game::{Eval, Solver};
+use crate::metrics::Metrics;
+use crate::pattern::Pattern;
+use crate::server::Session;
+use crate::slack::{self, SlackCommand};
+use crate::websocket;
+use crate::word::Word;
+
+/// The largest request body read, in bytes. Requests only carry a guess and a pattern.
+const MAX_BODY: usize = 64 * 1024;
+
+/// The largest request line and headers read, in bytes, and the most headers read.
+const MAX_HEAD: u64 = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+
+/// How long reading a request or writing a response may stall before the connection is
+/// dropped, so that idle clients do not hold on to a connection forever.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Why a [`Request`] could not be read.
+///
+/// # Variants
+/// * `Io` - The connection failed or timed out.
+/// * `Malformed` - The input is not an HTTP/1 request.
+/// * `HeadersTooLarge` - The request line and headers exceed [`MAX_HEAD`] bytes, or there are
+///   more than [`MAX_HEADERS`] headers.
+#[derive(Debug, Error)]
+pub enum RequestError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Malformed(String),
+    #[error("request headers too large")]
+    HeadersTooLarge,
+}
+
+impl RequestError {
+    /// The status code of the response to the request.
+    pub fn status(&self) -> u16 {
+        match self {
+            RequestError::Io(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => 408,
+            RequestError::HeadersTooLarge => 431,
+            _ => 400,
+        }
+    }
+}
+
+/// A parsed HTTP request.
+///
+/// # Fields
+/// * `method` - The method, e.g. `POST`.
+/// * `path` - The path without the query, e.g. `/session/3f2a/suggestions`.
+/// * `query` - The query parameters, e.g. `count=5`, not URL-decoded.
+/// * `headers` - The headers, with lowercase names.
+/// * `body` - The body, if any.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    /// Reads a request from `input`.
+    ///
+    /// # Returns
+    /// The request, or `None` if the connection was closed before a request started.
+    ///
+    /// # Errors
+    /// If the input cannot be read, is not an HTTP/1 request, or has too many headers, see
+    /// [`RequestError`].
+    pub fn read<R: BufRead>(mut input: R) -> Result<Option<Request>, RequestError> {
+        let invalid = |message: &str| RequestError::Malformed(message.to_string());
+        let mut budget = MAX_HEAD;
+        let mut read_line = |line: &mut String| -> Result<usize, RequestError> {
+            let read = (&mut input).take(budget).read_line(line)?;
+            budget -= read as u64;
+            if budget == 0 {
+                return Err(RequestError::HeadersTooLarge);
+            }
+            Ok(read)
+        };
+        let mut line = String::new();
+        if read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(invalid("malformed request line"));
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let query = query.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        let mut headers = HashMap::new();
+        for count in 0.. {
+            let mut header = String::new();
+            if read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+            if count == MAX_HEADERS {
+                return Err(RequestError::HeadersTooLarge);
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+        let length = match headers.get("content-length") {
+            Some(length) => length.parse().map_err(|_| invalid("malformed content length"))?,
+            None => 0,
+        };
+        if length > MAX_BODY {
+            return Err(invalid("request body too large"));
+        }
+        let mut body = vec![0; length];
+        input.read_exact(&mut body)?;
+        Ok(Some(Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            query,
+            headers,
+            body: String::from_utf8(body).map_err(|_| invalid("request body is not UTF-8"))?,
+        }))
+    }
+}
+
+/// A response to a [`Request`]: a status code and a JSON body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Response {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Response {
+        Response { status, body: json!({ "error": message.to_string() }) }
+    }
+
+    /// Writes the response, and asks the client to close the connection.
+    ///
+    /// # Errors
+    /// If writing fails.
+    pub fn write<W: Write>(&self, out: W) -> io::Result<()> {
+        write_response(out, self.status, "application/json", &self.body.to_string())
+    }
+}
+
+fn write_response<W: Write>(mut out: W, status: u16, content_type: &str, body: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+    write!(out, "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+                 Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+           status, reason, content_type, body.len(), body)?;
+    out.flush()
+}
+
+/// A REST backend for web frontends and chat bots, holding one [`Session`] per game:
+///
+/// * `POST /session` starts a game and returns its `id`.
+/// * `POST /session/{id}/feedback` with a body like `{"guess":"crane","pattern":"bbygb"}`
+///   enters a guess and returns the number of possible solutions.
+/// * `GET /session/{id}/suggestions?count=5` returns the best guesses with their entropy.
+/// * `DELETE /session/{id}` ends a game.
+/// * `GET /session/{id}/stream?count=5` opens a WebSocket that pushes the ranking of the best
+///   guesses while the words are scored, see [`HttpServer::stream`].
+/// * `POST /slack` answers Slack slash commands, see [`HttpServer::slack`].
+/// * `GET /metrics` returns the [`Metrics`] of the server for Prometheus.
+///
+/// At most [`HttpServer::MAX_SESSIONS`] games are kept; beyond that, the oldest is dropped.
+/// Connections are answered on their own threads, up to [`HttpServer::MAX_CONNECTIONS`] at a
+/// time, so a slow client only holds up itself.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::http::HttpServer;
+/// use wordl_rust_bot::{Solver, Word};
+///
+/// let words = ["tears", "bears", "gears", "crane"].map(Word::from_str);
+/// let mut server = HttpServer::new(Solver::new(&words));
+/// let created = server.handle("POST", "/session", &Default::default(), "");
+/// let id = created.body["id"].as_str().unwrap().to_string();
+/// let feedback = r#"{"guess":"crane","pattern":"bygby"}"#;
+/// let response = server.handle("POST", &format!("/session/{}/feedback", id), &Default::default(), feedback);
+/// assert_eq!(response.body["solution_space"], 3);
+/// ```
+pub struct HttpServer<'a> {
+    initial: Solver<'a>,
+    sessions: HashMap<String, Session<'a>>,
+    created: VecDeque<String>,
+    slack_secret: Option<String>,
+    slack_users: HashMap<String, String>,
+    metrics: Metrics,
+}
+
+impl<'a> HttpServer<'a> {
+    pub const MAX_SESSIONS: usize = 1000;
+
+    /// The number of connections answered at the same time. Beyond that, new connections are
+    /// answered with status 503 right away.
+    pub const MAX_CONNECTIONS: usize = 64;
+
+    /// The number of words scored between two messages of [`HttpServer::stream`].
+    pub const STREAM_CHUNK: usize = 1024;
+
+    /// Creates a server whose games all start like `solver`.
+    pub fn new(solver: Solver<'a>) -> HttpServer<'a> {
+        HttpServer {
+            initial: solver,
+            sessions: HashMap::new(),
+            created: VecDeque::new(),
+            slack_secret: None,
+            slack_users: HashMap::new(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Only answers Slack requests signed with `secret`, the signing secret of the Slack app.
+    pub fn with_slack_secret(mut self, secret: String) -> Self {
+        self.slack_secret = Some(secret);
+        self
+    }
+
+    /// Answers requests on `listener`, each connection on its own thread, until accepting a
+    /// connection fails. Connections that send malformed requests, or stall for longer than
+    /// [`TIMEOUT`], are closed and logged to standard error.
+    ///
+    /// # Errors
+    /// If accepting a connection fails.
+    pub fn serve(&mut self, listener: TcpListener) -> io::Result<()> {
+        let server = &Mutex::new(self);
+        let connections = &AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                if connections.fetch_add(1, Ordering::SeqCst) >= Self::MAX_CONNECTIONS {
+                    connections.fetch_sub(1, Ordering::SeqCst);
+                    let _ = stream.set_write_timeout(Some(TIMEOUT));
+                    if let Err(e) = Response::error(503, "too many connections").write(&stream) {
+                        eprintln!("{}", e);
+                    }
+                    continue;
+                }
+                scope.spawn(move || {
+                    if let Err(e) = Self::answer(server, stream) {
+                        eprintln!("{}", e);
+                    }
+                    connections.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// Locks the server shared by the connection threads. A thread that panicked leaves the
+    /// sessions usable, so a poisoned lock is taken over.
+    fn lock<'s, 'm>(server: &'s Mutex<&'m mut Self>) -> MutexGuard<'s, &'m mut Self> {
+        server.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Answers the request on `stream`. The server is only locked once the request has been
+    /// read, and not while a ranking is streamed.
+    fn answer(server: &Mutex<&mut Self>, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let response = match Request::read(BufReader::new(&stream)) {
+            Ok(Some(request)) => {
+                let segments = request.path.trim_matches('/').split('/').collect::<Vec<&str>>();
+                let key = request.headers.get("sec-websocket-key");
+                let mut locked = Self::lock(server);
+                match (request.method.as_str(), segments.as_slice(), key) {
+                    ("GET", ["session", id, "stream"], Some(key)) if locked.sessions.contains_key(*id) => {
+                        locked.metrics.request(101);
+                        let solver = locked.sessions[*id].solver().clone();
+                        drop(locked);
+                        websocket::write_handshake(&stream, key)?;
+                        let start = Instant::now();
+                        let result = stream_ranking(&solver, &request.query, &stream);
+                        Self::lock(server).metrics.suggested(start.elapsed());
+                        return result;
+                    }
+                    ("GET", ["metrics"], _) => {
+                        locked.metrics.request(200);
+                        let metrics = locked.metrics();
+                        drop(locked);
+                        return write_response(&stream, 200, Metrics::CONTENT_TYPE, &metrics);
+                    }
+                    ("GET", ["session", _, "stream"], None) => Response::error(400, "expected a WebSocket handshake"),
+                    ("POST", ["slack"], _) => locked.slack(&request.headers, &request.body),
+                    _ => locked.handle(&request.method, &request.path, &request.query, &request.body),
+                }
+            }
+            Ok(None) => return Ok(()),
+            Err(e) => Response::error(e.status(), e),
+        };
+        Self::lock(server).metrics.request(response.status);
+        response.write(&stream)
+    }
+
+    /// Sends the ranking of the game `id` over an accepted WebSocket connection while it is
+    /// computed: after every [`HttpServer::STREAM_CHUNK`] scored words, a message like
+    /// `{"scored":2048,"total":14855,"suggestions":[{"word":"tares","entropy":6.16,"expected":357.7,"worst":1022,"probability":0.00007},...],"done":false}`
+    /// with the best `count` guesses so far, and finally the full result with `"done":true`.
+    /// Then the connection is closed.
+    ///
+    /// # Errors
+    /// If writing to the connection fails, e.g. because the client went away.
+    pub fn stream<W: Write>(&self, id: &str, query: &HashMap<String, String>, out: W) -> io::Result<()> {
+        stream_ranking(self.sessions[id].solver(), query, out)
+    }
+
+    /// The metrics of the server in the Prometheus text format, as served at `GET /metrics`.
+    pub fn metrics(&self) -> String {
+        self.metrics.render(self.sessions.len())
+    }
+
+    /// Answers a request for `path` with the method `method`, the query parameters `query` and
+    /// the body `body`.
+    pub fn handle(&mut self, method: &str, path: &str, query: &HashMap<String, String>, body: &str) -> Response {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<&str>>();
+        match (method, segments.as_slice()) {
+            ("POST", ["session"]) => self.create(),
+            (_, ["session"]) => Response::error(405, "use POST to start a game"),
+            (method, ["session", id, rest @ ..]) => {
+                let Some(session) = self.sessions.get_mut(*id) else {
+                    return Response::error(404, format!("no game with id {}", id));
+                };
+                match (method, rest) {
+                    ("POST", ["feedback"]) => Self::feedback(session, &mut self.metrics, body),
+                    ("GET", ["suggestions"]) => Self::suggestions(session, &mut self.metrics, query),
+                    ("DELETE", []) => {
+                        self.metrics.session_ended(session.solved(), false);
+                        self.remove_session(id);
+                        Response::ok(json!({}))
+                    }
+                    (_, ["feedback"] | ["suggestions"] | []) => Response::error(405, "method not allowed"),
+                    _ => Response::error(404, format!("no such resource {}", path)),
+                }
+            }
+            _ => Response::error(404, format!("no such resource {}", path)),
+        }
+    }
+
+    /// Answers a Slack slash command like `/wordle crane bbygb`, sent as a form with the
+    /// `headers` and the `body`. Every Slack user plays their own game, and only sees the
+    /// replies themselves. If a signing secret is set, unsigned requests are rejected.
+    pub fn slack(&mut self, headers: &HashMap<String, String>, body: &str) -> Response {
+        if let Some(secret) = &self.slack_secret {
+            let header = |name: &str| headers.get(name).map_or("", String::as_str);
+            let (timestamp, signature) = (header("x-slack-request-timestamp"), header("x-slack-signature"));
+            if !slack::verify_signature(secret, timestamp, body, signature) {
+                return Response::error(401, "invalid Slack signature");
+            }
+        }
+        let form = slack::parse_form(body);
+        let field = |name: &str| form.get(name).map_or("", String::as_str);
+        let user = format!("{}:{}", field("team_id"), field("user_id"));
+        let id = match self.slack_users.get(&user).filter(|id| self.sessions.contains_key(*id)) {
+            Some(id) => id.clone(),
+            None => {
+                let id = self.create_session();
+                self.slack_users.insert(user, id.clone());
+                id
+            }
+        };
+        let session = self.sessions.get_mut(&id).unwrap();
+        let metrics = &mut self.metrics;
+        let text = match SlackCommand::parse(field("text")) {
+            SlackCommand::Suggest => Self::slack_reply(session, metrics),
+            SlackCommand::Reset => {
+                metrics.session_ended(session.solved(), false);
+                metrics.session_started();
+                session.reset();
+                format!("New game. {}", Self::slack_reply(session, metrics))
+            }
+            SlackCommand::Guess(guess, pattern) => {
+                match (Word::try_from_str_ignore_case(&guess), Pattern::try_from_str(&pattern)) {
+                    (Ok(guess), Ok(pattern)) => {
+                        let solved = session.solved();
+                        let solution_space = session.feedback(guess, pattern);
+                        metrics.feedback(solution_space, !solved && session.solved());
+                        Self::slack_reply(session, metrics)
+                    }
+                    (Err(e), _) => format!("{}. {}", e, slack::USAGE),
+                    (_, Err(e)) => format!("{}. {}", e, slack::USAGE),
+                }
+            }
+            SlackCommand::Help => slack::USAGE.to_string(),
+        };
+        Response::ok(json!({ "response_type": "ephemeral", "text": text }))
+    }
+
+    fn slack_reply(session: &Session, metrics: &mut Metrics) -> String {
+        let start = Instant::now();
+        let reply = slack::reply(session.solver());
+        metrics.suggested(start.elapsed());
+        reply
+    }
+
+    /// Starts a game, dropping the oldest one if there are too many.
+    ///
+    /// # Returns
+    /// The id of the new game.
+    fn create_session(&mut self) -> String {
+        if self.sessions.len() >= Self::MAX_SESSIONS {
+            if let Some(session) = self.created.front().cloned().and_then(|oldest| self.remove_session(&oldest)) {
+                self.metrics.session_ended(session.solved(), true);
+            }
+        }
+        self.metrics.session_started();
+        let id = format!("{:016x}", rand::thread_rng().gen::<u64>());
+        self.sessions.insert(id.clone(), Session::new(self.initial.clone()));
+        self.created.push_back(id.clone());
+        id
+    }
+
+    /// Ends the game `id`, and forgets the Slack user playing it, if any.
+    fn remove_session(&mut self, id: &str) -> Option<Session<'a>> {
+        self.created.retain(|created| created != id);
+        self.slack_users.retain(|_, session| session.as_str() != id);
+        self.sessions.remove(id)
+    }
+
+    fn create(&mut self) -> Response {
+        let id = self.create_session();
+        let solution_space = self.sessions[&id].solver().solution_space().len();
+        Response { status: 201, body: json!({ "id": id, "solution_space": solution_space }) }
+    }
+
+    fn feedback(session: &mut Session, metrics: &mut Metrics, body: &str) -> Response {
+        let request = match serde_json::from_str::<Value>(body) {
+            Ok(request) => request,
+            Err(e) => return Response::error(400, e),
+        };
+        let (Some(guess), Some(pattern)) = (request["guess"].as_str(), request["pattern"].as_str()) else {
+            return Response::error(400, "expected a body like {\"guess\":\"crane\",\"pattern\":\"bbygb\"}");
+        };
+        let guess = match Word::try_from_str_ignore_case(guess) {
+            Ok(guess) => guess,
+            Err(e) => return Response::error(400, e),
+        };
+        let pattern = match Pattern::try_from_str(pattern) {
+            Ok(pattern) => pattern,
+            Err(e) => return Response::error(400, e),
+        };
+        let solved = session.solved();
+        let solution_space = session.feedback(guess, pattern);
+        metrics.feedback(solution_space, !solved && session.solved());
+        Response::ok(json!({ "solution_space": solution_space }))
+    }
+
+    fn suggestions(session: &Session, metrics: &mut Metrics, query: &HashMap<String, String>) -> Response {
+        let count = match query.get("count").map(|count| count.parse::<usize>()) {
+            None => 5,
+            Some(Ok(count)) => count,
+            Some(Err(_)) => return Response::error(400, "count must be a number"),
+        };
+        let start = Instant::now();
+        let suggestions = session.suggest(count);
+        metrics.suggested(start.elapsed());
+        Response::ok(json!({
+            "solution_space": session.solver().solution_space().len(),
+            "suggestions": suggestions,
+        }))
+    }
+}
+
+/// Sends the ranking of `solver` over an accepted WebSocket connection while it is computed,
+/// see [`HttpServer::stream`].
+fn stream_ranking<W: Write>(solver: &Solver, query: &HashMap<String, String>, mut out: W) -> io::Result<()> {
+    let count = query.get("count").and_then(|count| count.parse().ok()).unwrap_or(5);
+    let total = solver.words().len();
+    let message = |ranking: &[Eval], scored: usize| json!({
+        "scored": scored,
+        "total": total,
+        "suggestions": &ranking[..count.min(ranking.len())],
+        "done": scored == total,
+    }).to_string();
+    let mut result = Ok(());
+    let ranking = solver.suggestions_progressively(HttpServer::STREAM_CHUNK, |ranking, scored| {
+        if result.is_ok() && scored < total {
+            result = websocket::write_text(&mut out, &message(ranking, scored));
+        }
+    });
+    result?;
+    websocket::write_text(&mut out, &message(&ranking, total))?;
+    websocket::write_close(&mut out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_http_server() {
+        let request = "POST /session/ab/feedback?x=1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\n\r\n{}";
+        let request = Request::read(request.as_bytes()).unwrap().unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str(), request.body.as_str()),
+                   ("POST", "/session/ab/feedback", "{}"));
+        assert_eq!(request.query["x"], "1");
+        let long = format!("GET / HTTP/1.1\r\nCookie: {}\r\n\r\n", "a".repeat(MAX_HEAD as usize));
+        assert_eq!(Request::read(long.as_bytes()).unwrap_err().status(), 431);
+        let many = format!("GET / HTTP/1.1\r\n{}\r\n", "X: 1\r\n".repeat(MAX_HEADERS + 1));
+        assert_eq!(Request::read(many.as_bytes()).unwrap_err().status(), 431);
+
+        let words = sample_words();
+        let mut server = HttpServer::new(Solver::new(&words));
+        let none = HashMap::new();
+        let created = server.handle("POST", "/session", &none, "");
+        assert_eq!(created.status, 201);
+        let id = created.body["id"].as_str().unwrap().to_string();
+        let count = HashMap::from([("count".to_string(), "2".to_string())]);
+        let suggestions = server.handle("GET", &format!("/session/{}/suggestions", id), &count, "");
+        assert_eq!(suggestions.body["suggestions"].as_array().unwrap().len(), 2);
+        let bad = server.handle("POST", &format!("/session/{}/feedback", id), &none, r#"{"guess":"crane"}"#);
+        assert_eq!(bad.status, 400);
+        let mut stream = Vec::new();
+        server.stream(&id, &count, &mut stream).unwrap();
+        // A single text frame with the final result, and a close frame.
+        assert_eq!(stream[0], 0x81);
+        let start = if stream[1] == 126 { 4 } else { 2 };
+        let message = serde_json::from_slice::<Value>(&stream[start..stream.len() - 2]).unwrap();
+        assert_eq!(message["done"], true);
+        assert_eq!(message["suggestions"][0]["word"], suggestions.body["suggestions"][0]["word"]);
+        assert_eq!(stream[stream.len() - 2..], [0x88, 0]);
+        assert_eq!(server.handle("DELETE", &format!("/session/{}", id), &none, "").status, 200);
+        let metrics = server.metrics();
+        assert!(metrics.contains("\nwordle_sessions_total 1\n"));
+        assert!(metrics.contains("\nwordle_sessions_active 0\n"));
+        assert!(metrics.contains("\nwordle_games_total{outcome=\"abandoned\"} 1\n"));
+        assert!(metrics.contains("\nwordle_suggestion_seconds_count 1\n"));
+
+        assert_eq!(server.handle("GET", &format!("/session/{}/suggestions", id), &none, "").status, 404);
+
+        let reply = server.slack(&none, "team_id=T1&user_id=U1&text=crane+bygby");
+        assert_eq!(reply.status, 200);
+        assert!(reply.body["text"].as_str().unwrap().contains("`crane`\n3 possible solutions. Try `"));
+        let reply = server.slack(&none, "team_id=T1&user_id=U1&text=reset");
+        assert!(reply.body["text"].as_str().unwrap().starts_with("New game. 5 possible"));
+        for _ in 0..HttpServer::MAX_SESSIONS {
+            server.create();
+        }
+        assert!(server.slack_users.is_empty());
+        let server = &mut HttpServer::new(Solver::new(&words)).with_slack_secret("secret".to_string());
+        assert_eq!(server.slack(&none, "text=").status, 401);
+    }
+}
@@ -0,0 +1,415 @@
+use std::io::Read;
+use std::sync::Arc;
+use miette::Diagnostic;
+use rayon::prelude::*;
+use thiserror::Error;
+use crate::alphabet::Alphabet;
+use crate::batch::GameRecord;
+use crate::error::WordListError;
+use crate::game::{score, SimulatedGame, Solver};
+use crate::matrix::PatternMatrix;
+use crate::packed::{score_packed, PackedWord};
+use crate::pattern::Pattern;
+use crate::style;
+use crate::word::Word;
+
+/// A game from a player's own history, as exported by stats trackers.
+///
+/// # Fields
+/// * `date` - The day the game was played, as written in the import.
+/// * `answer` - The solution of the game.
+/// * `guesses` - The number of guesses the player needed, or `None` if the game was lost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HumanGame {
+    pub date: String,
+    pub answer: Word,
+    pub guesses: Option<usize>,
+}
+
+/// A line of a history import that could not be read.
+#[derive(Debug, Error, Diagnostic)]
+#[error("line {line} of {name}: {reason}")]
+#[diagnostic(help("each line must have a date, the answer and the number of guesses (or X \
+                   for a lost game), e.g. `2024-01-31,crane,4`"))]
+pub struct ImportError {
+    pub name: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Reads a CSV file of past games with the columns `date`, `answer` and `guesses`. If the
+/// first line is a header naming these columns, they may come in any order. Lost games are
+/// written as `X`, like in the share grid. Lines that cannot be read are skipped and added
+/// to `skipped`.
+///
+/// # Errors
+/// If the file cannot be read, or contains no valid game.
+pub fn read_history<R: Read>(name: &str, mut reader: R,
+                             skipped: &mut Vec<ImportError>) -> Result<Vec<HumanGame>, WordListError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)
+        .map_err(|source| WordListError::Io { name: name.to_string(), source })?;
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).peekable();
+
+    let mut columns = [0, 1, 2];
+    if let Some((_, header)) = lines.peek() {
+        let header = header.split(',').map(|c| c.trim().to_lowercase()).collect::<Vec<_>>();
+        let find = |names: &[&str]| header.iter().position(|c| names.contains(&c.as_str()));
+        if let (Some(date), Some(answer), Some(guesses)) =
+            (find(&["date", "day"]), find(&["answer", "solution", "word"]), find(&["guesses", "score"])) {
+            columns = [date, answer, guesses];
+            lines.next();
+        }
+    }
+
+    let mut games = Vec::new();
+    for (i, line) in lines {
+        let fields = line.split(',').map(str::trim).collect::<Vec<&str>>();
+        let error = |reason: String| ImportError { name: name.to_string(), line: i + 1, reason };
+        let [date, answer, guesses] = columns.map(|c| fields.get(c).copied());
+        let (Some(date), Some(answer), Some(guesses)) = (date, answer, guesses) else {
+            skipped.push(error(format!("expected 3 columns, found {}", fields.len())));
+            continue;
+        };
+        let answer = match Word::try_from_str_ignore_case(answer) {
+            Ok(answer) => answer,
+            Err(e) => { skipped.push(error(e.to_string())); continue; }
+        };
+        let guesses = match guesses {
+            "X" | "x" => None,
+            n => match n.parse::<usize>() {
+                Ok(n) if n > 0 => Some(n),
+                _ => { skipped.push(error(format!("<{}> is not a number of guesses", n))); continue; }
+            },
+        };
+        games.push(HumanGame { date: date.to_string(), answer, guesses });
+    }
+    if games.is_empty() {
+        return Err(WordListError::Empty { name: name.to_string() });
+    }
+    Ok(games)
+}
+
+/// Compares a player's history with what the solver scores on the same answers.
+///
+/// Besides the number of guesses, the comparison is made in bits: on an answer the solver
+/// needs `s` guesses for, it gains `log2(n) / s` bits of information per guess on average,
+/// where `n` is the number of allowed words. Every guess the player needs beyond `s` is
+/// counted as that many bits "left on the table"; a player who beats the solver gets bits back.
+pub struct HumanComparison {
+    games: Vec<(HumanGame, GameRecord)>,
+}
+
+impl HumanComparison {
+    /// Scores a lost game like the solver's failures: one guess beyond the limit.
+    const LOST: usize = SimulatedGame::MAX_ROUNDS as usize + 1;
+
+    /// Simulates the solver, starting with `first_guess`, on the answers of `games`.
+    pub fn new(words: &[Word], games: Vec<HumanGame>, first_guess: Word) -> HumanComparison {
+        let matrix = Arc::new(PatternMatrix::new(words, words));
+        let games = games.into_par_iter().map(|game| {
+            let mut solver = SimulatedGame::new(words, game.answer, first_guess)
+                .with_matrix(Arc::clone(&matrix))
+                .quiet();
+            solver.run_game();
+            let record = solver.record();
+            (game, record)
+        }).collect();
+        HumanComparison { games }
+    }
+
+    fn human_guesses(game: &HumanGame) -> usize {
+        game.guesses.unwrap_or(Self::LOST)
+    }
+
+    fn solver_guesses(record: &GameRecord) -> usize {
+        if record.solved { record.rounds() } else { Self::LOST }
+    }
+
+    /// The bits the player left on the table in a game, negative if they beat the solver.
+    pub fn bits_left(game: &HumanGame, record: &GameRecord) -> f64 {
+        let solver = Self::solver_guesses(record);
+        let total = (*record.space_sizes.first().unwrap_or(&1) as f64).log2();
+        (Self::human_guesses(game) as f64 - solver as f64) * total / solver as f64
+    }
+
+    pub fn print(&self) {
+        let n = self.games.len().max(1) as f64;
+        let human = self.games.iter().map(|(g, _)| Self::human_guesses(g)).sum::<usize>() as f64 / n;
+        let solver = self.games.iter().map(|(_, r)| Self::solver_guesses(r)).sum::<usize>() as f64 / n;
+        let human_failures = self.games.iter().filter(|(g, _)| g.guesses.is_none()).count();
+        let solver_failures = self.games.iter().filter(|(_, r)| !r.solved).count();
+        let (mut won, mut tied, mut lost) = (0, 0, 0);
+        for (game, record) in &self.games {
+            match Self::human_guesses(game).cmp(&Self::solver_guesses(record)) {
+                std::cmp::Ordering::Less => won += 1,
+                std::cmp::Ordering::Equal => tied += 1,
+                std::cmp::Ordering::Greater => lost += 1,
+            }
+        }
+        let bits = self.games.iter().map(|(g, r)| Self::bits_left(g, r)).sum::<f64>();
+
+        println!("{}", style::bold(format_args!("Your history ({} games) against the solver:", self.games.len())));
+        println!("            you  solver");
+        println!("average  {:>6.2}  {:>6.2}", human, solver);
+        println!("failures {:>6}  {:>6}", human_failures, solver_failures);
+        println!("You beat the solver in {} games, tied in {} and needed more guesses in {}.", won, tied, lost);
+        println!("{} {:.1} ({:.2} per game)", style::bold("Bits left on the table:"), bits, bits / n);
+
+        let mut worst = self.games.iter().collect::<Vec<_>>();
+        worst.sort_by(|a, b| f64::total_cmp(&Self::bits_left(&b.0, &b.1), &Self::bits_left(&a.0, &a.1)));
+        println!("{}", style::bold("Games with the most bits left:"));
+        for (game, record) in worst.iter().take(5).filter(|(g, r)| Self::bits_left(g, r) > 0.0) {
+            let guesses = game.guesses.map_or("X".to_string(), |n| n.to_string());
+            println!("{} {}: you {}, solver {} ({}) → {:.1} bits", game.date, game.answer, guesses,
+                     record.rounds(), record.guesses.iter().map(Word::to_string).collect::<Vec<_>>().join(", "),
+                     Self::bits_left(game, record));
+        }
+    }
+}
+
+/// How one guess of a game compares to the best guess of its round, see [`Grade`].
+///
+/// # Fields
+/// * `guess` - The guess that was played.
+/// * `pattern` - The feedback it got.
+/// * `rank` - The rank of its entropy among all allowed words, starting at 1.
+/// * `entropy` - The information the guess was expected to reveal, in bits.
+/// * `best` - The guess with the highest entropy in its round.
+/// * `best_entropy` - The entropy of `best`.
+/// * `gained` - The information the feedback actually revealed, in bits.
+/// * `remaining` - The number of solutions left after the guess.
+pub struct GuessGrade {
+    pub guess: Word,
+    pub pattern: Pattern,
+    pub rank: usize,
+    pub entropy: f64,
+    pub best: Word,
+    pub best_entropy: f64,
+    pub gained: f64,
+    pub remaining: usize,
+}
+
+/// Grades a finished game against the solver, round by round: how every guess ranks among
+/// all allowed words by entropy, and how much information it was expected to reveal compared
+/// to the best guess of its round.
+///
+/// The skill score is the average share of the best entropy that the guesses achieved, from
+/// 0 to 100. Rounds where only the answer was left count fully if the answer was guessed.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::human::Grade;
+/// use wordl_rust_bot::Word;
+///
+/// let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+/// let guesses = ["slimy", "gears"].map(Word::from_str);
+/// let grade = Grade::new(&words, &words, Word::from_str("gears"), &guesses);
+/// assert_eq!(grade.guesses[0].remaining, 3);
+/// assert_eq!(grade.guesses[1].remaining, 1);
+/// assert!(grade.skill() < 100.0);
+/// ```
+pub struct Grade {
+    pub guesses: Vec<GuessGrade>,
+}
+
+impl Grade {
+    /// Replays `guesses` against `answer`, with guesses taken from `words` and solutions from
+    /// `solutions`.
+    pub fn new(words: &[Word], solutions: &[Word], answer: Word, guesses: &[Word]) -> Grade {
+        let rounds = guesses.iter().map(|guess| (*guess, score(guess, &answer))).collect::<Vec<_>>();
+        Grade::from_feedback(words, solutions, &rounds)
+    }
+
+    /// Replays `rounds` of guesses and the feedback they got, e.g. from a
+    /// [`Transcript`](crate::transcript::Transcript), without knowing the answer. Grading stops
+    /// early if no solution fits the feedback anymore.
+    pub fn from_feedback(words: &[Word], solutions: &[Word], rounds: &[(Word, Pattern)]) -> Grade {
+        Grade::with_matrix(words, solutions, Arc::new(PatternMatrix::new(words, solutions)), rounds)
+    }
+
+    /// Like [`Grade::from_feedback`], but looks up feedback in `matrix`, which must have been
+    /// built for `words` and `solutions`, so that many games can be graded with one matrix.
+    ///
+    /// # Panics
+    /// If `matrix` was built for other word lists.
+    pub fn with_matrix(words: &[Word], solutions: &[Word], matrix: Arc<PatternMatrix>,
+                       rounds: &[(Word, Pattern)]) -> Grade {
+        Grade::with_solver(Solver::with_solutions(words, solutions).with_matrix(matrix), rounds)
+    }
+
+    /// Like [`Grade::from_feedback`], but grades against `solver`, e.g. one with word weights
+    /// (see [`Solver::with_weights`]), so that the entropies and the information gained count
+    /// common answers as more likely. `solver` must not have had feedback yet; to grade many
+    /// games, set it up once and pass a clone for every game.
+    pub fn with_solver(mut solver: Solver, rounds: &[(Word, Pattern)]) -> Grade {
+        let words = solver.words();
+        let mut grades = Vec::with_capacity(rounds.len());
+        for (guess, pattern) in rounds {
+            let before = solver.solution_space().len();
+            if before == 0 {
+                break;
+            }
+            let weight = solver.solution_weight();
+            let ranking = solver.suggestions(words.len());
+            let entropy = solver.entropy(guess);
+            // Among equally good guesses, prefer one that could win right away.
+            let best = match solver.solution_space() {
+                [solution] => (**solution, 0.0),
+                space => ranking.iter()
+                    .take_while(|e| e.entropy > ranking[0].entropy - 1e-9)
+                    .find(|e| space.contains(&e.word))
+                    .map_or((*ranking[0].word, ranking[0].entropy), |e| (*e.word, e.entropy)),
+            };
+            let rank = ranking.iter().filter(|e| e.entropy > entropy + 1e-9).count() + 1;
+            solver.feedback(*guess, *pattern);
+            let remaining = solver.solution_space().len();
+            let gained = match remaining {
+                0 => (before as f64).log2(),
+                _ => (weight / solver.solution_weight()).log2(),
+            };
+            grades.push(GuessGrade {
+                guess: *guess,
+                pattern: *pattern,
+                rank,
+                entropy,
+                best: best.0,
+                best_entropy: best.1,
+                gained,
+                remaining,
+            });
+        }
+        Grade { guesses: grades }
+    }
+
+    /// The skill score of the game, from 0 to 100.
+    pub fn skill(&self) -> f64 {
+        let shares = self.guesses.iter().map(|grade| match grade.best_entropy > 0.0 {
+            true => grade.entropy / grade.best_entropy,
+            false => (grade.guess == grade.best) as u8 as f64,
+        });
+        100.0 * shares.sum::<f64>() / self.guesses.len().max(1) as f64
+    }
+
+    pub fn print(&self) {
+        println!("{}", style::bold("round guess  pattern  rank  entropy  best          gained  left"));
+        for (round, grade) in self.guesses.iter().enumerate() {
+            println!("{:>5} {}  {}   {:>5}  {:>7.2}  {} ({:>5.2})  {:>6.2}  {:>4}", round + 1, grade.guess,
+                     grade.pattern, grade.rank, grade.entropy, grade.best, grade.best_entropy, grade.gained, grade.remaining);
+        }
+        let expected = self.guesses.iter().map(|g| g.entropy).sum::<f64>();
+        let gained = self.guesses.iter().map(|g| g.gained).sum::<f64>();
+        println!("{} {:.0}/100", style::bold("Skill:"), self.skill());
+        println!("{} {:+.2} bits ({:.2} gained, {:.2} expected)", style::bold("Luck:"), gained - expected, gained,
+                 expected);
+    }
+}
+
+/// Reconstructs a game from a share grid posted without words, see
+/// [`crate::share::parse_grid`]: which answers could have produced the grid, and for a given
+/// answer, which guesses fit every row.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::human::ShareAnalysis;
+/// use wordl_rust_bot::{Pattern, Word};
+///
+/// let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+/// let grid = vec![Pattern::from_string("bbbbb"), Pattern::all_green()];
+/// let analysis = ShareAnalysis::new(&words, &words, grid);
+/// assert_eq!(analysis.answers(), [&Word::from_str("crane"), &Word::from_str("slimy")]);
+/// assert_eq!(analysis.guesses(&Word::from_str("crane"), 0), [&Word::from_str("slimy")]);
+/// ```
+pub struct ShareAnalysis<'a> {
+    words: &'a [Word],
+    patterns: Vec<Pattern>,
+    answers: Vec<&'a Word>,
+}
+
+impl<'a> ShareAnalysis<'a> {
+    /// The number of example guesses printed per row.
+    const EXAMPLES: usize = 8;
+
+    /// Finds the answers among `candidates` for which every row of `patterns` is the feedback
+    /// of some guess from `words`.
+    pub fn new(words: &'a [Word], candidates: &'a [Word], patterns: Vec<Pattern>) -> ShareAnalysis<'a> {
+        let alphabet = Alphabet::of(words.iter().chain(candidates));
+        let packed = PackedWord::fits(&alphabet).then(|| PackedWord::pack_all(words, &alphabet));
+        let answers = candidates.par_iter().filter(|answer| {
+            let mut possible = [false; Pattern::MAX];
+            match &packed {
+                Some(packed) => {
+                    let answer = PackedWord::new(answer, &alphabet);
+                    for guess in packed {
+                        possible[score_packed(*guess, answer).index()] = true;
+                    }
+                }
+                None => for guess in words {
+                    possible[score(guess, answer).index()] = true;
+                },
+            }
+            patterns.iter().all(|pattern| possible[pattern.index()])
+        }).collect();
+        ShareAnalysis { words, patterns, answers }
+    }
+
+    /// The answers that fit the grid.
+    pub fn answers(&self) -> &[&'a Word] {
+        &self.answers
+    }
+
+    /// The guesses that give the feedback of row `row` of the grid when the answer is `answer`.
+    pub fn guesses(&self, answer: &Word, row: usize) -> Vec<&'a Word> {
+        let pattern = self.patterns[row];
+        self.words.iter().filter(|guess| score(guess, answer) == pattern).collect()
+    }
+
+    /// Prints the answers that fit the grid, and if there is only one, the guesses that fit
+    /// each row and the number of guess sequences that fit the whole grid.
+    pub fn print(&self) {
+        match self.answers.len() {
+            0 => println!("{} in the list fits this grid.", style::bold("No answer")),
+            1 => println!("{}", style::bold(format_args!("The answer was {}.", self.answers[0]))),
+            n => {
+                let examples = self.answers.iter().take(20).map(|w| w.to_string()).collect::<Vec<String>>();
+                println!("{} {}{}", style::bold(format_args!("{} possible answers:", n)), examples.join(", "),
+                         if n > examples.len() { ", …" } else { "" });
+            }
+        }
+        let [answer] = self.answers.as_slice() else {
+            return;
+        };
+        let mut sequences = 1.0;
+        for (row, pattern) in self.patterns.iter().enumerate() {
+            let guesses = self.guesses(answer, row);
+            sequences *= guesses.len() as f64;
+            let examples = guesses.iter().take(Self::EXAMPLES).map(|w| w.to_string()).collect::<Vec<String>>();
+            println!("{} {} {:>5} words: {}{}", row + 1, pattern, guesses.len(), examples.join(", "),
+                     if guesses.len() > examples.len() { ", …" } else { "" });
+        }
+        println!("{} fit the grid.", style::bold(format_args!("{:.0} guess sequences", sequences)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_history() {
+        let text = "Guesses,Date,Answer\n4,2024-01-01,crane\nX,2024-01-02,tears\n3,2024-01-03,cran\n\n";
+        let mut skipped = Vec::new();
+        let games = read_history("history.csv", text.as_bytes(), &mut skipped).unwrap();
+        assert_eq!(games, vec![
+            HumanGame { date: "2024-01-01".to_string(), answer: Word::from_str("crane"), guesses: Some(4) },
+            HumanGame { date: "2024-01-02".to_string(), answer: Word::from_str("tears"), guesses: None },
+        ]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 4);
+
+        let games = read_history("history.csv", "2024-01-01,crane,2".as_bytes(), &mut skipped).unwrap();
+        assert_eq!(games[0].guesses, Some(2));
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use crate::error::WordleError;
+use crate::style;
+
+/// Prints `text` as a bold prompt and flushes standard output, so the prompt is visible
+/// before the user starts typing.
+pub fn prompt(text: &str) -> Result<(), WordleError> {
+    print!("{} ", style::bold(text));
+    io::stdout().flush()?;
+    Ok(())
+}
+
+/// Reads a single line from standard input, retrying if reading is interrupted by a signal.
+///
+/// # Errors
+/// * [`WordleError::Io`] if standard input cannot be read.
+/// * [`WordleError::EndOfInput`] if standard input has ended.
+pub fn read_line() -> Result<String, WordleError> {
+    let mut line = String::new();
+    loop {
+        match io::stdin().read_line(&mut line) {
+            Ok(0) => return Err(WordleError::EndOfInput),
+            Ok(_) => return Ok(line),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Ends a game whose round failed with `error`. If standard input ended or could not be
+/// read, `summary` is called to show where the game stopped.
+///
+/// # Returns
+/// `Ok` if standard input simply ended, so that the game counts as ended cleanly.
+///
+/// # Errors
+/// `error` itself if it is anything else.
+pub fn end_game(error: WordleError, summary: impl FnOnce()) -> Result<(), WordleError> {
+    match error {
+        WordleError::EndOfInput => {
+            summary();
+            Ok(())
+        }
+        WordleError::Io(_) => {
+            summary();
+            Err(error)
+        }
+        error => Err(error),
+    }
+}
+
+/// Reads lines from standard input until one of them can be parsed by `parse`. For every
+/// line that cannot be parsed, the problem is shown and the user is asked again.
+///
+/// # Errors
+/// The errors of [`read_line`].
+pub fn read_parsed<T, E: Display>(parse: impl Fn(&str) -> Result<T, E>) -> Result<T, WordleError> {
+    loop {
+        match parse(&read_line()?) {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                print!("{}. Try again: ", e);
+                io::stdout().flush()?;
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// The number of [`Evaluation`]s running, so that Ctrl+C only stops the program when none is.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether the last [`Evaluation`] was interrupted with Ctrl+C.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Makes Ctrl+C interrupt a running [`Evaluation`], which then returns the best suggestions
+/// computed so far, instead of ending the program. Pressed while no evaluation is running, or
+/// a second time during one, Ctrl+C ends the program with status 130, as usual. Not
+/// available in WebAssembly, where there is no Ctrl+C to handle.
+///
+/// # Errors
+/// If a handler for Ctrl+C is set already or cannot be set.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn install_handler() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        if RUNNING.load(Ordering::SeqCst) == 0 || INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+    })
+}
+
+/// Whether the last evaluation was interrupted with Ctrl+C, e.g. to tell the user that the
+/// suggestions are incomplete.
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Interrupts the running evaluations, as if Ctrl+C was pressed.
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// A scan over the word list that may be cut short, by Ctrl+C or when its deadline has
+/// passed. It counts as running until it is dropped.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::interrupt::{self, Evaluation};
+///
+/// let evaluation = Evaluation::start(None);
+/// assert!(!evaluation.should_stop());
+/// interrupt::interrupt();
+/// assert!(evaluation.should_stop());
+/// drop(evaluation);
+/// assert!(!Evaluation::start(None).should_stop());
+/// ```
+pub struct Evaluation {
+    deadline: Option<Instant>,
+}
+
+impl Evaluation {
+    /// Starts an evaluation that stops at `deadline`, if given. Unless another evaluation is
+    /// running, this forgets an earlier interruption.
+    pub fn start(deadline: Option<Instant>) -> Evaluation {
+        if RUNNING.fetch_add(1, Ordering::SeqCst) == 0 {
+            INTERRUPTED.store(false, Ordering::SeqCst);
+        }
+        Evaluation { deadline }
+    }
+
+    /// Whether the evaluation should return what it has computed so far.
+    pub fn should_stop(&self) -> bool {
+        interrupted() || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+impl Drop for Evaluation {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
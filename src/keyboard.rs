@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use crate::pattern::{Color, Pattern};
+use crate::style::{self, Palette, Styled};
+use crate::word::{Word, WORD_LENGTH};
+
+/// The rows of a QWERTY keyboard, as drawn by [`Keyboard`].
+const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// What the feedback so far reveals about every letter, as shown on the keyboard of the
+/// real game: green if the letter was green somewhere, yellow if it is in the solution but
+/// was never green, gray if it is not in the solution, and unmarked if it was not guessed yet.
+/// Guessed letters that are not on a QWERTY keyboard, such as `ä` or `ж`, get a row of their own.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::keyboard::Keyboard;
+/// use wordl_rust_bot::{Color, Pattern, Word};
+///
+/// let mut keyboard = Keyboard::new();
+/// keyboard.update(&Word::from_str("crane"), Pattern::from_string("bbygb"));
+/// assert!(keyboard.status('n') == Some(Color::Green));
+/// assert!(keyboard.status('a') == Some(Color::Yellow));
+/// assert!(keyboard.status('q').is_none());
+/// ```
+#[derive(Clone, Default)]
+pub struct Keyboard {
+    letters: BTreeMap<char, Color>,
+}
+
+impl Keyboard {
+    pub fn new() -> Keyboard {
+        Keyboard::default()
+    }
+
+    /// Adds the feedback `pattern` for `guess`. A letter only ever moves up from gray to
+    /// yellow to green: a gray repeated letter does not hide that it was yellow elsewhere.
+    pub fn update(&mut self, guess: &Word, pattern: Pattern) {
+        for i in 0..WORD_LENGTH {
+            let status = self.letters.get(&guess.letter(i)).copied();
+            let status = match (status, pattern[i]) {
+                (_, Color::Green) | (Some(Color::Green), _) => Color::Green,
+                (_, Color::Yellow) | (Some(Color::Yellow), _) => Color::Yellow,
+                (_, Color::Black) => Color::Black,
+            };
+            self.letters.insert(guess.letter(i), status);
+        }
+    }
+
+    /// The status of the lowercase letter `letter`, or `None` if it was not guessed yet.
+    pub fn status(&self, letter: char) -> Option<Color> {
+        self.letters.get(&letter).copied()
+    }
+}
+
+impl Display for Keyboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let others = self.letters.keys().filter(|letter| !ROWS.iter().any(|row| row.contains(**letter)))
+            .collect::<String>();
+        let rows = ROWS.iter().copied().chain(Some(others.as_str()).filter(|others| !others.is_empty()))
+            .collect::<Vec<&str>>();
+        for (indent, row) in rows.iter().enumerate() {
+            write!(f, "{}", " ".repeat(indent.min(ROWS.len() - 1)))?;
+            for letter in row.chars() {
+                match (self.status(letter), style::enabled()) {
+                    (Some(color), true) => write!(f, " {}", Styled::new(Palette::current().key_code(color), letter))?,
+                    // Without colors, green letters are uppercase, yellow ones marked and gray ones hidden.
+                    (Some(Color::Green), false) => write!(f, " {}", letter.to_uppercase())?,
+                    (Some(Color::Yellow), false) => write!(f, "*{}", letter)?,
+                    (Some(Color::Black), false) => write!(f, " .")?,
+                    (None, _) => write!(f, " {}", letter)?,
+                }
+            }
+            if indent + 1 < rows.len() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keyboard() {
+        let mut keyboard = Keyboard::new();
+        keyboard.update(&Word::from_str("geese"), Pattern::from_string("bybgb"));
+        assert!(keyboard.status('e') == Some(Color::Yellow));
+        assert!(keyboard.status('s') == Some(Color::Green));
+        assert!(keyboard.status('g') == Some(Color::Black));
+        keyboard.update(&Word::from_str("eerie"), Pattern::from_string("gbbbb"));
+        assert!(keyboard.status('e') == Some(Color::Green));
+        assert!(keyboard.status('r') == Some(Color::Black));
+        assert!(keyboard.status('A').is_none());
+        assert_eq!(keyboard.to_string().lines().count(), 3);
+        keyboard.update(&Word::from_str("äpfel"), Pattern::from_string("ybbbb"));
+        assert!(keyboard.status('ä') == Some(Color::Yellow));
+        assert_eq!(keyboard.to_string().lines().count(), 4);
+    }
+}
@@ -0,0 +1,80 @@
+//! A solver for Wordle and its variants.
+//!
+//! The solver suggests the guesses with the highest entropy, that is, the guesses whose
+//! feedback is expected to reveal the most about the solution. The main entry points are
+//!
+//! * [`Word`] and [`Pattern`] - a guess and the feedback the game gives for it,
+//! * [`score`] - the feedback a guess gets for a given solution,
+//! * [`Solver`] - the solver itself, which tracks the remaining solutions and suggests guesses,
+//! * [`GameState`] - the history of a game, which can be stored and replayed.
+//!
+//! # Example
+//!
+//! ```
+//! use wordl_rust_bot::{score, Solver, Word};
+//!
+//! let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+//! let solution = Word::from_str("gears");
+//! let mut solver = Solver::new(&words);
+//! while solver.solution_space().len() > 1 {
+//!     let guess = solver.best_guess();
+//!     solver.feedback(guess, score(&guess, &solution));
+//! }
+//! assert_eq!(*solver.solution_space()[0], solution);
+//! ```
+
+pub mod pattern;
+pub mod word;
+pub mod alphabet;
+pub mod locale;
+pub mod wordlist;
+pub mod game;
+pub mod matrix;
+pub mod bench;
+pub mod packed;
+pub mod tree;
+pub mod transcript;
+pub mod share;
+pub mod keyboard;
+pub mod phrase;
+pub mod batch;
+pub mod progress;
+pub mod cache;
+pub mod bundle;
+pub mod dictionary;
+pub mod daily;
+pub mod stats;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+pub mod human;
+pub mod server;
+#[cfg(feature = "serde")]
+pub mod http;
+pub mod metrics;
+pub mod websocket;
+#[cfg(feature = "serde")]
+pub mod slack;
+#[cfg(feature = "discord")]
+pub mod discord;
+#[cfg(feature = "telegram")]
+pub mod telegram;
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod error;
+pub mod exit;
+pub mod input;
+pub mod interrupt;
+pub mod style;
+pub mod output;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+#[cfg(feature = "sqlite")]
+pub mod db;
+
+pub use crate::error::WordleError;
+pub use crate::game::{score, Constraints, Estimate, Eval, GameState, GuessCheck, Solver, Strategy};
+pub use crate::pattern::{Color, Pattern, PatternParseError};
+pub use crate::word::{read_words, read_words_ignore_case, read_words_lenient, Word, WordError, WORD_LENGTH};
@@ -0,0 +1,170 @@
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The rules for converting words to lowercase, see [`crate::Word::try_from_str_ignore_case`].
+///
+/// The locale is a setting of the whole process, like the number of threads, chosen once at
+/// startup with [`Locale::set_current`]. It is not taken from `LANG`, because the right rules
+/// depend on the language of the word list, not on that of the user: Turkish rules would turn
+/// the `I` of `SLIMY` into a dotless `ı`.
+///
+/// # Variants
+/// * `Root` - The rules of Unicode that apply to all languages.
+/// * `Turkic` - The rules for Turkish and Azerbaijani, where `I` is the uppercase of the dotless
+///   `ı`, and `İ` that of the dotted `i`.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::locale::Locale;
+///
+/// assert_eq!(Locale::Turkic.to_lowercase("IŞIK"), "ışık");
+/// assert_eq!(Locale::Turkic.to_lowercase("İNCİR"), "incir");
+/// assert_eq!(Locale::Root.to_lowercase("IŞIK"), "işik");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    Root,
+    Turkic,
+}
+
+/// The current locale, as the index of the variant.
+static CURRENT: AtomicU8 = AtomicU8::new(Locale::Root as u8);
+
+impl Locale {
+    /// The locale for the language tag `tag`, e.g. `tr`, `tr-TR` or `az_AZ.UTF-8`. Languages
+    /// without special rules get [`Locale::Root`].
+    pub fn from_tag(tag: &str) -> Locale {
+        let language = tag.split(['-', '_', '.', '@']).next().unwrap_or("");
+        match language.to_ascii_lowercase().as_str() {
+            "tr" | "az" => Locale::Turkic,
+            _ => Locale::Root,
+        }
+    }
+
+    /// The locale used for input, [`Locale::Root`] unless set otherwise.
+    pub fn current() -> Locale {
+        match CURRENT.load(Ordering::Relaxed) {
+            1 => Locale::Turkic,
+            _ => Locale::Root,
+        }
+    }
+
+    /// Makes `self` the locale used for input from now on.
+    pub fn set_current(self) {
+        CURRENT.store(self as u8, Ordering::Relaxed);
+    }
+
+    /// Converts `text` to lowercase by the rules of the locale. Unlike converting character by
+    /// character, this keeps the final form of the Greek sigma, as [`str::to_lowercase`] does.
+    pub fn to_lowercase(self, text: &str) -> String {
+        match self {
+            Locale::Root => text.to_lowercase(),
+            Locale::Turkic => text.replace('I', "ı").replace('İ', "i").to_lowercase(),
+        }
+    }
+}
+
+/// Letters with diacritics and the base letters they are folded to, see [`AccentFolding`].
+const ACCENTS: &[(&str, char)] = &[
+    ("àáâãäåāăą", 'a'), ("çćĉċč", 'c'), ("ďđ", 'd'), ("èéêëēĕėęě", 'e'), ("ĝğġģ", 'g'),
+    ("ĥħ", 'h'), ("ìíîïĩīĭį", 'i'), ("ĵ", 'j'), ("ķ", 'k'), ("ĺļľŀł", 'l'), ("ñńņňŉ", 'n'),
+    ("òóôõöøōŏő", 'o'), ("ŕŗř", 'r'), ("śŝşšș", 's'), ("ţťŧț", 't'), ("ùúûüũūŭůűų", 'u'),
+    ("ŵ", 'w'), ("ýÿŷ", 'y'), ("źżž", 'z'), ("ё", 'е'), ("ї", 'і'),
+];
+
+/// Maps letters with diacritics to their base letters, as many Wordle clones in languages other
+/// than English do: the Portuguese *Termo* accepts `acao` for `ação`, and a Russian game counts
+/// `ё` as `е`. Letters that the language treats as letters of their own, such as the Spanish `ñ`,
+/// can be kept.
+///
+/// Like the [`Locale`], the folding is a setting of the whole process, chosen once at startup
+/// with [`AccentFolding::set_current`]. Without it, accented letters are distinct letters.
+///
+/// # Fields
+/// * `keep` - The letters with diacritics that are not folded.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::locale::AccentFolding;
+///
+/// assert_eq!(AccentFolding::new("").fold("ação"), "acao");
+/// assert_eq!(AccentFolding::new("ñ").fold("niño"), "niño");
+/// assert_eq!(AccentFolding::new("ñ").fold("pingüino"), "pinguino");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccentFolding {
+    keep: Vec<char>,
+}
+
+/// The accent folding used for input, if any.
+static FOLDING: OnceLock<AccentFolding> = OnceLock::new();
+
+impl AccentFolding {
+    /// Folds every letter with diacritics except the letters of `keep`.
+    pub fn new(keep: &str) -> AccentFolding {
+        AccentFolding { keep: keep.chars().collect() }
+    }
+
+    /// The accent folding used for input, or `None` if accented letters are distinct letters.
+    pub fn current() -> Option<&'static AccentFolding> {
+        FOLDING.get()
+    }
+
+    /// Makes `self` the accent folding used for input. Only the first call has an effect.
+    pub fn set_current(self) {
+        let _ = FOLDING.set(self);
+    }
+
+    /// The base letter of `letter`, or `letter` itself if it has no diacritics or is kept.
+    pub fn fold_letter(&self, letter: char) -> char {
+        if self.keep.contains(&letter) {
+            return letter;
+        }
+        ACCENTS.iter()
+            .find(|(accented, _)| accented.contains(letter))
+            .map_or(letter, |(_, base)| *base)
+    }
+
+    /// Folds every letter of `text`, see [`AccentFolding::fold_letter`].
+    pub fn fold(&self, text: &str) -> String {
+        text.chars().map(|letter| self.fold_letter(letter)).collect()
+    }
+}
+
+/// Converts `text` to lowercase by the rules of [`Locale::current`], and folds its accents with
+/// [`AccentFolding::current`] if that is set. This is how words typed by the user and entries
+/// of word lists are normalized.
+pub fn normalize(text: &str) -> String {
+    let text = Locale::current().to_lowercase(text);
+    match AccentFolding::current() {
+        Some(folding) => folding.fold(&text),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(Locale::from_tag("tr_TR.UTF-8"), Locale::Turkic);
+        assert_eq!(Locale::from_tag("AZ"), Locale::Turkic);
+        assert_eq!(Locale::from_tag("en-US"), Locale::Root);
+        assert_eq!(Locale::Root.to_lowercase("İ").chars().count(), 2);
+        assert_eq!(Locale::Turkic.to_lowercase("Iİ"), "ıi");
+        assert_eq!(Locale::Root.to_lowercase("ΟΔΟΣ"), "οδο\u{3c2}");
+    }
+
+    #[test]
+    fn test_accent_folding() {
+        let folding = AccentFolding::new("ñ");
+        assert_eq!(folding.fold("çàñéë"), "cañee");
+        assert_eq!(folding.fold("ёлка"), "елка");
+        assert_eq!(folding.fold_letter('ß'), 'ß');
+        assert!(ACCENTS.iter().all(|(accented, base)| accented.chars().all(|c| c.is_lowercase() && c != *base)));
+    }
+}
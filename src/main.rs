@@ -1,12 +1,55 @@
-mod pattern;
-mod word;
-mod game;
-
-use crate::word::*;
-use clap::{Parser, Subcommand};
-use clio::Input;
-use std::io::{BufRead, BufReader, Read};
-use crate::game::{HelpGame, PlayGame, SimulatedGame};
+use std::fs::File;
+use std::io::{self, IsTerminal, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clio::{Input, Output};
+use rayon::prelude::*;
+use wordl_rust_bot::bench;
+use wordl_rust_bot::cache::ScoreCache;
+use wordl_rust_bot::bundle::{bundled_lists, write_bundle, BundledList};
+use wordl_rust_bot::daily::{Date, METADATA_URL};
+use wordl_rust_bot::dictionary::{Dictionary, DICTIONARIES};
+use wordl_rust_bot::locale::{AccentFolding, Locale};
+#[cfg(feature = "fetch")]
+use wordl_rust_bot::fetch;
+#[cfg(all(feature = "fetch", feature = "serde"))]
+use wordl_rust_bot::{daily::Puzzle, error::FetchError};
+use wordl_rust_bot::batch::{backtest, compare, tournament, GuessHistogram, LetterStats, Manifest, OpenerResult, Prior, RecordFormat, RecordWriter};
+use wordl_rust_bot::human::{read_history, Grade, HumanComparison, ShareAnalysis};
+use wordl_rust_bot::matrix::PatternMatrix;
+use wordl_rust_bot::wordlist::{filter_length, merge, validate, Sharpening, WeightedList};
+use wordl_rust_bot::phrase::{read_phrases, PhraseGame};
+use wordl_rust_bot::share::parse_grid;
+use wordl_rust_bot::output::Verbosity;
+use wordl_rust_bot::progress::Progress;
+use wordl_rust_bot::stats::{PlayRecord, Stats};
+use wordl_rust_bot::style::{self, ColorChoice, Palette};
+use wordl_rust_bot::server::{serve_lines, Session};
+#[cfg(feature = "serde")]
+use wordl_rust_bot::http::HttpServer;
+#[cfg(feature = "discord")]
+use wordl_rust_bot::discord::DiscordBot;
+#[cfg(feature = "telegram")]
+use wordl_rust_bot::telegram::TelegramBot;
+#[cfg(feature = "serde")]
+use wordl_rust_bot::transcript::{read_transcripts, TranscriptLog};
+use wordl_rust_bot::transcript::Transcript;
+#[cfg(feature = "sqlite")]
+use wordl_rust_bot::{db::{ResultStore, Run, StoredGame}, transcript::Mode};
+use wordl_rust_bot::tree::{DecisionTree, GraphFormat, TreeFormat};
+use wordl_rust_bot::game::{antiwordle_opener, AbsurdleGame, AntiwordleGame, CheatSheet, ClusterView, HelpGame, MultiGame, PlayGame, SimulatedGame};
+use wordl_rust_bot::error::WordListError;
+use wordl_rust_bot::interrupt;
+use wordl_rust_bot::exit::ExitStatus;
+#[cfg(feature = "serde")]
+use wordl_rust_bot::exit::error_json;
+use wordl_rust_bot::{score, GameState, GuessCheck, Solver, Strategy, Word, WordleError};
 
 /// A program to solve wordle for you!
 #[derive(Parser)]
@@ -14,77 +57,2035 @@ use crate::game::{HelpGame, PlayGame, SimulatedGame};
 #[command(propagate_version = true)]
 struct Cli {
     #[command(subcommand)]
-    command: SubCommand
+    command: SubCommand,
+    /// The number of threads to score words with. By default, the value of the
+    /// `RAYON_NUM_THREADS` environment variable, or one thread per CPU core.
+    #[clap(long, global = true)]
+    threads: Option<NonZeroUsize>,
+    /// The dictionary compiled into the program that is used when no word list is given.
+    #[clap(long, global = true, default_value = DICTIONARIES[0].name,
+           value_parser = PossibleValuesParser::new(DICTIONARIES.iter().map(|d| d.name)))]
+    dict: String,
+    /// Read word lists as they are, skipping entries with uppercase letters, instead of
+    /// converting them to lowercase.
+    #[clap(long, global = true)]
+    keep_case: bool,
+    /// The language whose rules are used to convert guesses and word lists to lowercase, e.g.
+    /// `tr` for Turkish, where `I` becomes the dotless `ı`. By default, the rules that apply to
+    /// all languages.
+    #[clap(long, global = true, value_name = "LANGUAGE")]
+    locale: Option<String>,
+    /// Treat letters with diacritics as their base letters in guesses and word lists, e.g.
+    /// `ação` as `acao`, as many Wordle clones in other languages do.
+    #[clap(long, global = true)]
+    fold_accents: bool,
+    /// Letters that are kept distinct with `--fold-accents`, e.g. `ñ` for Spanish.
+    #[clap(long, global = true, value_name = "LETTERS", requires = "fold_accents")]
+    keep_letters: Option<String>,
+    /// Print only results, e.g. the final summary of `batch`, without a line per game or a
+    /// progress bar.
+    #[clap(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Print details, e.g. every remaining solution and how the best suggestion splits them
+    /// in every round of `assist`.
+    #[clap(short, long, global = true)]
+    verbose: bool,
+    /// When to color the output. `auto` colors it only on a terminal, and not if the
+    /// `NO_COLOR` environment variable is set.
+    #[clap(long, global = true, value_enum, default_value_t = ColorArg::Auto)]
+    color: ColorArg,
+    /// Show greens in orange and yellows in blue, in boards, keyboards and share grids, for
+    /// players who cannot tell green from yellow.
+    #[clap(long, global = true)]
+    high_contrast: bool,
+    /// How to print an error that ends the program. `json` prints it as one JSON object on
+    /// standard output, with the exit status as `code`, as `assist --format json` always does.
+    #[clap(long, global = true, value_enum, default_value_t = Format::Text)]
+    error_format: Format,
+    /// Append a transcript of every game of `assist`, `play` and `batch` to this JSON Lines
+    /// file: the solution if known, and every guess with its pattern, the number of
+    /// candidates before it, and its rank among the suggestions.
+    #[cfg(feature = "serde")]
+    #[clap(long, global = true, value_name = "FILE")]
+    log: Option<PathBuf>,
+    /// Store the results of `batch` runs and `play` games in this SQLite database, with the
+    /// strategy, the hashes of the word lists and the time taken. See `report`.
+    #[cfg(feature = "sqlite")]
+    #[clap(long, global = true, value_name = "FILE")]
+    db: Option<PathBuf>,
 }
 
+/// The dictionary that word lists written `dict:` refer to, chosen with `--dict`.
+static DICTIONARY: OnceLock<&'static Dictionary> = OnceLock::new();
+
+/// Whether word lists are read without converting them to lowercase, chosen with `--keep-case`.
+static KEEP_CASE: OnceLock<bool> = OnceLock::new();
+
+/// The file game transcripts are appended to, chosen with `--log`.
+#[cfg(feature = "serde")]
+static LOG: OnceLock<PathBuf> = OnceLock::new();
+
+/// The database results are stored in, chosen with `--db`.
+#[cfg(feature = "sqlite")]
+static DB: OnceLock<PathBuf> = OnceLock::new();
+
 #[derive(Subcommand)]
 enum SubCommand {
     /// Help with a game you are playing. The program will ask you to enter your guesses
     /// and the result you got, and from that will figure out candidate words to guess.
     Assist {
-        /// The list of all allowed five-letter words
-        #[clap(value_parser)]
-        word_file: Input
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// Favour latency over throughput: score the most promising guesses first and show
+        /// a provisional ranking while the rest are scored, instead of precomputing all
+        /// feedback buckets before the first round.
+        #[clap(long)]
+        low_latency: bool,
+        /// The number of words scored in parallel between two updates of the provisional
+        /// ranking, with `--low-latency`.
+        #[clap(long, default_value_t = 1024, requires = "low_latency")]
+        chunk_size: usize,
+        /// Compute at most this many scores per round, for slow devices: entropies are
+        /// estimated from a random sample of the remaining solutions and printed with their
+        /// 95% confidence interval.
+        #[clap(long, conflicts_with = "low_latency")]
+        score_budget: Option<usize>,
+        /// Compute the suggestions of every round for about this long at most, e.g. `2s` or
+        /// `500ms`. If ranking all words would take longer, only the candidates, or the words
+        /// covering the most common letters, are ranked.
+        #[clap(long, value_parser = parse_duration, conflicts_with_all = ["low_latency", "score_budget"])]
+        budget: Option<Duration>,
+        /// Keep the feedback of every word for every word and the first-round suggestions in
+        /// this directory, so that later runs with the same word list start instantly.
+        #[clap(long, conflicts_with_all = ["low_latency", "score_budget", "budget"])]
+        cache_dir: Option<PathBuf>,
+        /// Suggest the guesses of a decision tree written by `build-tree` instantly, as long as
+        /// the game follows it.
+        #[clap(long, value_parser)]
+        tree: Option<Input>,
+        /// How to print the suggestions of every round.
+        #[clap(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Show how the top suggestion splits the solution space every round: the number of
+        /// solutions left for each pattern it can get. Enter `explain WORD` at the guess
+        /// prompt to see the same for any word.
+        #[clap(long)]
+        explain: bool,
+        /// Continue a game saved by entering `:save FILE` at the guess prompt.
+        #[clap(long)]
+        resume: Option<PathBuf>,
+        /// What to do with a guess that is not in the word list.
+        #[clap(long, value_enum, default_value_t = GuessCheckArg::Warn)]
+        unknown_guess: GuessCheckArg,
+        /// Favour common words more and more from round to round, as the editors of the game
+        /// pick familiar words: the frequencies of the word list are raised to the power
+        /// `START + STEP * guesses so far`, but at most `MAX`. Without a value, `1,0.5,3`.
+        /// See `backtest` for how much it helps.
+        #[clap(long, value_name = "START,STEP,MAX", num_args = 0..=1,
+               default_missing_value = "1,0.5,3")]
+        sharpen: Option<Sharpening>,
     },
     /// Runs a batch of games to gather data about the algorithm’s performance.
     Batch {
-        /// The list of all allowed five-letter words
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of words to use as solutions for the games. By default, the answers of
+        /// the dictionary if the word list is one, e.g. `dict:`, and otherwise every allowed word.
         #[clap(value_parser)]
-        word_file: Input,
-        /// The list of words to use as solutions for the games.
+        solution_file: Option<WordSource>,
+        /// Play a single game against an adversarial host (as in Absurdle) that may pick
+        /// any word from the solution list, and report how many guesses it takes.
+        #[clap(long)]
+        absurdle: bool,
+        /// Play Antiwordle with every solution instead: avoid the solution for as long as
+        /// possible with guesses that fit all feedback so far, and report how long each
+        /// game lasts.
+        #[clap(long, conflicts_with = "absurdle")]
+        antiwordle: bool,
+        /// Write one record per game (solution, guesses, rounds, solved flag, and the
+        /// solution space size before every guess) to this file.
+        #[clap(long, value_parser, conflicts_with_all = ["absurdle", "antiwordle"])]
+        output: Option<Output>,
+        /// The format of the records. Defaults to JSON for `--output` files ending in
+        /// `.json`, and to CSV otherwise. Without `--output`, the records are written to
+        /// standard output instead of the usual report.
+        #[clap(long, value_enum, conflicts_with_all = ["absurdle", "antiwordle"])]
+        format: Option<RecordFormatArg>,
+        /// The first guess of every game. With `--antiwordle`, the word that leaves the
+        /// most words on average is always used.
+        #[clap(long, default_value = "tears", conflicts_with = "antiwordle")]
+        first_guess: String,
+        /// Keep the feedback of every word for every word in this directory, so that later
+        /// runs with the same word list skip computing it.
+        #[clap(long)]
+        cache_dir: Option<PathBuf>,
+        /// Make the guesses of a decision tree written by `build-tree`, including the first
+        /// one, and only compute guesses for states the tree does not cover.
+        #[clap(long, value_parser, conflicts_with_all = ["absurdle", "antiwordle"])]
+        tree: Option<Input>,
+        /// How to pick the guesses after the first one, e.g. `noisy` to simulate human play.
+        #[clap(long, default_value = "entropy", conflicts_with_all = ["absurdle", "antiwordle"])]
+        strategy: Strategy,
+        /// How often the `noisy` strategy makes a plausible instead of a good guess, between
+        /// 0 and 1.
+        #[clap(long, value_parser = parse_probability, default_value_t = Strategy::ERROR_RATE)]
+        error_rate: f64,
+        /// The seed of the `random` and `noisy` strategies. Every game draws from its own
+        /// generator, seeded with this seed and the number of the game. By default, a random
+        /// seed, which the manifest records.
+        #[clap(long)]
+        seed: Option<u64>,
+    },
+    /// Compares first guesses: simulates every solution with each of them and reports the
+    /// average and worst number of guesses and the number of failures per opener.
+    Openers {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of words to use as solutions for the games. By default, the answers of
+        /// the dictionary if the word list is one, e.g. `dict:`, and otherwise every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// The openers to compare, e.g. `tears,crane,slate`. By default, the guesses with
+        /// the highest entropy over the solutions are compared.
+        #[clap(long, value_delimiter = ',')]
+        openers: Vec<String>,
+        /// The number of openers to compare if none are given.
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Compares strategies: simulates every solution with each of them and prints a table of
+    /// the average and worst number of guesses, the failures and the runtime per strategy.
+    Compare {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of words to use as solutions for the games. By default, the answers of
+        /// the dictionary if the word list is one, e.g. `dict:`, and otherwise every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// The strategies to compare, e.g. `entropy,minimax`. By default, all of them but
+        /// `beam`, which takes much longer.
+        #[clap(long, value_delimiter = ',', default_value = "entropy,minimax,frequency,montecarlo,composite,partitions,positional,random,noisy")]
+        strategies: Vec<Strategy>,
+        /// The first guess of every game.
+        #[clap(long, default_value = "tears")]
+        first_guess: String,
+        /// The number of games the `montecarlo` strategy plays to rate a guess. More rate
+        /// guesses better, but take longer.
+        #[clap(long, default_value_t = Strategy::ROLLOUTS)]
+        rollouts: usize,
+        /// The number of guesses the `beam` strategy follows after every feedback.
+        #[clap(long, default_value_t = NonZeroUsize::new(Strategy::BEAM_WIDTH).unwrap())]
+        beam_width: NonZeroUsize,
+        /// The number of guesses the `beam` strategy looks ahead.
+        #[clap(long, default_value_t = NonZeroUsize::new(Strategy::BEAM_DEPTH).unwrap())]
+        depth: NonZeroUsize,
+        /// How much the `composite` strategy values winning right away: the bits of entropy
+        /// a guess that is certainly the solution is worth.
+        #[clap(long, value_parser = parse_weight, default_value_t = Strategy::WIN_WEIGHT)]
+        win_weight: f64,
+        /// How often the `noisy` strategy makes a plausible instead of a good guess, between
+        /// 0 and 1.
+        #[clap(long, value_parser = parse_probability, default_value_t = Strategy::ERROR_RATE)]
+        error_rate: f64,
+        /// The seed of the `random` and `noisy` strategies, see `batch --seed`.
+        #[clap(long)]
+        seed: Option<u64>,
+    },
+    /// Replays past answers in the order they were published, e.g. the daily answers of
+    /// Wordle, and compares how many guesses it takes with all solutions equally likely, with
+    /// the solutions weighed by their frequencies, and with the frequencies sharpened as with
+    /// `assist --sharpen`. Every game leaves the answers before it out of the solutions.
+    Backtest {
+        /// The past answers, one per line, oldest first.
+        #[clap(value_parser)]
+        answer_file: WordSource,
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions, with the frequency of each word after a tab. By
+        /// default, every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// The first guess of every game.
+        #[clap(long, default_value = "tears")]
+        first_guess: String,
+        /// The sharpening schedule compared, see `assist --sharpen`.
+        #[clap(long, value_name = "START,STEP,MAX", default_value_t = Sharpening::DEFAULT)]
+        sharpen: Sharpening,
+        /// The number of consecutive answers averaged together in the table by period, e.g.
+        /// 365 for a year of daily puzzles.
+        #[clap(long, default_value_t = NonZeroUsize::new(365).unwrap())]
+        period: NonZeroUsize,
+    },
+    /// Times the hot paths of the solver: building the score matrix, the entropy of one guess,
+    /// ranking all guesses and one simulated game, to measure performance work.
+    Bench {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, the answers of the dictionary if the
+        /// word list is one, e.g. `dict:`, and otherwise every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// How often each benchmark is timed.
+        #[clap(long, default_value_t = NonZeroUsize::new(5).unwrap())]
+        runs: NonZeroUsize,
+    },
+    /// Keeps the solver running for other programs, e.g. bots or GUIs, which send it guesses
+    /// and ask for suggestions without it reading the word list again for every query.
+    #[command(group(ArgGroup::new("transport").required(true)))]
+    Serve {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// Speak a line protocol on standard input and output: `GUESS crane bbygb`,
+        /// `SUGGEST 5`, `RESET` and `QUIT`, each answered with one line starting with `OK`
+        /// or `ERR`.
+        #[clap(long, group = "transport")]
+        stdio: bool,
+        /// Serve a REST API on this address, e.g. `127.0.0.1:8080`: `POST /session`,
+        /// `POST /session/{id}/feedback` and `GET /session/{id}/suggestions`, see the README.
+        #[cfg(feature = "serde")]
+        #[clap(long, group = "transport")]
+        http: Option<String>,
+    },
+    /// Runs a Discord bot that helps every channel with its game: `!wordle crane bbygb`
+    /// enters a guess and `!wordle` shows suggestions. The bot token is read from the
+    /// environment variable `DISCORD_TOKEN`.
+    #[cfg(feature = "discord")]
+    DiscordBot {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, the answers of the dictionary if the
+        /// word list is one, e.g. `dict:`, and otherwise every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+    },
+    /// Runs a Telegram bot that helps every chat with its game: `crane bbygb` enters a guess,
+    /// `crane` shows a keyboard to tap in its colors and `/stats` shows the games of the day.
+    /// The bot token is read from the environment variable `TELEGRAM_BOT_TOKEN`.
+    #[cfg(feature = "telegram")]
+    TelegramBot {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, the answers of the dictionary if the
+        /// word list is one, e.g. `dict:`, and otherwise every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+    },
+    /// Compares your own Wordle history with what the solver would have scored on the same
+    /// answers, and reports how many bits of information you left on the table.
+    ImportHistory {
+        /// The list of all allowed five-letter words, e.g. `dict:` for the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser)]
+        word_file: WordSource,
+        /// A CSV file of past games with the columns `date`, `answer` and `guesses`, where a
+        /// lost game has `X` guesses.
+        #[clap(value_parser)]
+        history_file: Input,
+        /// The first guess of the solver.
+        #[clap(long, default_value = "tears")]
+        first_guess: String,
+    },
+    /// Grades a finished game against the solver: the entropy rank of every guess, the
+    /// information it revealed compared to the best guess, and an overall skill score.
+    Grade {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// The answer of the game.
+        #[clap(long)]
+        answer: String,
+        /// The guesses of the game, e.g. `crane,slimy,gears`.
+        #[clap(long, value_delimiter = ',', required = true)]
+        guesses: Vec<String>,
+    },
+    /// Lists the runs stored with `--db`, latest first: the games played, the share solved,
+    /// the average number of guesses and the time taken.
+    #[cfg(feature = "sqlite")]
+    Report {
+        /// The database written with `--db`.
+        database: PathBuf,
+        /// Only list the runs of this subcommand.
+        #[clap(long, value_enum)]
+        mode: Option<ModeArg>,
+        /// The number of runs listed.
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+        /// List the solutions the games of the run with this id failed on instead.
+        #[clap(long, value_name = "ID")]
+        failures: Option<i64>,
+    },
+    /// Shows the games of a transcript file written with `--log` round by round, e.g. to
+    /// review past games or to see why a batch game failed.
+    #[cfg(feature = "serde")]
+    Replay {
+        /// The transcript file.
+        #[clap(value_parser)]
+        log_file: Input,
+        /// The list of all allowed five-letter words, for `--annotate`. By default, the
+        /// dictionary chosen with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions, for `--annotate`. By default, every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// Grade every guess against the solver, as `grade` does: its entropy rank, the best
+        /// guess of the round and the information it revealed.
+        #[clap(long)]
+        annotate: bool,
+        /// Only show the game with this number, counting from 1.
+        #[clap(long)]
+        game: Option<NonZeroUsize>,
+        /// Only show the games that were not solved.
+        #[clap(long)]
+        unsolved: bool,
+    },
+    /// Reconstructs a game from a share grid posted without words: which answers fit it,
+    /// and given the answer, which guesses fit every row.
+    ImportShare {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// The file with the pasted grid, by default standard input.
+        #[clap(long, value_parser, default_value = "-")]
+        grid: Input,
+        /// The answer of the game, if known.
+        #[clap(long)]
+        answer: Option<String>,
+    },
+    /// Help with a multi-board game (Dordle, Quordle, Octordle, Sedecordle), where every
+    /// guess is scored against several boards at once.
+    Multi {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The number of boards played simultaneously.
+        #[clap(short, long, default_value = "4",
+               value_parser = PossibleValuesParser::new(["2", "4", "8", "16"])
+                   .map(|s| s.parse::<usize>().unwrap()))]
+        boards: usize,
+    },
+    /// Groups the remaining candidates of a game, to show the structure of what is left.
+    Clusters {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The guesses so far with their results, e.g. `crane:bgybb,slimy:bbgyb`.
+        #[clap(long, default_value = "")]
+        history: String,
+        /// Group candidates by the pattern they would produce for this word. Without a probe,
+        /// candidates are grouped into families that differ in a single letter.
+        #[clap(long)]
+        probe: Option<String>,
+    },
+    /// Generates a printable lookup table of recommended guesses for every feedback the
+    /// first guess can produce, for playing away from a computer.
+    Cheatsheet {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The opening word the sheet is built for.
+        #[clap(long, default_value = "tears")]
+        first_guess: String,
+        /// The number of guesses covered by the sheet, including the first one.
+        #[clap(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(2..=4))]
+        depth: u8,
+        /// Where to write the sheet.
+        #[clap(long, value_parser, default_value = "-")]
+        out: Output,
+    },
+    /// Computes the complete decision tree of a strategy for a word list, i.e. the guess to
+    /// make for every feedback that can occur, and writes it to a file.
+    BuildTree {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, every allowed word.
         #[clap(value_parser)]
-        solution_file: Input,
+        solution_file: Option<WordSource>,
+        /// How to pick the guess of every node.
+        #[clap(long, default_value = "entropy")]
+        strategy: Strategy,
+        /// The number of games the `montecarlo` strategy plays to rate a guess.
+        #[clap(long, default_value_t = Strategy::ROLLOUTS)]
+        rollouts: usize,
+        /// The number of guesses the `beam` strategy follows after every feedback.
+        #[clap(long, default_value_t = NonZeroUsize::new(Strategy::BEAM_WIDTH).unwrap())]
+        beam_width: NonZeroUsize,
+        /// The number of guesses the `beam` strategy looks ahead.
+        #[clap(long, default_value_t = NonZeroUsize::new(Strategy::BEAM_DEPTH).unwrap())]
+        depth: NonZeroUsize,
+        /// How much the `composite` strategy values winning right away: the bits of entropy
+        /// a guess that is certainly the solution is worth.
+        #[clap(long, value_parser = parse_weight, default_value_t = Strategy::WIN_WEIGHT)]
+        win_weight: f64,
+        /// How often the `noisy` strategy makes a plausible instead of a good guess, between
+        /// 0 and 1.
+        #[clap(long, value_parser = parse_probability, default_value_t = Strategy::ERROR_RATE)]
+        error_rate: f64,
+        /// The guess of the root. By default, the strategy picks it.
+        #[clap(long)]
+        first_guess: Option<String>,
+        /// Only make guesses that are consistent with all feedback so far, and report whether
+        /// a tree solving every solution within six guesses was found.
+        #[clap(long)]
+        hard_mode: bool,
+        /// Where to write the tree.
+        #[clap(long, value_parser)]
+        out: Output,
+        /// The file format. By default, JSON for files ending in `.json` and the compact
+        /// binary format otherwise.
+        #[clap(long, value_enum)]
+        format: Option<TreeFormatArg>,
+    },
+    /// Exports a decision tree written by `build-tree` as a graph, with nodes labeled by guess
+    /// and edges by feedback, for visualization with Graphviz or Mermaid.
+    ExportTree {
+        /// The decision tree.
+        #[clap(value_parser)]
+        tree_file: Input,
+        /// The graph description language.
+        #[clap(long, value_enum, default_value_t = GraphFormatArg::Dot)]
+        format: GraphFormatArg,
+        /// The number of guesses to include. By default, the whole tree.
+        #[clap(long)]
+        depth: Option<usize>,
+        /// Where to write the graph.
+        #[clap(long, value_parser, default_value = "-")]
+        out: Output,
+    },
+    /// Checks a decision tree written by `build-tree` against a word list: every solution is
+    /// reached within the round limit and every guess is an allowed word. Prints a report with
+    /// counterexamples and fails if any check fails.
+    VerifyTree {
+        /// The decision tree.
+        #[clap(value_parser)]
+        tree_file: Input,
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The list of possible solutions. By default, every allowed word.
+        #[clap(value_parser)]
+        solution_file: Option<WordSource>,
+        /// Also check that every guess is consistent with the feedback before it. Always
+        /// checked for trees built with --hard-mode.
+        #[clap(long)]
+        hard_mode: bool,
+    },
+    /// Prints the single best next guess for a game given as a compact history, and nothing
+    /// else. Meant for quick use over SSH, e.g. `next wordle.txt crane:bgybb,slimy:bbgyb`.
+    Next {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The guesses so far with their results, e.g. `crane:bgybb,slimy:bbgyb`.
+        #[clap(default_value = "")]
+        history: String,
+    },
+    /// Help with a Phrazle-style game, where the solution is a phrase of several words
+    /// whose lengths are known in advance.
+    Phrase {
+        /// The list of candidate phrases, one per line
+        #[clap(value_parser)]
+        phrase_file: Input,
+        /// The lengths of the words of the puzzle, e.g. `1,5,2,4` for "a piece of cake".
+        #[clap(long, value_delimiter = ',', required = true)]
+        shape: Vec<usize>,
+    },
+    /// Writes a copy of this program with word lists built in, to hand to people as a single
+    /// file. The copy accepts `bundled:NAME` (or just `bundled:` for the first list) wherever
+    /// a word list is expected.
+    Bundle {
+        /// The word lists to include. They are selected by their file name.
+        #[clap(value_parser, required = true)]
+        word_files: Vec<WordSource>,
+        /// Where to write the program.
+        #[clap(long)]
+        out: PathBuf,
     },
     /// Play a normal game of wordle against this program.
     Play {
-        /// The list of all allowed five-letter words
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// Play Absurdle instead: the program does not pick a solution, but answers
+        /// every guess so that as many words as possible remain.
+        #[clap(long)]
+        absurdle: bool,
+        /// Copy the share grid to the clipboard at the end of the game. Needs the
+        /// `clipboard` feature.
+        #[clap(long, conflicts_with = "absurdle")]
+        copy: bool,
+        /// Pick the solution with this seed instead of at random, so that everyone playing
+        /// with the same seed and word list gets the same solution.
+        #[clap(long, conflicts_with = "absurdle")]
+        seed: Option<u64>,
+        /// Keep the result in this file instead of the default stats file, see `stats`.
+        #[clap(long, conflicts_with = "absurdle")]
+        stats_file: Option<PathBuf>,
+        /// Do not keep the result in the stats file.
+        #[clap(long, conflicts_with = "stats_file")]
+        no_stats: bool,
+        /// What to do with a guess that is not in the word list.
+        #[clap(long, value_enum, default_value_t = GuessCheckArg::Reject)]
+        unknown_guess: GuessCheckArg,
+        /// Keep playing until the word is found, however many guesses that takes. Practice
+        /// games are not kept in the stats file.
+        #[clap(long, conflicts_with_all = ["absurdle", "copy", "stats_file"])]
+        practice: bool,
+        /// Play Antiwordle instead: avoid the word for as long as possible, while every
+        /// guess must fit the feedback so far, as in hard mode. Antiwordle games are not
+        /// kept in the stats file.
+        #[clap(long, conflicts_with_all = ["absurdle", "copy", "stats_file", "practice"])]
+        antiwordle: bool,
+        /// Time every guess and the whole game, for speed play. The time of the game is kept
+        /// in the stats file.
+        #[clap(long, conflicts_with_all = ["absurdle", "antiwordle"])]
+        timed: bool,
+        /// Lose the game if a guess takes longer than this many seconds. The limit is checked
+        /// once the guess is entered, which is then discarded.
+        #[clap(long, value_name = "SECONDS", requires = "timed")]
+        time_limit: Option<u64>,
+    },
+    /// Help with today's New York Times Wordle: shows the puzzle number and starts an
+    /// `assist` session.
+    Daily {
+        /// The list of all allowed five-letter words. By default, the dictionary chosen
+        /// with `--dict`.
+        #[clap(value_parser, default_value = "dict:", hide_default_value = true)]
+        word_file: WordSource,
+        /// The day of the puzzle, as YYYY-MM-DD. By default today in UTC, which can be a day
+        /// off around midnight.
+        #[clap(long, value_parser = parse_puzzle_date)]
+        date: Option<Date>,
+        /// Download the answer of the puzzle and check the result against it at the end of
+        /// the game. Needs the `fetch` and `serde` features.
+        #[clap(long)]
+        spoiler: bool,
+        /// Where to download the puzzle from with `--spoiler`, with `{date}` standing for the
+        /// date. By default, the puzzles of the New York Times.
+        #[clap(long, default_value = METADATA_URL, requires = "spoiler")]
+        metadata_url: String,
+    },
+    /// Checks and cleans up word lists.
+    Wordlist {
+        #[command(subcommand)]
+        command: WordlistCommand,
+    },
+    /// Shows the statistics of the games of `play`: games played, share won, streaks and
+    /// the guess distribution.
+    Stats {
+        /// The stats file. By default `$XDG_DATA_HOME/wordl-rust-bot/stats.txt`.
+        #[clap(long)]
+        stats_file: Option<PathBuf>,
+    },
+}
+
+/// The subcommands of `wordlist`.
+#[derive(Subcommand)]
+enum WordlistCommand {
+    /// Checks a word list for entries that are not words, entries with uppercase letters and
+    /// duplicates, and prints a report. Fails if there are any problems, unless `--out` is given.
+    Validate {
+        /// The word list to check.
+        #[clap(value_parser)]
+        word_file: Input,
+        /// Write the cleaned list here: the words converted to lowercase, without duplicates
+        /// and without the entries that are not words.
+        #[clap(long)]
+        out: Option<Output>,
+    },
+    /// Merges word lists into one, keeping every word once and sorting them alphabetically.
+    Merge {
+        /// The word lists to merge.
+        #[clap(value_parser, required = true)]
+        word_files: Vec<WordSource>,
+        /// Leave out the words of these lists, e.g. past answers. May be given more than once.
+        #[clap(long, value_parser)]
+        subtract: Vec<WordSource>,
+        /// Keep the words in the order of the lists instead of sorting them.
+        #[clap(long)]
+        keep_order: bool,
+        /// Where to write the merged list.
+        #[clap(long, value_parser, default_value = "-")]
+        out: Output,
+    },
+    /// Extracts the words of one length from a dictionary file with one entry per line, e.g.
+    /// `/usr/share/dict/words`, converted to lowercase and without duplicates or entries with
+    /// anything but letters.
+    Filter {
+        /// The dictionary file.
         #[clap(value_parser)]
         word_file: Input,
+        /// The number of letters of the words to extract.
+        #[clap(long, default_value = "5")]
+        length: NonZeroUsize,
+        /// Leave out entries that start with an uppercase letter, which are mostly names.
+        #[clap(long)]
+        skip_capitalized: bool,
+        /// Where to write the words.
+        #[clap(long, value_parser, default_value = "-")]
+        out: Output,
     },
 }
 
-fn main() {
-    let cli = Cli::parse();
-    match cli.command {
-        SubCommand::Assist {word_file} => {
-            run_game(word_file)
+/// The output format of subcommands that can be wrapped by other programs.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Human-readable, colored text.
+    Text,
+    /// One JSON object per line.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// The subcommands whose runs are stored with `--db`, see [`Mode`].
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Copy, ValueEnum)]
+enum ModeArg {
+    Batch,
+    Play,
+}
+
+#[cfg(feature = "sqlite")]
+impl From<ModeArg> for Mode {
+    fn from(mode: ModeArg) -> Mode {
+        match mode {
+            ModeArg::Batch => Mode::Batch,
+            ModeArg::Play => Mode::Play,
+        }
+    }
+}
+
+/// What to do with a guess that is not in the word list, see [`GuessCheck`].
+#[derive(Clone, Copy, ValueEnum)]
+enum GuessCheckArg {
+    /// Take it without comment.
+    Accept,
+    /// Take it, but point out that the real game would refuse it.
+    Warn,
+    /// Ask for another guess.
+    Reject,
+}
+
+impl From<GuessCheckArg> for GuessCheck {
+    fn from(check: GuessCheckArg) -> GuessCheck {
+        match check {
+            GuessCheckArg::Accept => GuessCheck::Accept,
+            GuessCheckArg::Warn => GuessCheck::Warn,
+            GuessCheckArg::Reject => GuessCheck::Reject,
+        }
+    }
+}
+
+/// When to color the output, see [`ColorChoice`].
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorArg {
+    /// Only on a terminal, unless `NO_COLOR` is set.
+    Auto,
+    /// Always, e.g. when piping into `less -R`.
+    Always,
+    /// Never.
+    Never,
+}
+
+impl From<ColorArg> for ColorChoice {
+    fn from(color: ColorArg) -> ColorChoice {
+        match color {
+            ColorArg::Auto => ColorChoice::Auto,
+            ColorArg::Always => ColorChoice::Always,
+            ColorArg::Never => ColorChoice::Never,
         }
-        SubCommand::Batch {word_file, solution_file} => {
-            full_runs(word_file, solution_file);
+    }
+}
+
+/// The format of batch records, see [`RecordFormat`].
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RecordFormatArg {
+    /// Comma-separated values with a header line.
+    Csv,
+    /// One JSON object per line.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// The file formats of decision trees, see [`TreeFormat`].
+#[derive(Clone, Copy, ValueEnum)]
+enum TreeFormatArg {
+    /// A compact binary format.
+    Binary,
+    /// Nested JSON objects.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+impl From<TreeFormatArg> for TreeFormat {
+    fn from(format: TreeFormatArg) -> TreeFormat {
+        match format {
+            TreeFormatArg::Binary => TreeFormat::Binary,
+            #[cfg(feature = "serde")]
+            TreeFormatArg::Json => TreeFormat::Json,
         }
-        SubCommand::Play {word_file} => {
-            play_game(word_file);
+    }
+}
+
+/// The graph formats decision trees can be exported to, see [`GraphFormat`].
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormatArg {
+    /// Graphviz, e.g. `dot -Tsvg tree.dot > tree.svg`.
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+}
+
+impl From<GraphFormatArg> for GraphFormat {
+    fn from(format: GraphFormatArg) -> GraphFormat {
+        match format {
+            GraphFormatArg::Dot => GraphFormat::Dot,
+            GraphFormatArg::Mermaid => GraphFormat::Mermaid,
         }
     }
 }
 
-fn read_file<R: Read>(name: R) -> Vec<Word> {
-    let p = BufReader::new(name).lines().map(|line| {
-        Word::from_str(&line.unwrap())
-    }).collect();
-    p
+impl From<RecordFormatArg> for RecordFormat {
+    fn from(format: RecordFormatArg) -> RecordFormat {
+        match format {
+            RecordFormatArg::Csv => RecordFormat::Csv,
+            #[cfg(feature = "serde")]
+            RecordFormatArg::Json => RecordFormat::Json,
+        }
+    }
 }
 
-fn run_game<R: Read>(word_file: R) {
-    let words = read_file(word_file);
-    let mut game = HelpGame::new(&words);
-    game.run_game();
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // Help and version are printed to standard output and exit successfully.
+        Err(error) if !error.use_stderr() => error.exit(),
+        Err(error) => {
+            let _ = error.print();
+            return ExitStatus::Usage.into();
+        }
+    };
+    KEEP_CASE.get_or_init(|| cli.keep_case);
+    ColorChoice::from(cli.color).apply();
+    if cli.high_contrast {
+        Palette::HighContrast.set_current();
+    }
+    match (cli.quiet, cli.verbose) {
+        (true, _) => Verbosity::Quiet.set_current(),
+        (_, true) => Verbosity::Verbose.set_current(),
+        _ => {}
+    }
+    if let Some(locale) = &cli.locale {
+        Locale::from_tag(locale).set_current();
+    }
+    if cli.fold_accents {
+        AccentFolding::new(cli.keep_letters.as_deref().unwrap_or("")).set_current();
+    }
+    DICTIONARY.get_or_init(|| Dictionary::find(&cli.dict).expect("--dict only accepts known dictionaries"));
+    #[cfg(feature = "serde")]
+    if let Some(path) = cli.log {
+        LOG.get_or_init(|| path);
+    }
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = cli.db {
+        DB.get_or_init(|| path);
+    }
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads.get()).build_global()
+            .expect("the thread pool is configured before it is used");
+    }
+    let error_format = match &cli.command {
+        #[cfg(feature = "serde")]
+        SubCommand::Assist {format: Format::Json, ..} => Format::Json,
+        _ => cli.error_format,
+    };
+    match run(cli.command) {
+        Ok(status) => status.into(),
+        Err(error) => report(error, error_format),
+    }
 }
 
+/// Prints `error`, as text on standard error or as JSON on standard output, and returns the
+/// exit status for it.
+fn report(error: WordleError, format: Format) -> ExitCode {
+    let status = ExitStatus::from(&error);
+    match format {
+        Format::Text => eprintln!("Error: {:?}", miette::Report::new(error)),
+        #[cfg(feature = "serde")]
+        Format::Json => println!("{}", error_json(&error)),
+    }
+    status.into()
+}
 
-fn full_runs<R: Read>(words_file: R, solutions_file: R) {
-    let words = read_file(words_file);
-    let solutions = read_file(solutions_file);
-    let first_guess = Word::from_str("tears");
-    for s in solutions {
-        let mut game = SimulatedGame::new(&words, s, first_guess);
-        game.run_game();
+/// Runs `command`. Commands that play or follow a game report how it ended in the returned
+/// status, all others succeed unless they fail with an error.
+fn run(command: SubCommand) -> Result<ExitStatus, WordleError> {
+    match command {
+        SubCommand::Assist {word_file, low_latency, chunk_size, score_budget, budget, cache_dir, tree, format, explain,
+                            resume, unknown_guess, sharpen} => {
+            let tree = tree.map(read_tree).transpose()?;
+            return run_game(word_file, low_latency.then_some(chunk_size), score_budget, budget, cache_dir, tree, format,
+                     explain, resume.as_deref(), unknown_guess.into(), sharpen)
+        }
+        SubCommand::Batch {word_file, solution_file, absurdle, antiwordle, output, format, first_guess, cache_dir, tree,
+                           strategy, error_rate, seed} => {
+            let first_guess = parse_word("first-guess", &first_guess)?;
+            if absurdle {
+                absurdle_run(word_file, solution_file, first_guess)
+            } else if antiwordle {
+                antiwordle_runs(word_file, solution_file, cache_dir)
+            } else {
+                let tree = match tree {
+                    Some(file) => Some((file.path().to_string(), read_tree(file)?)),
+                    None => None,
+                };
+                let strategy = strategy.with_error_rate(error_rate);
+                let seed = seed.unwrap_or_else(rand::random);
+                full_runs(word_file, solution_file, output, format.map(RecordFormat::from), first_guess, strategy, seed,
+                          cache_dir, tree)
+            }
+        }
+        SubCommand::Openers {word_file, solution_file, openers, top} => {
+            let openers = openers.iter()
+                .map(|opener| parse_word("openers", opener))
+                .collect::<Result<Vec<Word>, WordleError>>()?;
+            opener_tournament(word_file, solution_file, &openers, top)
+        }
+        SubCommand::Compare {word_file, solution_file, strategies, first_guess, rollouts, beam_width, depth,
+                             win_weight, error_rate, seed} => {
+            let first_guess = parse_word("first-guess", &first_guess)?;
+            let strategies = strategies.into_iter()
+                .map(|s| s.with_rollouts(rollouts).with_beam(beam_width.get(), depth.get()).with_win_weight(win_weight)
+                    .with_error_rate(error_rate))
+                .collect::<Vec<Strategy>>();
+            compare_strategies(word_file, solution_file, &strategies, first_guess, seed.unwrap_or_else(rand::random))
+        }
+        SubCommand::Backtest {answer_file, word_file, solution_file, first_guess, sharpen, period} => {
+            let first_guess = parse_word("first-guess", &first_guess)?;
+            backtest_priors(answer_file, word_file, solution_file, first_guess, sharpen, period.get())
+        }
+        SubCommand::Bench {word_file, solution_file, runs} => benchmark(word_file, solution_file, runs.get()),
+        SubCommand::ImportHistory {word_file, history_file, first_guess} => {
+            let first_guess = parse_word("first-guess", &first_guess)?;
+            import_history(word_file, history_file, first_guess)
+        }
+        SubCommand::Grade {word_file, solution_file, answer, guesses} => {
+            let answer = parse_word("answer", &answer)?;
+            let guesses = guesses.iter()
+                .map(|guess| parse_word("guesses", guess))
+                .collect::<Result<Vec<Word>, WordleError>>()?;
+            grade(word_file, solution_file, answer, &guesses)
+        }
+        #[cfg(feature = "sqlite")]
+        SubCommand::Report {database, mode, limit, failures} => {
+            show_report(&database, mode.map(Mode::from), limit, failures)
+        }
+        #[cfg(feature = "serde")]
+        SubCommand::Replay {log_file, word_file, solution_file, annotate, game, unsolved} => {
+            let word_files = annotate.then_some((word_file, solution_file));
+            replay(log_file, word_files, game, unsolved)
+        }
+        SubCommand::ImportShare {word_file, solution_file, grid, answer} => {
+            let answer = answer.map(|answer| parse_word("answer", &answer)).transpose()?;
+            import_share(word_file, solution_file, grid, answer)
+        }
+        SubCommand::Multi {word_file, boards} => {
+            return multi_game(word_file, boards);
+        }
+        SubCommand::Clusters {word_file, history, probe} => {
+            clusters(word_file, &history, probe.as_deref())
+        }
+        SubCommand::Cheatsheet {word_file, first_guess, depth, out} => {
+            cheatsheet(word_file, &first_guess, depth, out)
+        }
+        SubCommand::BuildTree {word_file, solution_file, strategy, rollouts, beam_width, depth, win_weight,
+                               error_rate, first_guess, hard_mode, out, format} => {
+            let first_guess = first_guess.map(|guess| parse_word("first-guess", &guess)).transpose()?;
+            let strategy = strategy.with_rollouts(rollouts).with_beam(beam_width.get(), depth.get())
+                .with_win_weight(win_weight).with_error_rate(error_rate);
+            build_tree(word_file, solution_file, strategy, first_guess, hard_mode, out, format.map(TreeFormat::from))
+        }
+        SubCommand::ExportTree {tree_file, format, depth, out} => {
+            read_tree(tree_file)?.write_graph(out, format.into(), depth)?;
+            Ok(())
+        }
+        #[cfg(feature = "serde")]
+        SubCommand::Serve {word_file, solution_file, stdio: _, http} => {
+            serve(word_file, solution_file, http)
+        }
+        #[cfg(not(feature = "serde"))]
+        SubCommand::Serve {word_file, solution_file, stdio: _} => {
+            serve(word_file, solution_file, None)
+        }
+        #[cfg(feature = "discord")]
+        SubCommand::DiscordBot {word_file, solution_file} => {
+            discord_bot(word_file, solution_file)
+        }
+        #[cfg(feature = "telegram")]
+        SubCommand::TelegramBot {word_file, solution_file} => {
+            telegram_bot(word_file, solution_file)
+        }
+        SubCommand::VerifyTree {tree_file, word_file, solution_file, hard_mode} => {
+            verify_tree(tree_file, word_file, solution_file, hard_mode)
+        }
+        SubCommand::Next {word_file, history} => {
+            next_guess(word_file, &history)
+        }
+        SubCommand::Phrase {phrase_file, shape} => {
+            return phrase_game(phrase_file, &shape);
+        }
+        SubCommand::Bundle {word_files, out} => {
+            bundle(word_files, &out)
+        }
+        SubCommand::Play {word_file, absurdle, copy, seed, stats_file, no_stats, unknown_guess, practice, antiwordle,
+                          timed, time_limit} => {
+            let stats_file = match no_stats || practice || antiwordle {
+                true => None,
+                false => stats_file.or_else(Stats::default_path),
+            };
+            let timer = timed.then_some(time_limit.map(Duration::from_secs));
+            return play_game(word_file, absurdle, antiwordle, copy, seed, stats_file.as_deref(), unknown_guess.into(),
+                             practice, timer);
+        }
+        SubCommand::Daily {word_file, date, spoiler, metadata_url} => {
+            daily(word_file, date.unwrap_or_else(Date::today), spoiler, &metadata_url)
+        }
+        SubCommand::Stats {stats_file} => {
+            show_stats(stats_file.or_else(Stats::default_path).as_deref())
+        }
+        SubCommand::Wordlist {command: WordlistCommand::Validate {word_file, out}} => {
+            validate_word_list(word_file, out)
+        }
+        SubCommand::Wordlist {command: WordlistCommand::Merge {word_files, subtract, keep_order, out}} => {
+            merge_word_lists(word_files, subtract, keep_order, out)
+        }
+        SubCommand::Wordlist {command: WordlistCommand::Filter {word_file, length, skip_capitalized, mut out}} => {
+            let words = filter_length(&word_file.path().to_string(), word_file, length.get(), skip_capitalized)?;
+            for word in &words {
+                writeln!(out, "{}", word)?;
+            }
+            eprintln!("{} words with {} letters", words.len(), length);
+            Ok(())
+        }
+    }?;
+    Ok(ExitStatus::Success)
+}
+
+/// A word list given on the command line: either a file (`-` for standard input), a list
+/// built into the program, written `bundled:NAME`, see [`SubCommand::Bundle`], or a
+/// dictionary compiled into the program, written `dict:NAME` for its allowed words and
+/// `dict:NAME/answers` for its answers. Without a name, the dictionary of `--dict` is used.
+/// With the `fetch` feature, an `http://` URL is downloaded once and then read from the cache.
+#[derive(Clone)]
+enum WordSource {
+    File(Input),
+    Bundled(String),
+    Dictionary { name: Option<String>, answers: bool },
+    #[cfg(feature = "fetch")]
+    Url(String),
+}
+
+impl WordSource {
+    /// The solutions that go with this list of allowed words if no list of solutions is given:
+    /// the answers of a dictionary, or `None` if every allowed word is a possible solution.
+    fn answers(&self) -> Option<WordSource> {
+        match self {
+            WordSource::Dictionary { name, answers: false } => {
+                Some(WordSource::Dictionary { name: name.clone(), answers: true })
+            }
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            WordSource::File(input) => input.path().to_string(),
+            WordSource::Bundled(name) => format!("bundled:{}", name),
+            WordSource::Dictionary { name, answers } => {
+                let name = name.as_deref().unwrap_or_else(|| DICTIONARY.get().map_or("", |d| d.name));
+                format!("dict:{}{}", name, if *answers { "/answers" } else { "" })
+            }
+            #[cfg(feature = "fetch")]
+            WordSource::Url(url) => url.clone(),
+        }
+    }
+}
+
+impl FromStr for WordSource {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<WordSource, String> {
+        if let Some(name) = value.strip_prefix("bundled:") {
+            return Ok(WordSource::Bundled(name.to_string()));
+        }
+        if let Some(name) = value.strip_prefix("dict:") {
+            let (name, answers) = match name.strip_suffix("/answers") {
+                Some(name) => (name, true),
+                None => (name, false),
+            };
+            let name = Some(name.to_string()).filter(|name| !name.is_empty());
+            return Ok(WordSource::Dictionary { name, answers });
+        }
+        #[cfg(feature = "fetch")]
+        if fetch::is_url(value) {
+            return Ok(WordSource::Url(value.to_string()));
+        }
+        Input::new(value).map(WordSource::File).map_err(|e| e.to_string())
     }
 }
 
-fn play_game<R: Read>(word_file: R) {
-    let words = read_file(word_file);
-    PlayGame::new(&words).run_game();
+/// Reads the word list given as a command line argument. Malformed lines are skipped with
+/// a warning.
+fn read_file(source: WordSource) -> Result<Vec<Word>, WordleError> {
+    Ok(read_weighted(source)?.into_words())
 }
 
+/// Like [`read_file`], but keeps the frequencies the list gives after a tab as the weights of
+/// its words. The built-in dictionaries weigh all words the same.
+fn read_weighted(source: WordSource) -> Result<WeightedList, WordleError> {
+    let name = source.name();
+    let mut skipped = Vec::new();
+    let words = match source {
+        WordSource::File(input) => read_list(&name, input, &mut skipped)?,
+        WordSource::Bundled(list) => {
+            let lists = bundled_lists()?;
+            let text = lists.iter()
+                .find(|l| l.name == list || list.is_empty())
+                .map(|l| &l.text)
+                .ok_or_else(|| WordleError::MissingBundle {
+                    name: list,
+                    available: match lists.is_empty() {
+                        true => "none".to_string(),
+                        false => lists.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(", "),
+                    },
+                })?;
+            read_list(&name, text.as_bytes(), &mut skipped)?
+        }
+        WordSource::Dictionary { name, answers } => {
+            let dictionary = match name {
+                Some(name) => Dictionary::find(&name).ok_or_else(|| {
+                    WordleError::MissingDictionary { name, available: Dictionary::names() }
+                })?,
+                None => DICTIONARY.get().copied().unwrap_or(&DICTIONARIES[0]),
+            };
+            WeightedList::uniform(&if answers { dictionary.answers()? } else { dictionary.words()? })
+        }
+        #[cfg(feature = "fetch")]
+        WordSource::Url(url) => read_list(&name, fetch::get_cached(&url)?.as_bytes(), &mut skipped)?,
+    };
+    for error in skipped {
+        eprintln!("{:?}", miette::Report::new(error).wrap_err("skipping malformed line"));
+    }
+    Ok(words)
+}
 
+/// Reads the solutions of a simulation over `words`, read from `words_file`: the list of
+/// `solutions_file`, or without one, the answers of the dictionary if `words_file` is one,
+/// and otherwise `words` themselves.
+fn read_solutions(words_file: &WordSource, solutions_file: Option<WordSource>,
+                  words: &[Word]) -> Result<Vec<Word>, WordleError> {
+    match solutions_file.or_else(|| words_file.answers()) {
+        Some(solutions_file) => read_file(solutions_file),
+        None => Ok(words.to_vec()),
+    }
+}
+
+/// Reads a word list, converting it to lowercase and folding accents as chosen on the command
+/// line unless `--keep-case` is given. Every word is kept once, also if it only becomes the same
+/// as another by folding. Lists may give the frequency of each word after a tab.
+fn read_list<R: Read>(name: &str, reader: R, skipped: &mut Vec<WordListError>) -> Result<WeightedList, WordListError> {
+    let ignore_case = !KEEP_CASE.get().copied().unwrap_or(false);
+    WeightedList::read(name, reader, ignore_case, skipped)
+}
+
+/// Parses a word given as the value of the command line option `--argument`.
+fn parse_word(argument: &str, value: &str) -> Result<Word, WordleError> {
+    Word::try_from_str_ignore_case(value)
+        .map_err(|source| WordleError::Argument { argument: argument.to_string(), source })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_game(word_file: WordSource, chunk_size: Option<usize>, score_budget: Option<usize>,
+            time_budget: Option<Duration>, cache_dir: Option<PathBuf>, tree: Option<Arc<DecisionTree>>, format: Format,
+            explain: bool, resume: Option<&Path>, guess_check: GuessCheck,
+            sharpening: Option<Sharpening>) -> Result<ExitStatus, WordleError> {
+    let weights = Arc::new(read_weighted(word_file)?);
+    let words = weights.words();
+    if let Err(e) = interrupt::install_handler() {
+        eprintln!("warning: Ctrl+C will end the program instead of the evaluation: {}", e);
+    }
+    let cache = match cache_dir {
+        Some(directory) => Some(ScoreCache::load_or_build(&directory, words, words)?),
+        None => None,
+    };
+    let game = match (chunk_size, score_budget, time_budget, &cache) {
+        (_, _, _, Some(cache)) => HelpGame::cached(words, cache),
+        (_, Some(budget), _, None) => HelpGame::budgeted(words, budget),
+        (_, None, Some(budget), None) => HelpGame::timed(words, budget),
+        (Some(chunk_size), None, None, None) => HelpGame::low_latency(words, chunk_size),
+        (None, None, None, None) => HelpGame::new(words),
+    };
+    let game = game.with_weights(Arc::clone(&weights));
+    let game = match sharpening {
+        Some(sharpening) => game.with_sharpening(sharpening),
+        None => game,
+    };
+    let game = match tree {
+        Some(tree) => game.with_tree(tree),
+        None => game,
+    };
+    let game = if explain { game.with_explanations() } else { game };
+    let game = game.with_guess_check(guess_check);
+    let mut game = match format {
+        Format::Text if !io::stdin().is_terminal() => game.with_script_output(),
+        Format::Text => game,
+        #[cfg(feature = "serde")]
+        Format::Json => game.with_json_output(),
+    };
+    if let Some(path) = resume {
+        game = game.resume(path)?;
+    }
+    game.run_game()?;
+    log_transcripts([game.transcript()])?;
+    Ok(ExitStatus::game(game.solution().is_some(), game.remaining()))
+}
+
+/// Stores `run` and its `games` in the database of `--db`, if one was given.
+#[cfg(feature = "sqlite")]
+fn store_results(run: Run, games: impl IntoIterator<Item = StoredGame>) -> Result<(), WordleError> {
+    if let Some(path) = DB.get() {
+        ResultStore::open(path)?.insert(&run, games)?;
+    }
+    Ok(())
+}
+
+/// Appends `transcripts` to the file of `--log`, if one was given.
+#[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+fn log_transcripts(transcripts: impl IntoIterator<Item = Transcript>) -> Result<(), WordleError> {
+    #[cfg(feature = "serde")]
+    if let Some(path) = LOG.get() {
+        let mut log = TranscriptLog::open(path)?;
+        for transcript in transcripts {
+            log.write(&transcript)?;
+        }
+        log.finish()?;
+    }
+    Ok(())
+}
+
+
+#[allow(clippy::too_many_arguments)]
+fn full_runs(words_file: WordSource, solutions_file: Option<WordSource>, output: Option<Output>,
+             format: Option<RecordFormat>, first_guess: Word, strategy: Strategy, seed: u64,
+             cache_dir: Option<PathBuf>, tree: Option<(String, Arc<DecisionTree>)>) -> Result<(), WordleError> {
+    let start = Instant::now();
+    let solutions_file = solutions_file.or_else(|| words_file.answers());
+    let words_name = words_file.name();
+    let solutions_name = solutions_file.as_ref().map_or_else(|| words_name.clone(), WordSource::name);
+    let words = read_file(words_file)?;
+    let solutions = match solutions_file {
+        Some(solutions_file) => read_file(solutions_file)?,
+        None => words.clone(),
+    };
+    let mut manifest = Manifest::new()
+        .word_list(&words_name, &words)
+        .word_list(&solutions_name, &solutions)
+        .param("first guess", first_guess)
+        .strategy(strategy)
+        .seed(seed);
+    if let Some((name, _)) = &tree {
+        manifest = manifest.param("decision tree", name);
+    }
+    // Records go to stdout if a format is requested without a file, replacing the report.
+    let quiet = output.is_none() && format.is_some();
+    let manifest_path = output.as_ref()
+        .filter(|output| !output.is_std())
+        .map(|output| format!("{}.manifest", output.path()));
+    let mut writer = match (output, format) {
+        (Some(output), format) => {
+            let format = format.unwrap_or_else(|| default_record_format(&output));
+            Some(RecordWriter::new(output, format)?)
+        }
+        (None, Some(format)) => Some(RecordWriter::new(Output::std(), format)?),
+        (None, None) => None,
+    };
+    // Whole games run in parallel. Their output is buffered and printed in order, one chunk
+    // of games at a time, so that results keep coming in during long runs.
+    let chunk_size = 16 * rayon::current_num_threads();
+    let matrix = match cache_dir {
+        Some(directory) => ScoreCache::load_or_build(&directory, &words, &words)?.matrix,
+        None => Arc::new(PatternMatrix::new(&words, &words)),
+    };
+    let mut records = Vec::with_capacity(solutions.len());
+    let verbosity = Verbosity::current();
+    let mut progress = match verbosity.is_quiet() {
+        true => Progress::hidden(solutions.len()),
+        false => Progress::new(solutions.len()),
+    };
+    for (c, chunk) in solutions.chunks(chunk_size).enumerate() {
+        let games = chunk.par_iter().enumerate().map(|(i, s)| {
+            let mut game = SimulatedGame::new(&words, *s, first_guess)
+                .with_strategy(strategy)
+                .with_matrix(Arc::clone(&matrix))
+                .with_seed(seed, c * chunk_size + i)
+                .quiet();
+            if let Some((_, tree)) = &tree {
+                game = game.with_tree(Arc::clone(tree));
+            }
+            game.run_game();
+            (game.record(), game.summary())
+        }).collect::<Vec<_>>();
+        progress.clear();
+        for (record, summary) in games {
+            if !quiet && !verbosity.is_quiet() {
+                println!("{}", summary);
+            }
+            if let Some(writer) = &mut writer {
+                writer.write(&record)?;
+            }
+            records.push(record);
+        }
+        progress.advance(chunk.len());
+    }
+    progress.clear();
+    if let Some(writer) = writer {
+        writer.finish()?;
+    }
+    log_transcripts(records.iter().map(Transcript::simulated))?;
+    #[cfg(feature = "sqlite")]
+    store_results(Run {
+        mode: Mode::Batch,
+        strategy: if tree.is_some() { "tree".to_string() } else { strategy.to_string() },
+        first_guess: Some(first_guess),
+        word_list: Manifest::hash(&words),
+        solution_list: Manifest::hash(&solutions),
+        elapsed: start.elapsed(),
+    }, records.iter().map(StoredGame::from))?;
+    if !quiet {
+        LetterStats::new(&records).print();
+        GuessHistogram::new(&records, SimulatedGame::MAX_ROUNDS as usize).print();
+    }
+    manifest.set_elapsed(start.elapsed());
+    write_manifest(&manifest, quiet, manifest_path)
+}
+
+/// Prints the manifest of a batch run, to standard error if standard output holds the
+/// records, and writes it next to the exported records if there are any.
+fn write_manifest(manifest: &Manifest, quiet: bool, path: Option<String>) -> Result<(), WordleError> {
+    if quiet {
+        manifest.write(std::io::stderr())?;
+    } else {
+        println!("{}", style::bold("Manifest:"));
+        manifest.write(std::io::stdout())?;
+    }
+    if let Some(path) = path {
+        manifest.write(File::create(path)?)?;
+    }
+    Ok(())
+}
+
+/// Picks the record format from the extension of the `--output` file.
+fn default_record_format(output: &Output) -> RecordFormat {
+    match output.path().extension().and_then(|e| e.to_str()) {
+        #[cfg(feature = "serde")]
+        Some("json" | "jsonl") => RecordFormat::Json,
+        _ => RecordFormat::Csv,
+    }
+}
+
+/// Reads a decision tree written by `build-tree`.
+fn read_tree(file: Input) -> Result<Arc<DecisionTree>, WordleError> {
+    Ok(Arc::new(DecisionTree::read(file)?))
+}
+
+/// Serves the line protocol on standard input and output, or with the `serde` feature, a REST
+/// API on the address `http`. Slack requests are checked against the signing secret in the
+/// environment variable `SLACK_SIGNING_SECRET`, if it is set.
+fn serve(words_file: WordSource, solutions_file: Option<WordSource>,
+         http: Option<String>) -> Result<(), WordleError> {
+    let words = read_file(words_file)?;
+    let solutions = match solutions_file {
+        Some(solutions_file) => read_file(solutions_file)?,
+        None => words.clone(),
+    };
+    let solver = Solver::with_solutions(&words, &solutions);
+    match http {
+        #[cfg(feature = "serde")]
+        Some(address) => {
+            let listener = std::net::TcpListener::bind(&address)?;
+            eprintln!("Serving on http://{}", listener.local_addr()?);
+            let mut server = HttpServer::new(solver);
+            match std::env::var("SLACK_SIGNING_SECRET") {
+                Ok(secret) => server = server.with_slack_secret(secret),
+                Err(_) => eprintln!("SLACK_SIGNING_SECRET is not set, /slack accepts unsigned requests"),
+            }
+            server.serve(listener)?;
+        }
+        #[cfg(not(feature = "serde"))]
+        Some(_) => unreachable!("--http needs the serde feature"),
+        None => serve_lines(&mut Session::new(solver), io::stdin().lock(), io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+/// Runs the Discord bot with the token in the environment variable `DISCORD_TOKEN`. The bot
+/// answers until the program is stopped, so the word lists live as long as the program.
+#[cfg(feature = "discord")]
+fn discord_bot(words_file: WordSource, solutions_file: Option<WordSource>) -> Result<(), WordleError> {
+    let token = std::env::var("DISCORD_TOKEN")
+        .map_err(|_| WordleError::MissingToken { variable: "DISCORD_TOKEN".to_string() })?;
+    let words = read_file(words_file.clone())?;
+    let solutions = read_solutions(&words_file, solutions_file, &words)?;
+    let (words, solutions) = (Vec::leak(words), Vec::leak(solutions));
+    eprintln!("Connecting to Discord with {} words and {} solutions", words.len(), solutions.len());
+    DiscordBot::new(Solver::with_solutions(words, solutions)).run(&token)
+}
+
+/// Runs the Telegram bot with the token in the environment variable `TELEGRAM_BOT_TOKEN`.
+#[cfg(feature = "telegram")]
+fn telegram_bot(words_file: WordSource, solutions_file: Option<WordSource>) -> Result<(), WordleError> {
+    let token = std::env::var("TELEGRAM_BOT_TOKEN")
+        .map_err(|_| WordleError::MissingToken { variable: "TELEGRAM_BOT_TOKEN".to_string() })?;
+    let words = read_file(words_file.clone())?;
+    let solutions = read_solutions(&words_file, solutions_file, &words)?;
+    TelegramBot::new(Solver::with_solutions(&words, &solutions)).run(&token)
+}
+
+fn verify_tree(tree_file: Input, words_file: WordSource, solutions_file: Option<WordSource>,
+               hard_mode: bool) -> Result<(), WordleError> {
+    let tree = read_tree(tree_file)?;
+    let words = read_file(words_file)?;
+    let solutions = match solutions_file {
+        Some(solutions_file) => read_file(solutions_file)?,
+        None => words.clone(),
+    };
+    let verification = tree.verify(&words, &solutions, SimulatedGame::MAX_ROUNDS as usize,
+                                   hard_mode || tree.hard_mode);
+    verification.print();
+    match verification.passed() {
+        true => Ok(()),
+        false => Err(WordleError::InvalidTree { problems: verification.problems() }),
+    }
+}
+
+fn build_tree(words_file: WordSource, solutions_file: Option<WordSource>, strategy: Strategy,
+              first_guess: Option<Word>, hard_mode: bool, out: Output,
+              format: Option<TreeFormat>) -> Result<(), WordleError> {
+    let words = read_file(words_file)?;
+    let solutions = match solutions_file {
+        Some(solutions_file) => read_file(solutions_file)?,
+        None => words.clone(),
+    };
+    let unguessable = DecisionTree::unguessable(&words, &solutions);
+    if let Some(example) = unguessable.first() {
+        return Err(WordleError::UnguessableSolutions { count: unguessable.len(), example: *example });
+    }
+    let format = format.unwrap_or(match out.path().extension().and_then(|e| e.to_str()) {
+        #[cfg(feature = "serde")]
+        Some("json") => TreeFormat::Json,
+        _ => TreeFormat::Binary,
+    });
+    let start = Instant::now();
+    let (tree, complete) = if hard_mode {
+        DecisionTree::build_hard_mode(&words, &solutions, strategy, first_guess)
+    } else {
+        (DecisionTree::build(&words, &solutions, strategy, first_guess), true)
+    };
+    let stats = tree.stats(&solutions);
+    // The report goes to stderr if the tree itself is written to stdout.
+    let heading = format!("Decision tree ({} strategy{}, {} nodes, {:.1}s):", strategy,
+                          if hard_mode { ", hard mode" } else { "" }, tree.nodes(), start.elapsed().as_secs_f64());
+    let mut report = format!("{} first guess {}, {} solutions, average {:.3} guesses, at most {}, {} failures",
+                             style::bold(heading), tree.root.guess, stats.solutions, stats.average, stats.max_depth,
+                             stats.failures);
+    if hard_mode {
+        report.push_str(if complete {
+            "\nA hard-mode tree solving every solution within six guesses exists."
+        } else {
+            "\nNo hard-mode tree solving every solution within six guesses was found. Only the \
+             three best guesses of every position were tried, so one may still exist."
+        });
+    }
+    if out.is_std() {
+        eprintln!("{}", report);
+    } else {
+        println!("{}", report);
+    }
+    tree.write(out, format)?;
+    Ok(())
+}
+
+fn absurdle_run(words_file: WordSource, solutions_file: Option<WordSource>, first_guess: Word) -> Result<(), WordleError> {
+    let words = read_file(words_file.clone())?;
+    let solutions = read_solutions(&words_file, solutions_file, &words)?;
+    let matrix = Arc::new(PatternMatrix::new(&words, &solutions));
+    let rounds = SimulatedGame::absurdle(&words, &solutions, first_guess).with_matrix(matrix).run_game();
+    println!("Score {}", rounds);
+    Ok(())
+}
+
+/// Plays Antiwordle with every solution and reports how many guesses the games last.
+fn antiwordle_runs(words_file: WordSource, solutions_file: Option<WordSource>,
+                   cache_dir: Option<PathBuf>) -> Result<(), WordleError> {
+    let words = read_file(words_file.clone())?;
+    let solutions = read_solutions(&words_file, solutions_file, &words)?;
+    let matrix = match cache_dir {
+        Some(directory) => ScoreCache::load_or_build(&directory, &words, &words)?.matrix,
+        None => Arc::new(PatternMatrix::new(&words, &words)),
+    };
+    let opener = antiwordle_opener(&words);
+    let games = solutions.par_iter().map(|s| {
+        let mut game = SimulatedGame::antiwordle(&words, *s, opener).with_matrix(Arc::clone(&matrix)).quiet();
+        (game.run_game(), *s, game.summary())
+    }).collect::<Vec<_>>();
+    for (_, _, summary) in games.iter().filter(|_| !Verbosity::current().is_quiet()) {
+        println!("{}", summary);
+    }
+    if let Some((longest, solution, _)) = games.iter().max_by_key(|(rounds, _, _)| *rounds) {
+        let average = games.iter().map(|(rounds, _, _)| f64::from(*rounds)).sum::<f64>() / games.len() as f64;
+        println!("{}", style::bold(format_args!("Average length {:.3}, longest {} ({})", average, longest, solution)));
+    }
+    Ok(())
+}
+
+fn opener_tournament(words_file: WordSource, solutions_file: Option<WordSource>, openers: &[Word],
+                     top: usize) -> Result<(), WordleError> {
+    let words = read_file(words_file.clone())?;
+    let solutions = read_solutions(&words_file, solutions_file, &words)?;
+    let line = |r: &OpenerResult| format!("{}   {:>7.3}  {:>5}  {:>8}", r.opener, r.average, r.worst, r.failures);
+    println!("{}", style::bold("opener  average  worst  failures"));
+    let results = tournament(&words, &solutions, openers, top, |result| println!("{}", line(result)));
+    println!("{}", style::bold(format_args!("Ranking ({} solutions):", solutions.len())));
+    for (i, result) in results.iter().enumerate() {
+        println!("{:>2}. {}", i + 1, line(result));
+    }
+    Ok(())
+}
+
+fn compare_strategies(words_file: WordSource, solutions_file: Option<WordSource>, strategies: &[Strategy],
+                      first_guess: Word, seed: u64) -> Result<(), WordleError> {
+    let start = Instant::now();
+    let solutions_file = solutions_file.or_else(|| words_file.answers());
+    let words_name = words_file.name();
+    let solutions_name = solutions_file.as_ref().map_or_else(|| words_name.clone(), WordSource::name);
+    let words = read_file(words_file)?;
+    let solutions = match solutions_file {
+        Some(solutions_file) => read_file(solutions_file)?,
+        None => words.clone(),
+    };
+    let mut manifest = Manifest::new()
+        .word_list(&words_name, &words)
+        .word_list(&solutions_name, &solutions)
+        .param("first guess", first_guess)
+        .seed(seed);
+    for strategy in strategies {
+        manifest = manifest.strategy(*strategy);
+    }
+    println!("{}",
+             style::bold(format_args!("Strategies ({} solutions, first guess {}):", solutions.len(), first_guess)));
+    println!("{}", style::bold("strategy     average  worst  failures   runtime"));
+    compare(&words, &solutions, strategies, first_guess, seed, |r| {
+        println!("{:<10}  {:>8.3}  {:>5}  {:>8}  {:>7.1}s",
+                 r.strategy.to_string(), r.average, r.worst, r.failures, r.elapsed.as_secs_f64());
+    });
+    manifest.set_elapsed(start.elapsed());
+    write_manifest(&manifest, false, None)
+}
+
+/// Replays the answers of `answers_file` with every [`Prior`] and prints the average number of
+/// guesses of each, over all answers and by `period` answers.
+fn backtest_priors(answers_file: WordSource, words_file: WordSource, solutions_file: Option<WordSource>,
+                   first_guess: Word, sharpening: Sharpening, period: usize) -> Result<(), WordleError> {
+    let start = Instant::now();
+    let answers_name = answers_file.name();
+    let words_name = words_file.name();
+    let solutions_name = solutions_file.as_ref().map_or_else(|| words_name.clone(), WordSource::name);
+    let answers = read_file(answers_file)?;
+    let (words, solutions) = read_weighted_lists(words_file, solutions_file)?;
+    if solutions.is_uniform() {
+        eprintln!("The solutions have no frequencies, so all priors weigh them the same.");
+    }
+    let skipped = answers.iter().filter(|answer| solutions.weight(answer).is_none()).count();
+    if skipped > 0 {
+        eprintln!("Skipping {} answer{} that {} not in the list of solutions.", skipped,
+                  if skipped == 1 { "" } else { "s" }, if skipped == 1 { "is" } else { "are" });
+    }
+    let mut manifest = Manifest::new()
+        .word_list(&answers_name, &answers)
+        .word_list(&words_name, words.words())
+        .word_list(&solutions_name, solutions.words())
+        .param("first guess", first_guess)
+        .param("sharpening", sharpening);
+    let solutions = Arc::new(solutions);
+    let matrix = Arc::new(PatternMatrix::new(words.words(), solutions.words()));
+    let priors = [Prior::Uniform, Prior::Weighted, Prior::Sharpened(sharpening)];
+    let records = priors.map(|prior| backtest(&matrix, words.words(), &solutions, &answers, first_guess, prior));
+    let games = records[0].len();
+    println!("{}", style::bold(format_args!("Priors ({} answers, first guess {}, sharpening {}):", games,
+                                            first_guess, sharpening)));
+    println!("{}", style::bold("prior       average  worst  failures"));
+    for (prior, records) in priors.iter().zip(&records) {
+        let result = OpenerResult::new(first_guess, records);
+        println!("{:<10}  {:>7.3}  {:>5}  {:>8}", prior.to_string(), result.average, result.worst, result.failures);
+    }
+    println!();
+    println!("{}", style::bold(format_args!("Average guesses by {} answers:", period)));
+    println!("{}", style::bold("answers        uniform  weighted  sharpened"));
+    for first in (0..games).step_by(period) {
+        let last = usize::min(first + period, games);
+        let [uniform, weighted, sharpened] = records.each_ref()
+            .map(|records| OpenerResult::new(first_guess, &records[first..last]).average);
+        println!("{:<13}  {:>7.3}  {:>8.3}  {:>9.3}", format!("{}-{}", first + 1, last), uniform, weighted,
+                 sharpened);
+    }
+    manifest.set_elapsed(start.elapsed());
+    write_manifest(&manifest, false, None)
+}
+
+fn benchmark(words_file: WordSource, solutions_file: Option<WordSource>, runs: usize) -> Result<(), WordleError> {
+    let words = read_file(words_file.clone())?;
+    let solutions = read_solutions(&words_file, solutions_file, &words)?;
+    let threads = rayon::current_num_threads();
+    println!("{}", style::bold(format_args!("Benchmarks ({} words, {} solutions, {} runs, {} thread{}):",
+                                            words.len(), solutions.len(), runs, threads,
+                                            if threads == 1 { "" } else { "s" })));
+    println!("{}", style::bold("benchmark              min     median       mean    scores/s"));
+    bench::run(&words, &solutions, runs, |timing| {
+        let throughput = match timing.scores_per_second() {
+            Some(scores) => format!("{:>9.1}M", scores / 1e6),
+            None => format!("{:>10}", "-"),
+        };
+        println!("{:<15}  {:>9.3?}  {:>9.3?}  {:>9.3?}  {}",
+                 timing.name, timing.min, timing.median, timing.mean, throughput);
+    });
+    Ok(())
+}
+
+fn import_history(word_file: WordSource, history_file: Input, first_guess: Word) -> Result<(), WordleError> {
+    let words = read_file(word_file)?;
+    let name = history_file.path().to_string();
+    let mut skipped = Vec::new();
+    let mut games = read_history(&name, history_file, &mut skipped)?;
+    for error in skipped {
+        eprintln!("{:?}", miette::Report::new(error).wrap_err("skipping malformed line"));
+    }
+    games.retain(|game| {
+        let known = words.contains(&game.answer);
+        if !known {
+            eprintln!("skipping {}: {} is not in the word list", game.date, game.answer);
+        }
+        known
+    });
+    HumanComparison::new(&words, games, first_guess).print();
+    Ok(())
+}
+
+/// Reads the allowed guesses and the solutions with their weights, see [`read_weighted`].
+/// Without a list of solutions, every allowed guess is one.
+fn read_weighted_lists(words_file: WordSource,
+                       solutions_file: Option<WordSource>) -> Result<(WeightedList, WeightedList), WordleError> {
+    let words = read_weighted(words_file)?;
+    let solutions = match solutions_file {
+        Some(solutions_file) => read_weighted(solutions_file)?,
+        None => words.clone(),
+    };
+    Ok((words, solutions))
+}
+
+fn grade(words_file: WordSource, solutions_file: Option<WordSource>, answer: Word,
+         guesses: &[Word]) -> Result<(), WordleError> {
+    let (words, solutions) = read_weighted_lists(words_file, solutions_file)?;
+    if solutions.weight(&answer).is_none() {
+        eprintln!("{} is not in the list of solutions, the grades assume it could not be the answer", answer);
+    }
+    let rounds = guesses.iter().map(|guess| (*guess, score(guess, &answer))).collect::<Vec<_>>();
+    let solver = Solver::with_solutions(words.words(), solutions.words()).with_weights(Arc::new(solutions.clone()));
+    Grade::with_solver(solver, &rounds).print();
+    Ok(())
+}
+
+/// Prints the runs stored in `database`, or the failures of one of them.
+#[cfg(feature = "sqlite")]
+fn show_report(database: &Path, mode: Option<Mode>, limit: usize, failures: Option<i64>) -> Result<(), WordleError> {
+    let store = ResultStore::open(database)?;
+    if let Some(id) = failures {
+        match store.failures(id)? {
+            Some(solutions) if solutions.is_empty() => println!("Run {} solved every game.", id),
+            Some(solutions) => println!("{} {}", style::bold(format_args!("Run {} failed on {} solutions:", id,
+                                                                        solutions.len())), solutions.join(", ")),
+            None => println!("There is no run {} in {}.", id, database.display()),
+        }
+        return Ok(());
+    }
+    let runs = store.runs(mode, limit)?;
+    if runs.is_empty() {
+        println!("There are no runs in {}.", database.display());
+        return Ok(());
+    }
+    println!("{}", style::bold("   id  started              mode   strategy   first  words     games   solved  average      time"));
+    for run in runs {
+        let solved = 100.0 * f64::from(run.solved) / f64::from(run.games.max(1));
+        let average = run.average.map_or("-".to_string(), |average| format!("{:.3}", average));
+        println!("{:>5}  {}  {:<5}  {:<9}  {:<5}  {:.8}  {:>5}  {:>6.2}%  {:>7}  {:>7.1}s", run.id, run.started, run.mode,
+                 run.strategy, run.first_guess.as_deref().unwrap_or("-"), run.word_list, run.games, solved, average,
+                 run.elapsed.as_secs_f64());
+    }
+    Ok(())
+}
+
+/// Prints the games of `log_file`, graded against the solver with the word lists of
+/// `word_files` if given.
+#[cfg(feature = "serde")]
+fn replay(log_file: Input, word_files: Option<(WordSource, Option<WordSource>)>, game: Option<NonZeroUsize>,
+          unsolved: bool) -> Result<(), WordleError> {
+    let name = log_file.path().to_string();
+    let mut skipped = Vec::new();
+    let transcripts = read_transcripts(&name, log_file, &mut skipped)?;
+    for error in skipped {
+        eprintln!("{:?}", miette::Report::new(error).wrap_err("skipping malformed line"));
+    }
+    let lists = match word_files {
+        Some((words_file, solutions_file)) => Some(read_weighted_lists(words_file, solutions_file)?),
+        None => None,
+    };
+    // One matrix for all games, every game grades against a copy of the same solver.
+    let solver = lists.as_ref().map(|(words, solutions)| {
+        let matrix = Arc::new(PatternMatrix::new(words.words(), solutions.words()));
+        Solver::with_solutions(words.words(), solutions.words())
+            .with_matrix(matrix)
+            .with_weights(Arc::new(solutions.clone()))
+    });
+    let mut shown = 0;
+    for (i, transcript) in transcripts.iter().enumerate() {
+        if game.is_some_and(|game| game.get() != i + 1) || (unsolved && transcript.solved) {
+            continue;
+        }
+        println!("{} {}", style::bold(format_args!("Game {}:", i + 1)), transcript);
+        if let Some(solver) = &solver {
+            let rounds = transcript.rounds.iter().map(|round| (round.guess, round.pattern)).collect::<Vec<_>>();
+            Grade::with_solver(solver.clone(), &rounds).print();
+        }
+        println!();
+        shown += 1;
+    }
+    if shown == 0 {
+        println!("No games to show in {} ({} in total).", name, transcripts.len());
+    }
+    Ok(())
+}
+
+fn import_share(words_file: WordSource, solutions_file: Option<WordSource>, mut grid: Input,
+                answer: Option<Word>) -> Result<(), WordleError> {
+    let words = read_file(words_file)?;
+    let candidates = match (answer, solutions_file) {
+        (Some(answer), _) => vec![answer],
+        (None, Some(solutions_file)) => read_file(solutions_file)?,
+        (None, None) => words.clone(),
+    };
+    let mut text = String::new();
+    io::Read::read_to_string(&mut grid, &mut text)?;
+    let patterns = parse_grid(&text, SimulatedGame::MAX_ROUNDS)?;
+    ShareAnalysis::new(&words, &candidates, patterns).print();
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_game(word_file: WordSource, absurdle: bool, antiwordle: bool, copy: bool, seed: Option<u64>,
+             stats_file: Option<&Path>, guess_check: GuessCheck, practice: bool,
+             timer: Option<Option<Duration>>) -> Result<ExitStatus, WordleError> {
+    let words = read_file(word_file)?;
+    if absurdle {
+        let mut game = AbsurdleGame::new(&words).with_guess_check(guess_check);
+        game.run_game()?;
+        return Ok(ExitStatus::game(game.solved(), game.remaining()));
+    }
+    if antiwordle {
+        let game = match seed {
+            Some(seed) => AntiwordleGame::seeded(&words, seed),
+            None => AntiwordleGame::new(&words),
+        };
+        game.with_guess_check(guess_check).run_game()?;
+        return Ok(ExitStatus::Success);
+    }
+    let game = match seed {
+        Some(seed) => PlayGame::seeded(&words, seed),
+        None => PlayGame::new(&words),
+    };
+    let game = game.with_guess_check(guess_check);
+    let game = if practice { game.with_practice() } else { game };
+    let game = match timer {
+        Some(limit) => game.with_timer(limit),
+        None => game,
+    };
+    #[cfg(feature = "clipboard")]
+    let game = if copy { game.with_copy() } else { game };
+    #[cfg(not(feature = "clipboard"))]
+    if copy {
+        eprintln!("--copy needs the `clipboard` feature, ignoring it");
+    }
+    let mut game = game;
+    #[cfg(feature = "sqlite")]
+    let start = Instant::now();
+    game.run_game()?;
+    log_transcripts([game.transcript()])?;
+    #[cfg(feature = "sqlite")]
+    if game.is_over() && !practice {
+        let hash = Manifest::hash(&words);
+        store_results(Run {
+            mode: Mode::Play,
+            strategy: "human".to_string(),
+            first_guess: None,
+            word_list: hash.clone(),
+            solution_list: hash,
+            elapsed: start.elapsed(),
+        }, [StoredGame {
+            solution: game.solution(),
+            guesses: game.transcript().rounds.iter().map(|round| round.guess).collect(),
+            solved: game.solved(),
+            time: game.total_time(),
+        }])?;
+    }
+    if let Some(path) = stats_file.filter(|_| game.is_over()) {
+        let record = PlayRecord {
+            date: Date::today(),
+            word: game.solution(),
+            guesses: game.rounds() as u8,
+            won: game.solved(),
+            time: game.total_time(),
+        };
+        Stats::append(path, &record)?;
+        println!();
+        println!("{}", Stats::load(path)?);
+    }
+    Ok(match game.solved() {
+        true => ExitStatus::Success,
+        false => ExitStatus::Unsolved,
+    })
+}
+
+fn show_stats(stats_file: Option<&Path>) -> Result<(), WordleError> {
+    let stats = match stats_file {
+        Some(path) => Stats::load(path)?,
+        None => Stats::default(),
+    };
+    println!("{}", stats);
+    Ok(())
+}
+
+/// Prints the problems of the word list `word_file`, and writes the cleaned list to `out`.
+fn validate_word_list(word_file: Input, out: Option<Output>) -> Result<(), WordleError> {
+    let name = word_file.path().to_string();
+    let validation = validate(&name, word_file)?;
+    println!("{}", validation);
+    match out {
+        Some(mut out) => {
+            for word in &validation.words {
+                writeln!(out, "{}", word)?;
+            }
+            Ok(())
+        }
+        None if validation.is_clean() => Ok(()),
+        None => Err(WordleError::InvalidWordList { name, issues: validation.issues.len() }),
+    }
+}
+
+/// Merges the word lists `word_files` without the words of `subtract` and writes the result to `out`.
+fn merge_word_lists(word_files: Vec<WordSource>, subtract: Vec<WordSource>, keep_order: bool,
+                    mut out: Output) -> Result<(), WordleError> {
+    let lists = word_files.into_iter().map(read_file).collect::<Result<Vec<Vec<Word>>, WordleError>>()?;
+    let subtract = subtract.into_iter().map(read_file).collect::<Result<Vec<Vec<Word>>, WordleError>>()?.concat();
+    let merged = merge(lists.iter().map(Vec::as_slice), &subtract, !keep_order);
+    for word in &merged {
+        writeln!(out, "{}", word)?;
+    }
+    eprintln!("{} words from {} lists", merged.len(), lists.len());
+    Ok(())
+}
+
+/// Parses a duration like `2s`, `500ms` or `1.5` (seconds), for `--budget`.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = match value.strip_suffix("ms") {
+        Some(number) => (number, 0.001),
+        None => (value.strip_suffix('s').unwrap_or(value), 1.0),
+    };
+    number.trim().parse::<f64>().ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * unit).ok())
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| format!("<{}> is not a positive duration like 2s or 500ms", value))
+}
+
+/// Parses the weight of `--win-weight`, which must not be negative.
+fn parse_weight(value: &str) -> Result<f64, String> {
+    value.trim().parse::<f64>().ok()
+        .filter(|weight| weight.is_finite() && *weight >= 0.0)
+        .ok_or_else(|| format!("<{}> is not a non-negative number", value))
+}
+
+/// Parses the probability of `--error-rate`, which must be between 0 and 1.
+fn parse_probability(value: &str) -> Result<f64, String> {
+    value.trim().parse::<f64>().ok()
+        .filter(|probability| (0.0..=1.0).contains(probability))
+        .ok_or_else(|| format!("<{}> is not a probability between 0 and 1", value))
+}
+
+/// Parses the value of `daily --date`, which must not be before the first puzzle.
+fn parse_puzzle_date(value: &str) -> Result<Date, String> {
+    let date: Date = value.parse()?;
+    match date.puzzle_number() {
+        Some(_) => Ok(date),
+        None => Err(format!("the first Wordle was published on {}", Date::LAUNCH)),
+    }
+}
+
+/// Downloads the metadata of the puzzle of `date` from `url`, where `{date}` stands for the date.
+#[cfg(all(feature = "fetch", feature = "serde"))]
+fn fetch_puzzle(url: &str, date: Date) -> Result<Puzzle, WordleError> {
+    let url = url.replace("{date}", &date.to_string());
+    let text = fetch::get(&url)?;
+    serde_json::from_str(&text).map_err(|_| FetchError::Response { url }.into())
+}
+
+#[cfg_attr(not(all(feature = "fetch", feature = "serde")), allow(unused_variables, unused_mut))]
+fn daily(word_file: WordSource, date: Date, spoiler: bool, metadata_url: &str) -> Result<(), WordleError> {
+    let mut number = date.puzzle_number().unwrap_or(0);
+    #[cfg(all(feature = "fetch", feature = "serde"))]
+    let answer = match spoiler {
+        true => {
+            let puzzle = fetch_puzzle(metadata_url, date)?;
+            number = puzzle.days_since_launch;
+            Some(Word::try_from_str_ignore_case(&puzzle.solution)?)
+        }
+        false => None,
+    };
+    #[cfg(not(all(feature = "fetch", feature = "serde")))]
+    if spoiler {
+        eprintln!("--spoiler needs the `fetch` and `serde` features, ignoring it");
+    }
+    let words = read_file(word_file)?;
+    println!("{}   {}", style::bold(format_args!("Wordle #{}", number)), date);
+    let mut game = match io::stdin().is_terminal() {
+        true => HelpGame::new(&words),
+        false => HelpGame::new(&words).with_script_output(),
+    };
+    game.run_game()?;
+    #[cfg(all(feature = "fetch", feature = "serde"))]
+    if let Some(answer) = answer {
+        match game.solution() {
+            Some(solution) if solution == answer => println!("Correct, the answer is {}.", style::bold(answer)),
+            _ if !words.contains(&answer) => println!("The answer is {}, which is not in the word list.",
+                                                      style::bold(answer)),
+            _ => println!("The answer is {}; check the patterns entered.", style::bold(answer)),
+        }
+    }
+    Ok(())
+}
+
+fn bundle(word_files: Vec<WordSource>, out: &Path) -> Result<(), WordleError> {
+    let mut lists = Vec::with_capacity(word_files.len());
+    for source in word_files {
+        let name = match &source {
+            WordSource::File(input) => input.path().file_name()
+                .map_or_else(|| input.path().to_string(), |n| n.to_string_lossy().to_string()),
+            WordSource::Bundled(name) => name.clone(),
+            WordSource::Dictionary { .. } => source.name().trim_start_matches("dict:").to_string(),
+            #[cfg(feature = "fetch")]
+            WordSource::Url(url) => url.trim_end_matches('/').rsplit('/').next().unwrap_or(url).to_string(),
+        };
+        lists.push(BundledList::new(&name, &read_file(source)?));
+    }
+    write_bundle(&std::env::current_exe()?, &lists, File::create(out)?)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(out, std::fs::Permissions::from_mode(0o755))?;
+    }
+    let names = lists.iter().map(|l| l.name.as_str()).collect::<Vec<_>>();
+    println!("Wrote {} with {}.", out.display(), names.join(", "));
+    Ok(())
+}
+
+fn phrase_game(phrase_file: Input, shape: &[usize]) -> Result<ExitStatus, WordleError> {
+    let name = phrase_file.path().to_string();
+    let mut skipped = Vec::new();
+    let phrases = read_phrases(&name, phrase_file, &mut skipped)?;
+    for error in skipped {
+        eprintln!("{:?}", miette::Report::new(error).wrap_err("skipping malformed line"));
+    }
+    let mut game = PhraseGame::new(&phrases, shape)?;
+    game.run_game()?;
+    Ok(ExitStatus::game(game.solved(), game.remaining()))
+}
+
+fn multi_game(word_file: WordSource, boards: usize) -> Result<ExitStatus, WordleError> {
+    let words = read_file(word_file)?;
+    let mut game = MultiGame::new(&words, boards);
+    game.run_game()?;
+    Ok(ExitStatus::game(game.solved(), game.remaining()))
+}
+
+fn clusters(word_file: WordSource, history: &str, probe: Option<&str>) -> Result<(), WordleError> {
+    let history = GameState::parse(history)?;
+    let probe = probe.map(|probe| parse_word("probe", probe)).transpose()?;
+    let words = read_file(word_file)?;
+    ClusterView::new(&words, &history).print(probe);
+    Ok(())
+}
+
+fn cheatsheet(word_file: WordSource, first_guess: &str, depth: u8, mut out: Output) -> Result<(), WordleError> {
+    let first_guess = parse_word("first-guess", first_guess)?;
+    let words = read_file(word_file)?;
+    let sheet = CheatSheet::new(&words, first_guess, depth);
+    sheet.write(&mut out)
+}
+
+fn next_guess(word_file: WordSource, history: &str) -> Result<(), WordleError> {
+    let state = GameState::parse(history)?;
+    let weights = Arc::new(read_weighted(word_file)?);
+    let mut solver = Solver::new(weights.words()).with_weights(Arc::clone(&weights));
+    for (guess, pattern) in &state.history {
+        solver.feedback(*guess, *pattern);
+    }
+    if solver.solution_space().is_empty() {
+        return Err(WordleError::InconsistentFeedback { state });
+    }
+    println!("{}", solver.best_guess());
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use rayon::prelude::*;
+use crate::alphabet::Alphabet;
+use crate::game::score;
+use crate::packed::{score_packed, PackedWord};
+use crate::pattern::Pattern;
+use crate::word::Word;
+
+/// The feedback of every guess for every solution, computed once so that games only look
+/// it up instead of calling [`score`] again and again.
+///
+/// The patterns are stored in a flat array with one row per guess, each holding the
+/// [`Pattern::index`] of the feedback for every solution. Guesses and solutions are
+/// identified by their position in the lists the matrix was built from. For `n` guesses and
+/// `m` solutions the matrix takes `n·m` bytes, i.e. about 220 MB if every word of a 15k-word
+/// list is both a guess and a solution.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::matrix::PatternMatrix;
+/// use wordl_rust_bot::{score, Word};
+///
+/// let words = ["tears", "bears", "crane"].map(Word::from_str);
+/// let matrix = PatternMatrix::new(&words, &words);
+/// let (guess, solution) = (matrix.guess_id(&words[2]).unwrap(), matrix.solution_id(&words[0]).unwrap());
+/// assert_eq!(matrix.pattern(guess, solution), score(&words[2], &words[0]));
+/// ```
+pub struct PatternMatrix {
+    patterns: Vec<u8>,
+    guesses: usize,
+    solutions: usize,
+    guess_ids: HashMap<Word, u32>,
+    solution_ids: HashMap<Word, u32>,
+}
+
+impl PatternMatrix {
+    /// Scores every word of `guesses` against every word of `solutions`, in parallel and with
+    /// [`score_packed`], or with [`score`] if the words have too many different letters to be
+    /// packed.
+    pub fn new(guesses: &[Word], solutions: &[Word]) -> PatternMatrix {
+        let mut patterns = vec![0_u8; guesses.len() * solutions.len()];
+        let alphabet = Alphabet::of(guesses.iter().chain(solutions));
+        if !solutions.is_empty() && PackedWord::fits(&alphabet) {
+            let packed = PackedWord::pack_all(solutions, &alphabet);
+            patterns.par_chunks_mut(solutions.len()).zip(guesses.par_iter()).for_each(|(row, guess)| {
+                let guess = PackedWord::new(guess, &alphabet);
+                for (pattern, solution) in row.iter_mut().zip(&packed) {
+                    *pattern = score_packed(guess, *solution).index() as u8;
+                }
+            });
+        } else if !solutions.is_empty() {
+            patterns.par_chunks_mut(solutions.len()).zip(guesses.par_iter()).for_each(|(row, guess)| {
+                for (pattern, solution) in row.iter_mut().zip(solutions) {
+                    *pattern = score(guess, solution).index() as u8;
+                }
+            });
+        }
+        Self::from_patterns(guesses, solutions, patterns)
+    }
+
+    /// Creates the matrix of `guesses` and `solutions` from patterns computed before, e.g.
+    /// read from a [`crate::cache::ScoreCache`].
+    pub(crate) fn from_patterns(guesses: &[Word], solutions: &[Word], patterns: Vec<u8>) -> PatternMatrix {
+        assert_eq!(patterns.len(), guesses.len() * solutions.len());
+        let ids = |words: &[Word]| words.iter().enumerate()
+            .rev()
+            .map(|(i, w)| (*w, i as u32))
+            .collect::<HashMap<Word, u32>>();
+        PatternMatrix {
+            patterns,
+            guesses: guesses.len(),
+            solutions: solutions.len(),
+            guess_ids: ids(guesses),
+            solution_ids: ids(solutions),
+        }
+    }
+
+    /// The number of guesses, i.e. rows.
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+
+    /// The number of solutions, i.e. columns.
+    pub fn solutions(&self) -> usize {
+        self.solutions
+    }
+
+    /// The position of `word` in the list of guesses, or `None` if it is not a guess.
+    /// Words listed twice have the id of their first occurrence.
+    pub fn guess_id(&self, word: &Word) -> Option<u32> {
+        self.guess_ids.get(word).copied()
+    }
+
+    /// The position of `word` in the list of solutions, or `None` if it is not a solution.
+    pub fn solution_id(&self, word: &Word) -> Option<u32> {
+        self.solution_ids.get(word).copied()
+    }
+
+    /// The [`Pattern::index`] of the feedback for every solution, if `guess` is the guess.
+    pub fn row(&self, guess: u32) -> &[u8] {
+        let start = guess as usize * self.solutions;
+        &self.patterns[start..start + self.solutions]
+    }
+
+    /// The feedback `guess` gets if `solution` is the solution.
+    pub fn pattern(&self, guess: u32, solution: u32) -> Pattern {
+        Pattern::from_index(self.row(guess)[solution as usize] as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matrix() {
+        let guesses = ["tears", "bears", "xylyl", "crane"].map(Word::from_str);
+        let solutions = ["crane", "gears", "slimy"].map(Word::from_str);
+        let matrix = PatternMatrix::new(&guesses, &solutions);
+        assert_eq!((matrix.guesses(), matrix.solutions()), (4, 3));
+        for (g, guess) in guesses.iter().enumerate() {
+            for (s, solution) in solutions.iter().enumerate() {
+                assert_eq!(matrix.pattern(g as u32, s as u32), score(guess, solution));
+            }
+        }
+        assert_eq!(matrix.guess_id(&Word::from_str("crane")), Some(3));
+        assert_eq!(matrix.solution_id(&Word::from_str("crane")), Some(0));
+        assert_eq!(matrix.solution_id(&Word::from_str("tears")), None);
+    }
+}
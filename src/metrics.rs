@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Counts how often a value fell into each of a fixed set of buckets, as a Prometheus
+/// histogram.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    /// A histogram with buckets up to each of `bounds`, in increasing order, and one for
+    /// larger values.
+    pub fn new(bounds: &'static [f64]) -> Histogram {
+        Histogram { bounds, counts: vec![0; bounds.len()], sum: 0.0, count: 0 }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        if let Some(bucket) = self.bounds.iter().position(|&bound| value <= bound) {
+            self.counts[bucket] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn write(&self, out: &mut String, name: &str, help: &str) {
+        header(out, name, help, "histogram");
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, self.count);
+        let _ = writeln!(out, "{}_sum {}", name, self.sum);
+        let _ = writeln!(out, "{}_count {}", name, self.count);
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind);
+}
+
+/// What the HTTP server has done since it started, for operators to monitor in Prometheus.
+///
+/// # Fields
+/// * `sessions` - The games started.
+/// * `evicted` - The games dropped because there were too many.
+/// * `won` - The games in which the solution was entered, counted when it is entered.
+/// * `abandoned` - The games that were deleted, dropped or reset before they were won.
+/// * `requests` - The number of requests answered, by status code.
+/// * `suggestion_seconds` - How long ranking the guesses took.
+/// * `solution_space` - The number of possible solutions after each guess.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wordl_rust_bot::metrics::Metrics;
+///
+/// let mut metrics = Metrics::default();
+/// metrics.session_started();
+/// metrics.feedback(3, false);
+/// metrics.suggested(Duration::from_millis(20));
+/// let text = metrics.render(1);
+/// assert!(text.contains("\nwordle_sessions_active 1\n"));
+/// assert!(text.contains("\nwordle_solution_space_bucket{le=\"5\"} 1\n"));
+/// assert!(text.contains("\nwordle_suggestion_seconds_bucket{le=\"0.01\"} 0\n"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metrics {
+    pub sessions: u64,
+    pub evicted: u64,
+    pub won: u64,
+    pub abandoned: u64,
+    pub requests: BTreeMap<u16, u64>,
+    pub suggestion_seconds: Histogram,
+    pub solution_space: Histogram,
+}
+
+impl Default for Metrics {
+    fn default() -> Metrics {
+        Metrics {
+            sessions: 0,
+            evicted: 0,
+            won: 0,
+            abandoned: 0,
+            requests: BTreeMap::new(),
+            suggestion_seconds: Histogram::new(Self::SECONDS),
+            solution_space: Histogram::new(Self::SOLUTION_SPACE),
+        }
+    }
+}
+
+impl Metrics {
+    /// The content type of [`Metrics::render`], the Prometheus text format.
+    pub const CONTENT_TYPE: &'static str = "text/plain; version=0.0.4";
+
+    const SECONDS: &'static [f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0];
+    const SOLUTION_SPACE: &'static [f64] = &[0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0,
+                                             1000.0, 2000.0, 5000.0, 10000.0];
+
+    pub fn session_started(&mut self) {
+        self.sessions += 1;
+    }
+
+    /// Counts a game that ended by being deleted, dropped or reset, and whether it had been
+    /// `solved` by then.
+    pub fn session_ended(&mut self, solved: bool, evicted: bool) {
+        if !solved {
+            self.abandoned += 1;
+        }
+        if evicted {
+            self.evicted += 1;
+        }
+    }
+
+    /// Counts a guess after which `solution_space` solutions were left, and which `solved`
+    /// the game.
+    pub fn feedback(&mut self, solution_space: usize, solved: bool) {
+        self.solution_space.observe(solution_space as f64);
+        if solved {
+            self.won += 1;
+        }
+    }
+
+    /// Counts a ranking of the guesses that took `time`.
+    pub fn suggested(&mut self, time: Duration) {
+        self.suggestion_seconds.observe(time.as_secs_f64());
+    }
+
+    pub fn request(&mut self, status: u16) {
+        *self.requests.entry(status).or_default() += 1;
+    }
+
+    /// The metrics in the Prometheus text format, with `active` games currently kept.
+    pub fn render(&self, active: usize) -> String {
+        let mut out = String::new();
+        let mut single = |name: &str, help: &str, kind: &str, value: u64| {
+            header(&mut out, name, help, kind);
+            let _ = writeln!(out, "{} {}", name, value);
+        };
+        single("wordle_sessions_total", "Games started.", "counter", self.sessions);
+        single("wordle_sessions_active", "Games currently kept.", "gauge", active as u64);
+        single("wordle_sessions_evicted_total", "Games dropped because there were too many.", "counter", self.evicted);
+        header(&mut out, "wordle_games_total", "Games won, and games ended before they were won.", "counter");
+        let _ = writeln!(out, "wordle_games_total{{outcome=\"won\"}} {}", self.won);
+        let _ = writeln!(out, "wordle_games_total{{outcome=\"abandoned\"}} {}", self.abandoned);
+        header(&mut out, "wordle_http_requests_total", "Requests answered, by status code.", "counter");
+        for (status, count) in &self.requests {
+            let _ = writeln!(out, "wordle_http_requests_total{{status=\"{}\"}} {}", status, count);
+        }
+        self.suggestion_seconds.write(&mut out, "wordle_suggestion_seconds", "Time taken to rank the guesses.");
+        self.solution_space.write(&mut out, "wordle_solution_space", "Possible solutions left after a guess.");
+        out
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the games print besides what they are run for, chosen once at startup with
+/// `-q` or `-v`, see [`Verbosity::set_current`].
+///
+/// # Variants
+/// * `Quiet` - Only results: `batch` prints the final summary but no line per game and no
+///   progress bar, and `assist` leaves out the list of remaining solutions.
+/// * `Normal` - The default.
+/// * `Verbose` - Details: `assist` lists every remaining solution, more suggestions, and
+///   how the best suggestion splits the solutions in every round.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::output::Verbosity;
+///
+/// assert_eq!(Verbosity::Normal.candidates_shown(), 5);
+/// assert_eq!(Verbosity::Verbose.candidates_shown(), usize::MAX);
+/// assert!(Verbosity::Quiet.is_quiet() && !Verbosity::Quiet.is_verbose());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+/// The current verbosity, as the index of the variant.
+static CURRENT: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+impl Verbosity {
+    /// The verbosity of the output, [`Verbosity::Normal`] unless set otherwise.
+    pub fn current() -> Verbosity {
+        match CURRENT.load(Ordering::Relaxed) {
+            0 => Verbosity::Quiet,
+            2 => Verbosity::Verbose,
+            _ => Verbosity::Normal,
+        }
+    }
+
+    /// Makes `self` the verbosity of the output from now on.
+    pub fn set_current(self) {
+        CURRENT.store(self as u8, Ordering::Relaxed);
+    }
+
+    pub fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
+
+    pub fn is_verbose(self) -> bool {
+        self == Verbosity::Verbose
+    }
+
+    /// How many of the remaining solutions are listed: all of them if verbose.
+    pub fn candidates_shown(self) -> usize {
+        match self {
+            Verbosity::Verbose => usize::MAX,
+            _ => 5,
+        }
+    }
+
+    /// How many suggested guesses are listed.
+    pub fn suggestions_shown(self) -> usize {
+        match self {
+            Verbosity::Verbose => 10,
+            _ => 5,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::alphabet::Alphabet;
+use crate::pattern::Pattern;
+use crate::word::{Word, WORD_LENGTH};
+
+/// The lowest bit of every letter field of a [`PackedWord`].
+const LOW_BITS: u32 = 0b00001_00001_00001_00001_00001;
+
+/// The value of green and yellow at each position of a [`Pattern::index`].
+const GREEN: [u32; WORD_LENGTH] = [2, 6, 18, 54, 162];
+const YELLOW: [u32; WORD_LENGTH] = [1, 3, 9, 27, 81];
+
+/// A [`Word`] packed into a single `u32`, five bits per letter with the first letter in the
+/// lowest bits, for scoring with bit operations instead of comparing letter by letter.
+///
+/// Each letter is stored as its index in an [`Alphabet`], which therefore must not have more
+/// than [`PackedWord::MAX_LETTERS`] letters. Words to be scored against each other must be
+/// packed with the same alphabet.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::alphabet::Alphabet;
+/// use wordl_rust_bot::packed::{score_packed, PackedWord};
+/// use wordl_rust_bot::{score, Word};
+///
+/// let (guess, solution) = (Word::from_str("tears"), Word::from_str("stear"));
+/// let alphabet = Alphabet::of([&guess, &solution]);
+/// assert_eq!(score_packed(PackedWord::new(&guess, &alphabet), PackedWord::new(&solution, &alphabet)),
+///            score(&guess, &solution));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PackedWord(u32);
+
+impl PackedWord {
+    /// The most letters an alphabet may have for its words to be packed: five bits per
+    /// letter, and four bits per letter for the counts in [`score_packed`].
+    pub const MAX_LETTERS: usize = 32;
+
+    /// Packs `word`.
+    ///
+    /// # Panics
+    /// If a letter of `word` is not in `alphabet`, or `alphabet` has more than
+    /// [`PackedWord::MAX_LETTERS`] letters.
+    pub fn new(word: &Word, alphabet: &Alphabet) -> PackedWord {
+        assert!(PackedWord::fits(alphabet), "alphabet has more than {} letters", PackedWord::MAX_LETTERS);
+        let packed = word.letters().iter().rev().fold(0, |packed, letter| {
+            let index = alphabet.index(*letter).unwrap_or_else(|| panic!("'{}' is not in the alphabet", letter));
+            packed << 5 | index as u32
+        });
+        PackedWord(packed)
+    }
+
+    /// Packs `word` without an alphabet, with `a` as letter 0, if it only has the letters `a` to
+    /// `z`. Words packed this way can only be scored against each other.
+    pub fn ascii(word: &Word) -> Option<PackedWord> {
+        let letters = word.as_bytes();
+        if !letters.iter().all(u8::is_ascii_lowercase) {
+            return None;
+        }
+        Some(PackedWord(letters.iter().rev().fold(0, |packed, letter| packed << 5 | u32::from(letter - b'a'))))
+    }
+
+    /// Whether words of `alphabet` can be packed.
+    pub fn fits(alphabet: &Alphabet) -> bool {
+        alphabet.len() <= PackedWord::MAX_LETTERS
+    }
+
+    /// The letter at `index` as its index in the alphabet.
+    fn letter(self, index: usize) -> u32 {
+        self.0 >> (5 * index) & 0b11111
+    }
+
+    /// Packs every word of `words`, see [`PackedWord::new`].
+    pub fn pack_all(words: &[Word], alphabet: &Alphabet) -> Vec<PackedWord> {
+        words.iter().map(|word| PackedWord::new(word, alphabet)).collect()
+    }
+}
+
+/// Computes the same feedback as [`crate::score`], but on packed words and without branches:
+///
+/// 1. The positions where the letters agree are the letter fields of `guess ^ solution` that
+///    are zero, found for all positions at once by folding each field into its lowest bit.
+/// 2. The letters of the solution that are not green are counted in a `u128` with four bits
+///    per letter of the alphabet.
+/// 3. Every other letter of the guess is yellow if its count is not zero, and uses up one.
+///
+/// This is the function used to fill a [`crate::matrix::PatternMatrix`], which scores every
+/// guess against every solution, and by [`crate::score`] for words of the letters `a` to `z`,
+/// see [`PackedWord::ascii`].
+pub fn score_packed(guess: PackedWord, solution: PackedWord) -> Pattern {
+    let diff = guess.0 ^ solution.0;
+    let different = (diff | diff >> 1 | diff >> 2 | diff >> 3 | diff >> 4) & LOW_BITS;
+    let green = !different & LOW_BITS;
+
+    let mut counts = 0_u128;
+    for i in 0..WORD_LENGTH {
+        let open = (different >> (5 * i)) as u128 & 1;
+        counts += open << (4 * solution.letter(i));
+    }
+
+    let mut index = 0;
+    for i in 0..WORD_LENGTH {
+        let is_green = green >> (5 * i) & 1;
+        let shift = 4 * guess.letter(i);
+        let available = (counts >> shift & 0b1111 != 0) as u32;
+        let is_yellow = available & (is_green ^ 1);
+        counts -= (is_yellow as u128) << shift;
+        index += is_green * GREEN[i] + is_yellow * YELLOW[i];
+    }
+    Pattern::from_index(index as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::score_codes;
+
+    /// The feedback of `guess` for `solution` without packing, to check against.
+    fn score(guess: &Word, solution: &Word) -> Pattern {
+        let mut pattern = Pattern::all_black();
+        score_codes(guess.as_bytes(), solution.as_bytes(), |i, color| pattern.set(i, color));
+        pattern
+    }
+
+    #[test]
+    fn test_score_packed() {
+        let words = ["tears", "stear", "aattt", "txxxx", "eerie", "sheen", "geese", "abbey", "zzzza", "azzzz",
+                     "llama", "mamma", "crane", "xylyl", "ääßaß", "ßaaää"].map(Word::from_str);
+        let alphabet = Alphabet::of(&words);
+        for guess in &words {
+            for solution in &words {
+                assert_eq!(score_packed(PackedWord::new(guess, &alphabet), PackedWord::new(solution, &alphabet)),
+                           score(guess, solution), "{} against {}", guess, solution);
+                if let (Some(g), Some(s)) = (PackedWord::ascii(guess), PackedWord::ascii(solution)) {
+                    assert_eq!(score_packed(g, s), score(guess, solution), "{} against {}", guess, solution);
+                    assert_eq!(crate::score(guess, solution), score(guess, solution));
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,35 @@
 use std::fmt::{Debug, Display, Formatter};
-use std::io;
 use std::ops::Index;
+use miette::Diagnostic;
+use thiserror::Error;
+use crate::error::WordleError;
+use crate::input;
+use crate::style::{Palette, Styled};
 use crate::word::WORD_LENGTH;
 
+/// Describes why a string is not a valid [`Pattern`].
+///
+/// # Variants
+/// * `Length` - The string does not have exactly [`WORD_LENGTH`] characters.
+/// * `Color` - The character at `position` is none of `g`, `y` and `b`.
+#[derive(Debug, Error, Diagnostic, Clone, PartialEq, Eq)]
+pub enum PatternParseError {
+    #[error("pattern <{pattern}> has {length} colors instead of {WORD_LENGTH}")]
+    #[diagnostic(help("enter one color per letter of the guess"))]
+    Length { pattern: String, length: usize },
+    #[error("pattern <{pattern}> contains unknown color '{letter}'")]
+    #[diagnostic(help("use g = green, y = yellow, b = black"))]
+    Color { pattern: String, letter: char, position: usize },
+}
+
 /// Represents the color feedback in a Wordle game.
 ///
 /// # Variants
 /// * `Green` - Indicates a correct letter in the correct position.
 /// * `Yellow` - Indicates a correct letter in the wrong position.
 /// * `Black` - Indicates a letter that is not present in the word.
-#[derive(PartialEq, Eq, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
 pub enum Color { Green, Yellow, Black, }
 
 impl Color {
@@ -27,15 +47,16 @@ impl Color {
 
 impl Display for Color {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", match self {
-            Color::Green => {"\x1b[32mg\x1b[0m"}
-            Color::Yellow => {"\x1b[33my\x1b[0m"}
-            Color::Black => {"\x1b[30mb\x1b[0m"}
-        })
+        let letter = match self {
+            Color::Green => 'g',
+            Color::Yellow => 'y',
+            Color::Black => 'b',
+        };
+        write!(f, "{}", Styled::new(Palette::current().pattern_code(*self), letter))
     }
 }
 
-#[derive(PartialEq, Eq, Copy, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub struct Pattern {
     pattern: u8
 }
@@ -47,41 +68,104 @@ impl Pattern {
 
     pub fn all_black() -> Pattern { Pattern{ pattern: 0 } }
 
+    pub fn all_green() -> Pattern { Pattern{ pattern: (Self::MAX - 1) as u8 } }
+
     pub fn index(&self) -> usize { self.pattern as usize }
 
+    /// The inverse of [`Pattern::index`].
+    pub fn from_index(index: usize) -> Pattern {
+        assert!(index < Self::MAX);
+        Pattern{ pattern: index as u8 }
+    }
+
     pub fn set(&mut self, i: usize, color: Color) {
         let lower = self.pattern % Self::BASES[i];
         let higher = self.pattern / Self::BASES[i + 1] * Self::BASES[i + 1];
         self.pattern = lower + higher + Self::BASES[i] * color.value();
     }
 
+    /// Parses a pattern written with one letter per position, `g` for green, `y` for yellow
+    /// and `b` for black, e.g. `bgybb`. Surrounding whitespace and case are ignored.
+    ///
+    /// # Panics
+    /// If the input is not a valid pattern, see [`Pattern::try_from_str`].
     pub fn from_string(line: &str) -> Pattern {
+        Pattern::try_from_str(line).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Parses a pattern like [`Pattern::from_string`], but reports malformed input instead
+    /// of panicking.
+    ///
+    /// # Errors
+    /// * [`PatternParseError::Length`] if the trimmed input does not have [`WORD_LENGTH`] characters.
+    /// * [`PatternParseError::Color`] if a character is none of `g`, `y` and `b`.
+    pub fn try_from_str(line: &str) -> Result<Pattern, PatternParseError> {
         let line = line.trim();
+        let chars = line.chars().collect::<Vec<char>>();
+        if chars.len() != WORD_LENGTH {
+            return Err(PatternParseError::Length { pattern: line.to_string(), length: chars.len() });
+        }
         let mut pattern = Pattern::all_black();
-        let line = line.chars().collect::<Vec<char>>();
-        assert_eq!(line.len(), WORD_LENGTH);
-        for i in 0..WORD_LENGTH {
-            let color = match line[i] {
+        for (i, c) in chars.iter().enumerate() {
+            let color = match c.to_ascii_lowercase() {
                 'b' => Color::Black,
                 'y' => Color::Yellow,
                 'g' => Color::Green,
-                _ => panic!("unknown char {}. Use g = green, y = yellow, b = black.",
-                            line[i]),
+                _ => return Err(PatternParseError::Color {
+                    pattern: line.to_string(), letter: *c, position: i
+                }),
             };
             pattern.set(i, color);
         }
-        pattern
+        Ok(pattern)
+    }
+
+    /// The pattern in the notation of [`Pattern::from_string`], without colors,
+    /// e.g. `bgybb`.
+    pub fn letters(&self) -> String {
+        (0..WORD_LENGTH).map(|i| match self[i] {
+            Color::Green => 'g',
+            Color::Yellow => 'y',
+            Color::Black => 'b',
+        }).collect()
     }
 
-    pub fn read() -> Pattern {
-        let mut line = String::new();
-        io::stdin().read_line(&mut line).expect("Read failed");
-        Pattern::from_string(&line)
+    /// Reads a pattern from standard input. If the input is not a valid pattern, the
+    /// problem is shown and the user is asked again.
+    ///
+    /// # Errors
+    /// If standard input cannot be read or has ended, see [`input::read_line`].
+    pub fn read() -> Result<Pattern, WordleError> {
+        input::read_parsed(Pattern::try_from_str)
     }
 
     pub const MAX: usize = usize::pow(Color::SIZE as usize, WORD_LENGTH as u32);
 }
 
+impl TryFrom<&str> for Pattern {
+    type Error = PatternParseError;
+
+    fn try_from(line: &str) -> Result<Pattern, PatternParseError> {
+        Pattern::try_from_str(line)
+    }
+}
+
+/// Patterns are serialized in their plain letter form, e.g. `"bgybb"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Pattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.letters())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Pattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Pattern, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Pattern::try_from_str(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Index<usize> for Pattern {
     type Output = Color;
 
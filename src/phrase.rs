@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::io::Read;
+use std::ops::Index;
+use miette::Diagnostic;
+use rayon::prelude::*;
+use thiserror::Error;
+use crate::error::{WordListError, WordleError};
+use crate::game::score_codes;
+use crate::input;
+use crate::pattern::Color;
+use crate::style;
+use crate::word::{decode, encode, MAX_LETTERS};
+
+/// Describes why a string is not a valid [`Phrase`] or [`PhrasePattern`].
+///
+/// # Variants
+/// * `Empty` - The phrase contains no words.
+/// * `Letter` - The phrase contains a character that is neither a letter nor whitespace.
+/// * `Alphabet` - The phrase has a letter outside ASCII, but all words and phrases together
+///   already use [`MAX_LETTERS`] of them.
+/// * `Shape` - A pattern does not have as many colors as the phrase has letters.
+/// * `Color` - A pattern contains a character that is none of `g`, `y` and `b`.
+/// * `NoCandidates` - No phrase in the list has words of the lengths of the puzzle.
+#[derive(Debug, Error, Diagnostic, Clone, PartialEq, Eq)]
+pub enum PhraseError {
+    #[error("the phrase is empty")]
+    Empty,
+    #[error("phrase <{phrase}> contains '{letter}', which is not a letter")]
+    #[diagnostic(help("phrases may only contain letters, separated by spaces"))]
+    Letter { phrase: String, letter: char },
+    #[error("phrase <{phrase}> contains '{letter}', but the phrases already use {MAX_LETTERS} letters besides a to z")]
+    #[diagnostic(help("phrases may use at most {MAX_LETTERS} letters outside ASCII"))]
+    Alphabet { phrase: String, letter: char },
+    #[error("pattern <{pattern}> has {length} colors, but the phrase has {expected} letters")]
+    #[diagnostic(help("write one color per letter; spaces between the words are optional"))]
+    Shape { pattern: String, length: usize, expected: usize },
+    #[error("pattern <{pattern}> contains '{letter}', which is not a color")]
+    #[diagnostic(help("use 'g' for green, 'y' for yellow and 'b' for black"))]
+    Color { pattern: String, letter: char },
+    #[error("no phrase in the list has words of lengths {shape:?}")]
+    #[diagnostic(help("check the word lengths given with --shape, and that the list is a phrase list"))]
+    NoCandidates { shape: Vec<usize> },
+}
+
+/// A phrase of several words, as guessed in Phrazle-style games.
+///
+/// The word boundaries of a puzzle are fixed and known to the player, so guesses always
+/// have the same [`Phrase::shape`] as the solution. Apart from that, a phrase behaves like
+/// one long word: see [`score_phrase`] for how guesses are scored.
+///
+/// # Example
+/// ```
+/// use wordl_rust_bot::phrase::Phrase;
+///
+/// let phrase = Phrase::try_from_str("a piece of cake").unwrap();
+/// assert_eq!(phrase.shape(), &[1, 5, 2, 4]);
+/// assert_eq!(phrase.letter(1), 'p');
+/// assert_eq!(phrase.to_string(), "a piece of cake");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Phrase {
+    /// The codes of the letters, as in [`Word::as_bytes`](crate::Word::as_bytes).
+    letters: Vec<u8>,
+    shape: Vec<usize>,
+}
+
+impl Phrase {
+
+    /// Parses a phrase whose words are separated by whitespace.
+    ///
+    /// # Errors
+    /// If the phrase is empty, contains characters other than letters and whitespace, or a
+    /// letter outside ASCII that there is no code left for.
+    pub fn try_from_str(phrase: &str) -> Result<Phrase, PhraseError> {
+        let phrase = phrase.trim();
+        if let Some(letter) = phrase.chars().find(|c| !c.is_alphabetic() && !c.is_whitespace()) {
+            return Err(PhraseError::Letter { phrase: phrase.to_string(), letter });
+        }
+        let words = phrase.split_whitespace().collect::<Vec<&str>>();
+        if words.is_empty() {
+            return Err(PhraseError::Empty);
+        }
+        let letters = words.iter().flat_map(|w| w.chars())
+            .map(|letter| encode(letter).ok_or_else(|| PhraseError::Alphabet { phrase: phrase.to_string(), letter }))
+            .collect::<Result<Vec<u8>, PhraseError>>()?;
+        Ok(Phrase { letters, shape: words.iter().map(|w| w.chars().count()).collect() })
+    }
+
+    /// The lengths of the words of the phrase.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The number of letters, not counting spaces.
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    /// The letter at position `index`, counting letters only.
+    ///
+    /// # Panics
+    /// If `index` is not less than [`Phrase::len`].
+    pub fn letter(&self, index: usize) -> char {
+        decode(self.letters[index])
+    }
+
+    /// Reads a phrase from standard input. If the input is not a valid phrase, the problem
+    /// is shown and the user is asked again.
+    pub fn read() -> Result<Phrase, WordleError> {
+        input::read_parsed(Phrase::try_from_str)
+    }
+}
+
+impl Index<usize> for Phrase {
+    type Output = u8;
+
+    /// The code of the letter at position `index`, counting letters only, see [`Phrase::letter`]
+    /// for the letter as a `char`.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.letters[index]
+    }
+}
+
+/// Writes `items` split into words of the given `shape`, separated by spaces.
+fn write_segmented<T: Display>(f: &mut Formatter<'_>, items: &[T], shape: &[usize]) -> std::fmt::Result {
+    let mut start = 0;
+    for (i, length) in shape.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        for item in &items[start..start + length] {
+            write!(f, "{}", item)?;
+        }
+        start += length;
+    }
+    Ok(())
+}
+
+impl Display for Phrase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let letters = self.letters.iter().map(|code| decode(*code)).collect::<Vec<char>>();
+        write_segmented(f, &letters, &self.shape)
+    }
+}
+
+impl Debug for Phrase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// The feedback for a guessed [`Phrase`]: one [`Color`] per letter, grouped into one
+/// pattern per word.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PhrasePattern {
+    colors: Vec<Color>,
+    shape: Vec<usize>,
+}
+
+impl PhrasePattern {
+
+    /// Parses a pattern for a phrase of the given `shape`, written like a [`Pattern`] with one
+    /// letter per position, e.g. `y bybbg gb bbyb`. Spaces between the words are optional.
+    ///
+    /// [`Pattern`]: crate::Pattern
+    ///
+    /// # Errors
+    /// If the number of colors does not match the shape, or a character is not a color.
+    pub fn try_from_str(line: &str, shape: &[usize]) -> Result<PhrasePattern, PhraseError> {
+        let line = line.trim();
+        let colors = line.chars().filter(|c| !c.is_whitespace()).map(|c| match c {
+            'b' => Ok(Color::Black),
+            'y' => Ok(Color::Yellow),
+            'g' => Ok(Color::Green),
+            _ => Err(PhraseError::Color { pattern: line.to_string(), letter: c }),
+        }).collect::<Result<Vec<Color>, PhraseError>>()?;
+        let expected = shape.iter().sum();
+        if colors.len() != expected {
+            return Err(PhraseError::Shape { pattern: line.to_string(), length: colors.len(), expected });
+        }
+        Ok(PhrasePattern { colors, shape: shape.to_vec() })
+    }
+
+    /// Whether every letter is green, i.e. the phrase was guessed.
+    pub fn is_solved(&self) -> bool {
+        self.colors.iter().all(|&c| c == Color::Green)
+    }
+
+    /// The pattern without colors, e.g. `y bybbg gb bbyb`.
+    pub fn letters(&self) -> String {
+        let letters = self.colors.iter().map(|c| match c {
+            Color::Green => 'g',
+            Color::Yellow => 'y',
+            Color::Black => 'b',
+        }).collect::<Vec<char>>();
+        let mut text = String::new();
+        let mut start = 0;
+        for length in &self.shape {
+            if start > 0 {
+                text.push(' ');
+            }
+            text.extend(&letters[start..start + length]);
+            start += length;
+        }
+        text
+    }
+
+    /// Reads a pattern for a phrase of the given `shape` from standard input.
+    pub fn read(shape: &[usize]) -> Result<PhrasePattern, WordleError> {
+        input::read_parsed(|line| PhrasePattern::try_from_str(line, shape))
+    }
+}
+
+impl Index<usize> for PhrasePattern {
+    type Output = Color;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.colors[index]
+    }
+}
+
+impl Display for PhrasePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_segmented(f, &self.colors, &self.shape)
+    }
+}
+
+impl Debug for PhrasePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Computes the score of a guessed phrase, following the rules of [`score`] over the phrase
+/// as a whole: a letter that occurs elsewhere in the solution is yellow, no matter whether
+/// in the same word or in another one.
+///
+/// [`score`]: crate::score
+///
+/// # Panics
+/// If guess and solution have different shapes.
+///
+/// # Example
+/// ```
+/// use wordl_rust_bot::phrase::{score_phrase, Phrase};
+///
+/// let guess = Phrase::try_from_str("a piece of cake").unwrap();
+/// let solution = Phrase::try_from_str("i spent an hour").unwrap();
+/// assert_eq!(score_phrase(&guess, &solution).letters(), "y yygbb yb bbbb");
+/// ```
+pub fn score_phrase(guess: &Phrase, solution: &Phrase) -> PhrasePattern {
+    assert_eq!(guess.shape, solution.shape, "guess and solution must have the same shape");
+    let mut colors = vec![Color::Black; guess.len()];
+    score_codes(&guess.letters, &solution.letters, |i, color| colors[i] = color);
+    PhrasePattern { colors, shape: guess.shape.clone() }
+}
+
+/// Reads a list of phrases with one phrase per line. Blank lines are ignored, and lines that
+/// are not valid phrases (e.g. because they contain punctuation) are skipped and added to
+/// `skipped`.
+///
+/// # Errors
+/// If the list cannot be read, or if it does not contain a single valid phrase.
+pub fn read_phrases<R: Read>(name: &str, mut reader: R,
+                             skipped: &mut Vec<PhraseError>) -> Result<Vec<Phrase>, WordListError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)
+        .map_err(|source| WordListError::Io { name: name.to_string(), source })?;
+    let mut phrases = Vec::new();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        match Phrase::try_from_str(line) {
+            Ok(phrase) => phrases.push(phrase),
+            Err(e) => skipped.push(e),
+        }
+    }
+    if phrases.is_empty() {
+        return Err(WordListError::Empty { name: name.to_string() });
+    }
+    Ok(phrases)
+}
+
+/// Helps with a Phrazle-style game. Only phrases of the puzzle's shape are considered, and
+/// guesses are suggested from the remaining candidates by their entropy, as in [`HelpGame`].
+///
+/// [`HelpGame`]: crate::game::HelpGame
+pub struct PhraseGame<'a> {
+    shape: Vec<usize>,
+    solution_space: Vec<&'a Phrase>,
+    round: u8,
+    solved: bool,
+}
+
+impl PhraseGame<'_> {
+    pub const MAX_ROUNDS: u8 = 6;
+
+    /// Starts a game for a puzzle whose words have the lengths `shape`, with the `phrases` of
+    /// that shape as candidates.
+    ///
+    /// # Errors
+    /// [`PhraseError::NoCandidates`] if no phrase has the shape.
+    pub fn new<'a>(phrases: &'a [Phrase], shape: &[usize]) -> Result<PhraseGame<'a>, PhraseError> {
+        let solution_space = phrases.iter().filter(|p| p.shape() == shape).collect::<Vec<&Phrase>>();
+        if solution_space.is_empty() {
+            return Err(PhraseError::NoCandidates { shape: shape.to_vec() });
+        }
+        Ok(PhraseGame { shape: shape.to_vec(), solution_space, round: 0, solved: false })
+    }
+
+    /// Whether the phrase was found.
+    pub fn solved(&self) -> bool {
+        self.solved
+    }
+
+    /// The number of phrases that still fit the feedback, `0` if no phrase in the list does.
+    pub fn remaining(&self) -> usize {
+        self.solution_space.len()
+    }
+
+    fn entropy(&self, guess: &Phrase) -> f64 {
+        let mut counts: HashMap<PhrasePattern, u32> = HashMap::new();
+        for solution in &self.solution_space {
+            *counts.entry(score_phrase(guess, solution)).or_insert(0) += 1;
+        }
+        let total = self.solution_space.len() as f64;
+        -counts.values().map(|&count| {
+            let p = count as f64 / total;
+            p * p.log2()
+        }).sum::<f64>()
+    }
+
+    fn evaluate(&self) -> Vec<(&Phrase, f64)> {
+        let mut evaluation = self.solution_space.par_iter()
+            .map(|p| (*p, self.entropy(p)))
+            .collect::<Vec<_>>();
+        evaluation.sort_by(|a, b| f64::total_cmp(&b.1, &a.1));
+        evaluation
+    }
+
+    fn read(&self) -> Result<(Phrase, PhrasePattern), WordleError> {
+        let guess = loop {
+            input::prompt("Enter guessed phrase:")?;
+            let guess = Phrase::read()?;
+            if guess.shape() == self.shape {
+                break guess;
+            }
+            println!("The phrase must have words of lengths {:?}.", self.shape);
+        };
+        input::prompt("Enter resulting pattern:")?;
+        let pattern = PhrasePattern::read(&self.shape)?;
+        println!("You have guessed {} with result {}", style::bold(&guess), style::bold(&pattern));
+        Ok((guess, pattern))
+    }
+
+    fn round(&mut self) -> Result<PhrasePattern, WordleError> {
+        println!("{} {}", style::bold(format_args!("Solution Space ({} entries):", self.solution_space.len())),
+                 self.solution_space.iter().take(5).map(|p| p.to_string()).collect::<Vec<_>>().join(", "));
+        let suggestions = self.evaluate().iter().take(5)
+            .map(|(p, entropy)| format!("{} ({:.3})", p, entropy))
+            .collect::<Vec<String>>();
+        println!("{} {}", style::bold("Suggested Guesses:"), suggestions.join(", "));
+        let (guess, pattern) = self.read()?;
+        self.solution_space.retain(|solution| score_phrase(&guess, solution) == pattern);
+        self.round += 1;
+        Ok(pattern)
+    }
+
+    pub fn run_game(&mut self) -> Result<(), WordleError> {
+        loop {
+            let pattern = match self.round() {
+                Ok(pattern) => pattern,
+                Err(error) => return input::end_game(error, || {
+                    println!();
+                    println!("{}   {} phrases were still possible.", style::bold("Input ended."),
+                             self.solution_space.len());
+                }),
+            };
+            if pattern.is_solved() {
+                self.solved = true;
+                println!("{}", style::bold("Success!"));
+                break;
+            } else if self.solution_space.is_empty() {
+                println!("{}   No fitting phrase in the list!", style::bold("Failure!"));
+                break;
+            } else if self.round >= Self::MAX_ROUNDS {
+                println!("{}   Rounds exhausted!", style::bold("Failure!"));
+                break;
+            }
+        }
+        println!("Score {}", self.round);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn phrase(phrase: &str) -> Phrase {
+        Phrase::try_from_str(phrase).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Phrase::try_from_str("  "), Err(PhraseError::Empty));
+        assert!(matches!(Phrase::try_from_str("it's"), Err(PhraseError::Letter { letter: '\'', .. })));
+        let pattern = PhrasePattern::try_from_str("gybgg bb", &[3, 4]).unwrap();
+        assert_eq!(pattern.letters(), "gyb ggbb");
+        assert!(PhrasePattern::try_from_str("gyb", &[3, 4]).is_err());
+    }
+
+    #[test]
+    fn test_score_phrase() {
+        assert!(score_phrase(&phrase("of cake"), &phrase("of cake")).is_solved());
+        assert_eq!(score_phrase(&phrase("ab cd"), &phrase("cd ab")).letters(), "yy yy");
+        assert_eq!(score_phrase(&phrase("aa bb"), &phrase("ba cc")).letters(), "bg yb");
+        assert_eq!(score_phrase(&phrase("für mäh"), &phrase("mäh für")).letters(), "yyy yyy");
+    }
+
+    #[test]
+    fn test_phrase_game() {
+        let phrases = [phrase("of cake"), phrase("in time"), phrase("a piece")];
+        assert_eq!(PhraseGame::new(&phrases, &[2, 4]).unwrap().remaining(), 2);
+        assert_eq!(PhraseGame::new(&phrases, &[3]).err(), Some(PhraseError::NoCandidates { shape: vec![3] }));
+    }
+}
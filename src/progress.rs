@@ -0,0 +1,95 @@
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// A progress bar for long runs over many games, with the rate and the estimated time left,
+/// e.g. `[##########----------] 1160/2315  48.2 games/s  ETA 0:24`.
+///
+/// The bar is drawn on standard error, and only if both standard output and standard error
+/// are terminals, so that it never ends up in redirected output. Output printed while the
+/// bar is shown should be preceded by [`Progress::clear`]; the next [`Progress::advance`]
+/// draws the bar again below it.
+///
+/// # Fields
+/// * `total` - The number of games of the whole run.
+/// * `done` - The number of games finished so far.
+/// * `start` - When the run started, for the rate and the time left.
+/// * `visible` - Whether the bar is drawn at all.
+pub struct Progress {
+    total: usize,
+    done: usize,
+    start: Instant,
+    visible: bool,
+}
+
+impl Progress {
+    /// The width of the bar itself, in characters.
+    const WIDTH: usize = 20;
+
+    /// A progress bar for `total` games, shown if the output goes to a terminal.
+    pub fn new(total: usize) -> Progress {
+        Progress {
+            total,
+            done: 0,
+            start: Instant::now(),
+            visible: io::stdout().is_terminal() && io::stderr().is_terminal(),
+        }
+    }
+
+    /// A progress bar that is never drawn, e.g. with `--quiet`.
+    pub fn hidden(total: usize) -> Progress {
+        Progress { visible: false, ..Progress::new(total) }
+    }
+
+    /// Counts `count` more games as finished and draws the bar.
+    pub fn advance(&mut self, count: usize) {
+        self.done = (self.done + count).min(self.total);
+        if self.visible {
+            eprint!("\r\x1b[K{}", self.render(self.start.elapsed()));
+            let _ = io::stderr().flush();
+        }
+    }
+
+    /// Removes the bar from the terminal, so that other output can be printed in its place.
+    pub fn clear(&self) {
+        if self.visible && self.done > 0 {
+            eprint!("\r\x1b[K");
+            let _ = io::stderr().flush();
+        }
+    }
+
+    /// The line of the bar after `elapsed` time.
+    fn render(&self, elapsed: Duration) -> String {
+        let filled = (self.done * Self::WIDTH).checked_div(self.total).unwrap_or(Self::WIDTH);
+        let rate = self.done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        let eta = match rate > 0.0 {
+            true => format_duration((self.total - self.done) as f64 / rate),
+            false => "?".to_string(),
+        };
+        format!("[{}{}] {}/{}  {:.1} games/s  ETA {}", "#".repeat(filled), "-".repeat(Self::WIDTH - filled),
+                self.done, self.total, rate, eta)
+    }
+}
+
+/// Formats `seconds` as minutes and seconds, e.g. `1:05`, with hours only if needed.
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::hidden(100);
+        progress.advance(25);
+        assert_eq!(progress.render(Duration::from_secs(5)), "[#####---------------] 25/100  5.0 games/s  ETA 0:15");
+        progress.advance(100);
+        assert_eq!(progress.render(Duration::from_secs(20)), "[####################] 100/100  5.0 games/s  ETA 0:00");
+        assert_eq!(format_duration(3725.0), "1:02:05");
+    }
+}
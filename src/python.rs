@@ -0,0 +1,257 @@
+use std::fmt::Display;
+use std::sync::Arc;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use crate::batch;
+use crate::ffi::WordLists;
+use crate::game::{self, Solver, Strategy};
+use crate::matrix::PatternMatrix;
+use crate::pattern::Pattern;
+use crate::word::Word;
+
+fn value_error(error: impl Display) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// A word, as the functions of the module accept it: a `Word`, or a string that is parsed
+/// ignoring case.
+#[derive(FromPyObject)]
+enum WordArg {
+    Word(PyWord),
+    Text(String),
+}
+
+impl WordArg {
+    fn word(self) -> PyResult<Word> {
+        match self {
+            WordArg::Word(word) => Ok(word.0),
+            WordArg::Text(text) => Word::try_from_str_ignore_case(&text).map_err(value_error),
+        }
+    }
+}
+
+/// A pattern, as the functions of the module accept it: a `Pattern`, or a string such as
+/// `"bygby"`.
+#[derive(FromPyObject)]
+enum PatternArg {
+    Pattern(PyPattern),
+    Text(String),
+}
+
+impl PatternArg {
+    fn pattern(self) -> PyResult<Pattern> {
+        match self {
+            PatternArg::Pattern(pattern) => Ok(pattern.0),
+            PatternArg::Text(text) => Pattern::try_from_str(&text).map_err(value_error),
+        }
+    }
+}
+
+fn word_list(words: Vec<WordArg>) -> PyResult<Vec<Word>> {
+    let words = words.into_iter().map(WordArg::word).collect::<PyResult<Vec<Word>>>()?;
+    match words.is_empty() {
+        true => Err(PyValueError::new_err("the word list is empty")),
+        false => Ok(words),
+    }
+}
+
+/// A five-letter word, e.g. `Word("crane")`.
+#[pyclass(name = "Word", module = "wordl_rust_bot", frozen, eq, ord, hash, from_py_object)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyWord(Word);
+
+#[pymethods]
+impl PyWord {
+    #[new]
+    fn new(word: &str) -> PyResult<PyWord> {
+        Word::try_from_str_ignore_case(word).map(PyWord).map_err(value_error)
+    }
+
+    /// The pattern this word gets as a guess for `solution`.
+    fn score(&self, solution: WordArg) -> PyResult<PyPattern> {
+        Ok(PyPattern(game::score(&self.0, &solution.word()?)))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Word('{}')", self.0)
+    }
+}
+
+/// The colors a guess gets, e.g. `Pattern("bygby")` for black, yellow, green, black, yellow.
+/// `int(pattern)` numbers the 243 patterns from 0, e.g. to count them in an array.
+#[pyclass(name = "Pattern", module = "wordl_rust_bot", frozen, eq, hash, from_py_object)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PyPattern(Pattern);
+
+#[pymethods]
+impl PyPattern {
+    #[new]
+    fn new(pattern: &str) -> PyResult<PyPattern> {
+        Pattern::try_from_str(pattern).map(PyPattern).map_err(value_error)
+    }
+
+    /// Whether every letter is green.
+    #[getter]
+    fn solved(&self) -> bool {
+        self.0 == Pattern::all_green()
+    }
+
+    fn __int__(&self) -> usize {
+        self.0.index()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.letters()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Pattern('{}')", self.0.letters())
+    }
+}
+
+/// The solver for one game at a time: `feedback` enters the pattern of a guess and
+/// `suggestions` ranks the next guesses by entropy. The solver owns copies of its word lists.
+#[pyclass(name = "Solver", module = "wordl_rust_bot", unsendable)]
+pub struct PySolver {
+    // Declared before the lists, so that both solvers are dropped before the lists they borrow.
+    solver: Solver<'static>,
+    initial: Solver<'static>,
+    _lists: WordLists,
+}
+
+#[pymethods]
+impl PySolver {
+    /// A solver that guesses from `words` and expects the solution among `solutions`, or
+    /// among `words` if there are none. In hard mode, every guess fits all feedback so far.
+    #[new]
+    #[pyo3(signature = (words, solutions = None, hard_mode = false))]
+    fn new(words: Vec<WordArg>, solutions: Option<Vec<WordArg>>, hard_mode: bool) -> PyResult<PySolver> {
+        let words = word_list(words)?;
+        let solutions = match solutions {
+            Some(solutions) => word_list(solutions)?,
+            None => words.clone(),
+        };
+        let lists = WordLists::new(words, solutions);
+        // SAFETY: the solvers are dropped before the lists, and never handed out.
+        let mut solver = unsafe { lists.solver() };
+        if hard_mode {
+            solver = solver.hard_mode();
+        }
+        Ok(PySolver { initial: solver.clone(), solver, _lists: lists })
+    }
+
+    /// Enters the feedback `pattern` the game gave for `guess`, and returns the number of
+    /// solutions that are still possible.
+    fn feedback(&mut self, guess: WordArg, pattern: PatternArg) -> PyResult<usize> {
+        self.solver.feedback(guess.word()?, pattern.pattern()?);
+        Ok(self.solver.solution_space().len())
+    }
+
+    /// The `count` guesses with the highest entropy as `(word, entropy)` pairs, best first.
+    #[pyo3(signature = (count = 5))]
+    fn suggestions(&self, count: usize) -> Vec<(PyWord, f64)> {
+        self.solver.suggestions(count).iter().map(|e| (PyWord(*e.word), e.entropy)).collect()
+    }
+
+    /// The guess with the highest entropy, or the solution once it is known.
+    fn best_guess(&self) -> PyResult<PyWord> {
+        match self.solver.solution_space().is_empty() {
+            true => Err(PyValueError::new_err("no word fits the feedback so far")),
+            false => Ok(PyWord(self.solver.best_guess())),
+        }
+    }
+
+    /// The entropy of `guess` in bits, also for words that are not in the word list.
+    fn entropy(&self, guess: WordArg) -> PyResult<f64> {
+        Ok(self.solver.entropy(&guess.word()?))
+    }
+
+    /// The solutions that fit all feedback so far.
+    #[getter]
+    fn solution_space(&self) -> Vec<PyWord> {
+        self.solver.solution_space().iter().map(|word| PyWord(**word)).collect()
+    }
+
+    /// The guesses entered so far with their patterns.
+    #[getter]
+    fn history(&self) -> Vec<(PyWord, PyPattern)> {
+        self.solver.state().history.iter().map(|(guess, pattern)| (PyWord(*guess), PyPattern(*pattern))).collect()
+    }
+
+    /// Starts a new game.
+    fn reset(&mut self) {
+        self.solver = self.initial.clone();
+    }
+}
+
+/// The pattern `guess` gets for `solution`.
+#[pyfunction]
+fn score(guess: WordArg, solution: WordArg) -> PyResult<PyPattern> {
+    Ok(PyPattern(game::score(&guess.word()?, &solution.word()?)))
+}
+
+/// Plays a game against every word of `solutions`, or of `words` if there are none, on all
+/// cores and without holding the GIL. The games start with `first_guess`, by default the
+/// best guess over `words`, and pick the following guesses with `strategy`, e.g. `"entropy"`
+/// or `"minimax"`.
+///
+/// Returns a `(solution, guesses, solved)` tuple for every game.
+#[pyfunction]
+#[pyo3(signature = (words, solutions = None, first_guess = None, strategy = "entropy", seed = None))]
+fn simulate(py: Python<'_>, words: Vec<WordArg>, solutions: Option<Vec<WordArg>>, first_guess: Option<WordArg>,
+            strategy: &str, seed: Option<u64>) -> PyResult<Vec<(PyWord, Vec<PyWord>, bool)>> {
+    let words = word_list(words)?;
+    let solutions = match solutions {
+        Some(solutions) => word_list(solutions)?,
+        None => words.clone(),
+    };
+    let strategy = strategy.parse::<Strategy>().map_err(PyValueError::new_err)?;
+    let first_guess = first_guess.map(WordArg::word).transpose()?;
+    let records = py.detach(|| {
+        let first_guess = first_guess.unwrap_or_else(|| Solver::new(&words).best_guess());
+        let matrix = Arc::new(PatternMatrix::new(&words, &words));
+        batch::simulate(&matrix, &words, &solutions, first_guess, strategy, seed)
+    });
+    Ok(records.into_iter().map(|record| {
+        let guesses = record.guesses.into_iter().map(PyWord).collect();
+        (PyWord(record.solution), guesses, record.solved)
+    }).collect())
+}
+
+/// The Python module `wordl_rust_bot`, built with maturin, see `pyproject.toml`.
+#[pymodule]
+fn wordl_rust_bot(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyWord>()?;
+    module.add_class::<PyPattern>()?;
+    module.add_class::<PySolver>()?;
+    module.add_function(wrap_pyfunction!(score, module)?)?;
+    module.add_function(wrap_pyfunction!(simulate, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_python() {
+        let words = sample_words().into_iter().map(|word| WordArg::Word(PyWord(word))).collect();
+        let mut solver = PySolver::new(words, None, false).unwrap();
+        let crane = PyWord::new("CRANE").unwrap();
+        assert_eq!(crane.__repr__(), "Word('crane')");
+        let pattern = crane.score(WordArg::Text("gears".to_string())).unwrap();
+        assert_eq!((pattern.__str__(), pattern.solved()), ("bygby".to_string(), false));
+        assert_eq!(solver.feedback(WordArg::Word(crane), PatternArg::Pattern(pattern)).unwrap(), 3);
+        assert!(solver.feedback(WordArg::Text("crane".to_string()), PatternArg::Text("xx".to_string())).is_err());
+        assert_eq!(solver.history().len(), 1);
+        assert_eq!(solver.suggestions(2).len(), 2);
+        solver.reset();
+        assert_eq!(solver.solution_space().len(), 5);
+        assert!(PySolver::new(Vec::new(), None, false).is_err());
+    }
+}
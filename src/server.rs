@@ -0,0 +1,168 @@
+use std::io::{self, BufRead, Write};
+use crate::game::{Eval, Solver};
+use crate::pattern::Pattern;
+use crate::word::Word;
+
+/// A game held open for an external program, e.g. a bot or a GUI, so that it can ask for
+/// suggestions again and again without the solver reading the word list every time.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::server::Session;
+/// use wordl_rust_bot::{Pattern, Solver, Word};
+///
+/// let words = ["tears", "bears", "gears", "crane"].map(Word::from_str);
+/// let mut session = Session::new(Solver::new(&words));
+/// assert_eq!(session.feedback(Word::from_str("crane"), Pattern::from_string("bygby")), 3);
+/// session.reset();
+/// assert_eq!(session.solver().solution_space().len(), 4);
+/// ```
+#[derive(Clone)]
+pub struct Session<'a> {
+    initial: Solver<'a>,
+    solver: Solver<'a>,
+}
+
+impl<'a> Session<'a> {
+    /// Starts a session in which every game begins like `solver`.
+    pub fn new(solver: Solver<'a>) -> Session<'a> {
+        Session { initial: solver.clone(), solver }
+    }
+
+    /// Enters the feedback `pattern` for `guess`.
+    ///
+    /// # Returns
+    /// The number of solutions that are still possible.
+    pub fn feedback(&mut self, guess: Word, pattern: Pattern) -> usize {
+        self.solver.feedback(guess, pattern);
+        self.solver.solution_space().len()
+    }
+
+    /// The `count` best guesses, best first.
+    pub fn suggest(&self, count: usize) -> Vec<Eval<'a>> {
+        self.solver.suggestions(count)
+    }
+
+    /// Starts a new game.
+    pub fn reset(&mut self) {
+        self.solver = self.initial.clone();
+    }
+
+    pub fn solver(&self) -> &Solver<'a> {
+        &self.solver
+    }
+
+    /// Whether the solution was entered in the current game.
+    pub fn solved(&self) -> bool {
+        self.solver.state().history.last().is_some_and(|(_, pattern)| *pattern == Pattern::all_green())
+    }
+}
+
+/// A request of the line protocol of [`serve_lines`].
+///
+/// # Variants
+/// * `Guess` - `GUESS crane bbygb`: enters a guess and the pattern it got.
+/// * `Suggest` - `SUGGEST 5`: asks for the best guesses, by default 5.
+/// * `Reset` - `RESET`: starts a new game.
+/// * `Quit` - `QUIT`: ends the session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Guess(Word, Pattern),
+    Suggest(usize),
+    Reset,
+    Quit,
+}
+
+impl Command {
+    /// Parses a line of the protocol. Commands and patterns are case-insensitive.
+    ///
+    /// # Errors
+    /// A message saying what is wrong with the line.
+    pub fn parse(line: &str) -> Result<Command, String> {
+        let parts = line.split_whitespace().collect::<Vec<&str>>();
+        let Some(command) = parts.first() else {
+            return Err("empty command".to_string());
+        };
+        match (command.to_uppercase().as_str(), &parts[1..]) {
+            ("GUESS", [guess, pattern]) => {
+                let guess = Word::try_from_str_ignore_case(guess).map_err(|e| e.to_string())?;
+                let pattern = Pattern::try_from_str(pattern).map_err(|e| e.to_string())?;
+                Ok(Command::Guess(guess, pattern))
+            }
+            ("GUESS", _) => Err("usage: GUESS <word> <pattern>".to_string()),
+            ("SUGGEST", []) => Ok(Command::Suggest(5)),
+            ("SUGGEST", [count]) => count.parse().map(Command::Suggest)
+                .map_err(|_| format!("<{}> is not a number of suggestions", count)),
+            ("SUGGEST", _) => Err("usage: SUGGEST [count]".to_string()),
+            ("RESET", []) => Ok(Command::Reset),
+            ("QUIT", []) => Ok(Command::Quit),
+            (command, _) => Err(format!("unknown command {}, expected GUESS, SUGGEST, RESET or QUIT", command)),
+        }
+    }
+}
+
+/// Answers the requests of `session` read from `input`, one per line, until `QUIT` or the end
+/// of the input. Every request gets exactly one line in response:
+///
+/// * `GUESS` and `RESET` are answered with `OK` and the number of possible solutions.
+/// * `SUGGEST` is answered with `OK` and the suggested words, separated by spaces.
+/// * A malformed request is answered with `ERR` and a message.
+///
+/// # Example
+///
+/// ```text
+/// > GUESS tares BYBBB
+/// < OK 731
+/// > SUGGEST 3
+/// < OK colin liana aloin
+/// ```
+///
+/// # Errors
+/// If reading the input or writing the output fails.
+pub fn serve_lines<R: BufRead, W: Write>(session: &mut Session, input: R, mut output: W) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Command::parse(&line) {
+            Ok(Command::Guess(guess, pattern)) => writeln!(output, "OK {}", session.feedback(guess, pattern))?,
+            Ok(Command::Suggest(count)) => {
+                let words = session.suggest(count).iter().map(|e| e.word.to_string()).collect::<Vec<String>>();
+                writeln!(output, "OK {}", words.join(" "))?
+            }
+            Ok(Command::Reset) => {
+                session.reset();
+                writeln!(output, "OK {}", session.solver().solution_space().len())?
+            }
+            Ok(Command::Quit) => break,
+            Err(message) => writeln!(output, "ERR {}", message)?,
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_serve_lines() {
+        let words = sample_words();
+        let mut session = Session::new(Solver::new(&words));
+        let input = "SUGGEST 1\nguess crane BYGBY\nGUESS crane\n\nSUGGEST x\nRESET\nQUIT\nSUGGEST\n";
+        let mut output = Vec::new();
+        serve_lines(&mut session, input.as_bytes(), &mut output).unwrap();
+        let lines = String::from_utf8(output).unwrap();
+        let lines = lines.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("OK ") && lines[0].split(' ').count() == 2);
+        assert_eq!(lines[1], "OK 3");
+        assert!(lines[2].starts_with("ERR usage"));
+        assert!(lines[3].starts_with("ERR "));
+        assert_eq!(lines[4], "OK 5");
+    }
+}
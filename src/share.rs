@@ -0,0 +1,228 @@
+use miette::Diagnostic;
+use thiserror::Error;
+use crate::pattern::{Color, Pattern};
+use crate::style::Palette;
+use crate::word::WORD_LENGTH;
+
+/// Describes why a pasted share grid cannot be read, see [`parse_grid`].
+///
+/// # Variants
+/// * `Empty` - The text contains no row of squares.
+/// * `Row` - The row on `line` (counting from 1) does not have one square per letter.
+/// * `TooManyRows` - The grid has more rows than a single board can have.
+#[derive(Debug, Error, Diagnostic, Clone, PartialEq, Eq)]
+pub enum GridError {
+    #[error("the share grid contains no row of colored squares")]
+    #[diagnostic(help("paste the grid as shared by the game, e.g. ⬛🟨⬛⬛⬛ on each line"))]
+    Empty,
+    #[error("row on line {line} of the share grid has {length} squares instead of {WORD_LENGTH}")]
+    #[diagnostic(help("paste a single board, with one row per guess"))]
+    Row { line: usize, length: usize },
+    #[error("the share grid has {rows} rows, more than the {max_rounds} guesses of a game")]
+    #[diagnostic(help("paste a single board, with one row per guess"))]
+    TooManyRows { rows: usize, max_rounds: u8 },
+}
+
+/// The outcome of a single board, as far as it is needed for sharing: the
+/// sequence of feedback patterns and whether the board was solved.
+pub struct Board {
+    pub patterns: Vec<Pattern>,
+    pub solved: bool,
+}
+
+/// A spoiler-free summary of a finished game, in the emoji format players
+/// post in chats.
+///
+/// Single-board games are rendered like the New York Times Wordle
+/// (`Wordle 4/6*` followed by the grid), multi-board games like Quordle and
+/// Octordle (a block of keycap scores, then the grids side by side in pairs).
+///
+/// # Fields
+///
+/// * `name` - The header, e.g. `Wordle (bot)` or `Daily Quordle`.
+/// * `boards` - The boards that were played, in order.
+/// * `max_rounds` - The number of guesses that were available.
+/// * `hard_mode` - Whether to mark the result with an asterisk.
+///
+/// # Example
+///
+/// ```text
+/// Wordle (bot) 3/6*
+///
+/// ⬛🟨⬛⬛⬛
+/// 🟩⬛🟩🟨⬛
+/// 🟩🟩🟩🟩🟩
+/// ```
+pub struct Share {
+    name: String,
+    boards: Vec<Board>,
+    max_rounds: u8,
+    hard_mode: bool,
+}
+
+impl Share {
+    pub fn new(name: &str, boards: Vec<Board>, max_rounds: u8, hard_mode: bool) -> Share {
+        Share { name: name.to_string(), boards, max_rounds, hard_mode }
+    }
+
+    pub fn render(&self) -> String {
+        if self.boards.len() == 1 {
+            self.render_single(&self.boards[0])
+        } else {
+            self.render_multi()
+        }
+    }
+
+    fn render_single(&self, board: &Board) -> String {
+        let mut text = format!("{} {}/{}{}\n",
+                               self.name, Self::score(board), self.max_rounds,
+                               if self.hard_mode { "*" } else { "" });
+        for pattern in &board.patterns {
+            text.push('\n');
+            text.push_str(&Self::row(pattern));
+        }
+        text
+    }
+
+    /// Renders the Quordle/Octordle layout. Boards are arranged in pairs: first
+    /// the scores as keycap emojis (🟥 for a failed board), then the grids.
+    /// Rows of a board that was solved earlier than its neighbour are padded
+    /// with white squares.
+    fn render_multi(&self) -> String {
+        let mut text = self.name.clone();
+        if self.hard_mode {
+            text.push('*');
+        }
+        text.push('\n');
+        for pair in self.boards.chunks(2) {
+            for board in pair {
+                text.push_str(&if board.solved {
+                    Self::keycap(board.patterns.len())
+                } else {
+                    "🟥".to_string()
+                });
+            }
+            text.push('\n');
+        }
+        for pair in self.boards.chunks(2) {
+            text.push('\n');
+            let rows = pair.iter().map(|b| b.patterns.len()).max().unwrap_or(0);
+            for i in 0..rows {
+                let row = pair.iter().map(|board| match board.patterns.get(i) {
+                    Some(pattern) => Self::row(pattern),
+                    None => "⬜".repeat(WORD_LENGTH),
+                }).collect::<Vec<String>>();
+                text.push_str(&row.join(" "));
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    fn score(board: &Board) -> String {
+        if board.solved {
+            board.patterns.len().to_string()
+        } else {
+            "X".to_string()
+        }
+    }
+
+    /// The colors of `pattern` as a row of square emojis, e.g. `⬛🟨⬛⬛🟩`, or `⬛🟦⬛⬛🟧` in
+    /// the [`Palette::HighContrast`] palette.
+    pub(crate) fn row(pattern: &Pattern) -> String {
+        (0..WORD_LENGTH).map(|i| Palette::current().square(pattern[i])).collect()
+    }
+
+    fn keycap(n: usize) -> String {
+        n.to_string().chars().map(|digit| format!("{}\u{fe0f}\u{20e3}", digit)).collect()
+    }
+}
+
+/// Reads the patterns of a single board from a share grid as posted in chats, the inverse of
+/// [`Share::render`]. Lines that contain anything besides squares, such as the header
+/// `Wordle 1,234 4/6*`, are skipped. Besides the usual colors, the squares of the
+/// high-contrast mode are understood: 🟧 for green and 🟦 for yellow.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::share::parse_grid;
+/// use wordl_rust_bot::Pattern;
+///
+/// let patterns = parse_grid("Wordle 1,234 2/6\n\n⬛🟨⬜⬛⬛\n🟩🟩🟩🟩🟩\n", 6).unwrap();
+/// assert_eq!(patterns, [Pattern::from_string("bybbb"), Pattern::all_green()]);
+/// ```
+///
+/// # Errors
+/// A [`GridError`] if a row does not have one square per letter, or there are no rows or
+/// more than `max_rounds`.
+pub fn parse_grid(text: &str, max_rounds: u8) -> Result<Vec<Pattern>, GridError> {
+    let square = |c: char| match c {
+        '🟩' | '🟧' => Some(Color::Green),
+        '🟨' | '🟦' => Some(Color::Yellow),
+        '⬛' | '⬜' => Some(Color::Black),
+        _ => None,
+    };
+    let mut patterns = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let chars = line.chars().filter(|c| !c.is_whitespace() && *c != '\u{fe0f}').collect::<Vec<char>>();
+        let Some(colors) = chars.iter().map(|c| square(*c)).collect::<Option<Vec<Color>>>() else {
+            continue;
+        };
+        if colors.is_empty() {
+            continue;
+        }
+        if colors.len() != WORD_LENGTH {
+            return Err(GridError::Row { line: i + 1, length: colors.len() });
+        }
+        let mut pattern = Pattern::all_black();
+        for (position, color) in colors.into_iter().enumerate() {
+            pattern.set(position, color);
+        }
+        patterns.push(pattern);
+    }
+    match patterns.len() {
+        0 => Err(GridError::Empty),
+        rows if rows > max_rounds as usize => Err(GridError::TooManyRows { rows, max_rounds }),
+        _ => Ok(patterns),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn board(patterns: &[&str], solved: bool) -> Board {
+        Board { patterns: patterns.iter().map(|p| Pattern::from_string(p)).collect(), solved }
+    }
+
+    #[test]
+    fn test_render_single() {
+        let share = Share::new("Wordle", vec![board(&["bybbb", "ggggg"], true)], 6, true);
+        assert_eq!(share.render(), "Wordle 2/6*\n\n⬛🟨⬛⬛⬛\n🟩🟩🟩🟩🟩");
+        let share = Share::new("Wordle", vec![board(&["bybbb"], false)], 6, false);
+        assert_eq!(share.render(), "Wordle X/6\n\n⬛🟨⬛⬛⬛");
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let share = Share::new("Wordle", vec![board(&["bybbb", "gbgyb", "ggggg"], true)], 6, true);
+        let patterns = parse_grid(&share.render(), 6).unwrap();
+        assert_eq!(patterns, board(&["bybbb", "gbgyb", "ggggg"], true).patterns);
+        assert_eq!(parse_grid("🟧🟦⬜⬜⬜", 6).unwrap(), [Pattern::from_string("gybbb")]);
+        assert_eq!(parse_grid("Wordle\n🟩🟩⬛", 6), Err(GridError::Row { line: 2, length: 3 }));
+        assert_eq!(parse_grid("Wordle 1/6", 6), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn test_render_multi() {
+        let share = Share::new("Quordle", vec![
+            board(&["ggggg"], true),
+            board(&["bbbbb", "ggggg"], true),
+            board(&["bbbbb"], false),
+        ], 9, false);
+        assert_eq!(share.render(), "Quordle\n1\u{fe0f}\u{20e3}2\u{fe0f}\u{20e3}\n🟥\n\
+            \n🟩🟩🟩🟩🟩 ⬛⬛⬛⬛⬛\n⬜⬜⬜⬜⬜ 🟩🟩🟩🟩🟩\n\
+            \n⬛⬛⬛⬛⬛\n");
+    }
+}
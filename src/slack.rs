@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sha2::{Digest, Sha256};
+use crate::game::Solver;
+use crate::share::Share;
+
+/// How old a request may be, in seconds, before it is rejected as a possible replay.
+const MAX_AGE: u64 = 5 * 60;
+
+/// A request of a Slack slash command like `/wordle crane bbygb`, see
+/// [`crate::http::HttpServer::slack`].
+///
+/// # Variants
+/// * `Suggest` - `/wordle`: shows the board and the best guesses.
+/// * `Guess` - `/wordle crane bbygb`: enters a guess and its pattern, then suggests.
+/// * `Reset` - `/wordle reset`: starts a new game.
+/// * `Help` - `/wordle help`, or anything else: explains the command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SlackCommand {
+    Suggest,
+    Guess(String, String),
+    Reset,
+    Help,
+}
+
+impl SlackCommand {
+    pub fn parse(text: &str) -> SlackCommand {
+        let parts = text.split_whitespace().collect::<Vec<&str>>();
+        match parts.as_slice() {
+            [] | ["suggest"] => SlackCommand::Suggest,
+            ["reset"] | ["new"] => SlackCommand::Reset,
+            [guess, pattern] => SlackCommand::Guess(guess.to_lowercase(), pattern.to_lowercase()),
+            _ => SlackCommand::Help,
+        }
+    }
+}
+
+/// The usage of the slash command, as shown by [`SlackCommand::Help`].
+pub const USAGE: &str = "`/wordle GUESS PATTERN` enters a guess and its colors (g = green, y = yellow, \
+                         b = black), e.g. `/wordle crane bbygb`. `/wordle` shows suggestions, \
+                         `/wordle reset` starts a new game.";
+
+/// Decodes a body of the form `application/x-www-form-urlencoded`, as Slack sends it.
+pub fn parse_form(body: &str) -> HashMap<String, String> {
+    fn decode(text: &str) -> String {
+        let bytes = text.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let escaped = bytes.get(i + 1..i + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+            match (bytes[i], escaped) {
+                (b'+', _) => decoded.push(b' '),
+                (b'%', Some(byte)) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                (byte, _) => decoded.push(byte),
+            }
+            i += 1;
+        }
+        String::from_utf8_lossy(&decoded).into_owned()
+    }
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (decode(key), decode(value)))
+        .collect()
+}
+
+/// Checks that a request was sent by Slack: its `X-Slack-Signature` must be the HMAC-SHA256
+/// of `v0:TIMESTAMP:BODY` under the app's signing secret, and its `X-Slack-Request-Timestamp`
+/// must be at most five minutes old.
+pub fn verify_signature(secret: &str, timestamp: &str, body: &str, signature: &str) -> bool {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let Ok(sent) = timestamp.parse::<u64>() else {
+        return false;
+    };
+    if now.abs_diff(sent) > MAX_AGE {
+        return false;
+    }
+    let mac = hmac_sha256(secret.as_bytes(), format!("v0:{}:{}", timestamp, body).as_bytes());
+    let expected = format!("v0={}", mac.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+    // Compare in constant time, so the signature cannot be guessed byte by byte.
+    expected.len() == signature.len()
+        && expected.bytes().zip(signature.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0_u8; 64];
+    if key.len() > block.len() {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let inner = Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// The reply to a slash command for the game `solver`: the board so far with the guessed
+/// words, and the best guesses, or the solution once it is known.
+pub fn reply(solver: &Solver) -> String {
+    let mut lines = solver.state().history.iter()
+        .map(|(guess, pattern)| format!("{} `{}`", Share::row(pattern), guess))
+        .collect::<Vec<String>>();
+    let remaining = solver.solution_space();
+    lines.push(match remaining.len() {
+        0 => "No word in the list fits this feedback. `/wordle reset` starts a new game.".to_string(),
+        1 => format!("The solution is *{}*.", remaining[0]),
+        n => {
+            let suggestions = solver.suggestions(5).iter()
+                .map(|e| format!("`{}` ({:.2})", e.word, e.entropy))
+                .collect::<Vec<String>>();
+            format!("{} possible solutions. Try {}.", n, suggestions.join(", "))
+        }
+    });
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack() {
+        let form = parse_form("command=%2Fwordle&text=crane+BBYGB&user_id=U1&bad=%zz");
+        assert_eq!(form["command"], "/wordle");
+        assert_eq!(SlackCommand::parse(&form["text"]), SlackCommand::Guess("crane".to_string(), "bbygb".to_string()));
+        assert_eq!(form["bad"], "%zz");
+        assert_eq!(SlackCommand::parse(""), SlackCommand::Suggest);
+        assert_eq!(SlackCommand::parse("what is this"), SlackCommand::Help);
+
+        // RFC 4231, test case 2.
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(mac.iter().map(|b| format!("{:02x}", b)).collect::<String>(),
+                   "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs().to_string();
+        let signature = format!("v0={}", hmac_sha256(b"secret", format!("v0:{}:text=x", now).as_bytes())
+            .iter().map(|b| format!("{:02x}", b)).collect::<String>());
+        assert!(verify_signature("secret", &now, "text=x", &signature));
+        assert!(!verify_signature("secret", &now, "text=y", &signature));
+        assert!(!verify_signature("secret", "1000", "text=x", &signature));
+    }
+}
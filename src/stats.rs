@@ -0,0 +1,230 @@
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::daily::Date;
+use crate::game::SimulatedGame;
+use crate::style::{self, Palette, Styled};
+use crate::word::Word;
+
+/// The result of a game of `play`, as kept in the stats file.
+///
+/// # Fields
+/// * `date` - The day the game was played.
+/// * `word` - The solution.
+/// * `guesses` - The number of guesses made.
+/// * `won` - Whether the solution was found within the allowed guesses.
+/// * `time` - How long the game took, for games played with `--timed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayRecord {
+    pub date: Date,
+    pub word: Word,
+    pub guesses: u8,
+    pub won: bool,
+    pub time: Option<Duration>,
+}
+
+impl PlayRecord {
+    /// Parses a line of the stats file, e.g. `2022-01-01 rebus 4 won`, or
+    /// `2022-01-01 rebus 4 won 31.7` for a timed game that took 31.7 seconds.
+    pub fn parse(line: &str) -> Option<PlayRecord> {
+        let mut parts = line.split_whitespace();
+        let date = parts.next()?.parse().ok()?;
+        let word = Word::try_from_str(parts.next()?).ok()?;
+        let guesses = parts.next()?.parse().ok()?;
+        let won = match parts.next()? {
+            "won" => true,
+            "lost" => false,
+            _ => return None,
+        };
+        let time = match parts.next() {
+            Some(seconds) => Some(Duration::try_from_secs_f64(seconds.parse().ok()?).ok()?),
+            None => None,
+        };
+        parts.next().is_none().then_some(PlayRecord { date, word, guesses, won, time })
+    }
+}
+
+impl Display for PlayRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.date, self.word, self.guesses, if self.won { "won" } else { "lost" })?;
+        match self.time {
+            Some(time) => write!(f, " {:.1}", time.as_secs_f64()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The results of all games of `play`, with the figures of the statistics screen of the
+/// real game.
+///
+/// The results are kept in a text file with one [`PlayRecord`] per line, oldest first,
+/// which is only ever appended to.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::stats::Stats;
+///
+/// let stats = Stats::parse("2022-01-01 rebus 4 won\n2022-01-02 boost 6 lost\n2022-01-03 truss 3 won\n");
+/// assert_eq!(stats.played(), 3);
+/// assert_eq!((stats.current_streak(), stats.max_streak()), (1, 1));
+/// assert_eq!(stats.distribution()[3], 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    records: Vec<PlayRecord>,
+}
+
+impl Stats {
+    /// The stats file used unless another one is given: `$XDG_DATA_HOME/wordl-rust-bot/stats.txt`,
+    /// or `~/.local/share/wordl-rust-bot/stats.txt` if `XDG_DATA_HOME` is not set.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+        Some(base.join("wordl-rust-bot").join("stats.txt"))
+    }
+
+    /// Parses the contents of a stats file. Malformed lines are skipped.
+    pub fn parse(text: &str) -> Stats {
+        Stats { records: text.lines().filter_map(PlayRecord::parse).collect() }
+    }
+
+    /// Reads the stats file at `path`, which counts as empty if it does not exist yet.
+    ///
+    /// # Errors
+    /// If the file exists but cannot be read.
+    pub fn load(path: &Path) -> io::Result<Stats> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Stats::parse(&text)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Stats::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Adds `record` to the end of the stats file at `path`, creating the file and its
+    /// directory if needed.
+    ///
+    /// # Errors
+    /// If the file cannot be created or written.
+    pub fn append(path: &Path, record: &PlayRecord) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", record)
+    }
+
+    pub fn records(&self) -> &[PlayRecord] {
+        &self.records
+    }
+
+    pub fn played(&self) -> usize {
+        self.records.len()
+    }
+
+    /// The share of games won, in percent, rounded down as in the real game.
+    pub fn win_percentage(&self) -> usize {
+        let won = self.records.iter().filter(|record| record.won).count();
+        (won * 100).checked_div(self.played()).unwrap_or(0)
+    }
+
+    /// The number of games won since the last game lost.
+    pub fn current_streak(&self) -> usize {
+        self.records.iter().rev().take_while(|record| record.won).count()
+    }
+
+    /// The most games won in a row.
+    pub fn max_streak(&self) -> usize {
+        self.records.split(|record| !record.won).map(<[PlayRecord]>::len).max().unwrap_or(0)
+    }
+
+    /// How many games were won with 1, 2, ... guesses, at index 0, 1, ...
+    pub fn distribution(&self) -> [usize; SimulatedGame::MAX_ROUNDS as usize] {
+        let mut distribution = [0; SimulatedGame::MAX_ROUNDS as usize];
+        for record in self.records.iter().filter(|record| record.won) {
+            if let Some(count) = (record.guesses as usize).checked_sub(1).and_then(|i| distribution.get_mut(i)) {
+                *count += 1;
+            }
+        }
+        distribution
+    }
+
+    /// The times of the timed games won.
+    fn winning_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.records.iter().filter(|record| record.won).filter_map(|record| record.time)
+    }
+
+    /// The shortest time in which a timed game was won.
+    pub fn best_time(&self) -> Option<Duration> {
+        self.winning_times().min()
+    }
+
+    /// The average time of the timed games won.
+    pub fn average_time(&self) -> Option<Duration> {
+        let count = self.winning_times().count() as u32;
+        (count > 0).then(|| self.winning_times().sum::<Duration>() / count)
+    }
+}
+
+impl Display for Stats {
+    /// Prints the figures and the guess distribution, marking the last game if it was won.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", style::bold("Statistics"))?;
+        writeln!(f, "{:>8} {:>8} {:>8} {:>8}", self.played(), self.win_percentage(),
+                 self.current_streak(), self.max_streak())?;
+        writeln!(f, "{:>8} {:>8} {:>8} {:>8}", "Played", "Win %", "Current", "Max")?;
+        writeln!(f, "{:>8} {:>8} {:>8} {:>8}", "", "", "Streak", "Streak")?;
+        if let (Some(best), Some(average)) = (self.best_time(), self.average_time()) {
+            writeln!(f)?;
+            writeln!(f, "Best time {:.1}s, average {:.1}s", best.as_secs_f64(), average.as_secs_f64())?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", style::bold("Guess Distribution"))?;
+        let distribution = self.distribution();
+        let most = distribution.iter().copied().max().unwrap_or(0).max(1);
+        let last = self.records.last().filter(|record| record.won).map(|record| record.guesses as usize);
+        for (i, &count) in distribution.iter().enumerate() {
+            let width = 1 + count * 30 / most;
+            let bar = format!(" {:<width$}", count);
+            match (last == Some(i + 1), style::enabled()) {
+                (true, true) => write!(f, "\n{} {}", i + 1, Styled::new(Palette::current().highlight_code(), bar))?,
+                (false, true) => write!(f, "\n{} {}", i + 1, Styled::new(style::REVERSE, bar))?,
+                // Without colors, the bar is drawn with `#`, and the last game is marked.
+                (true, false) => write!(f, "\n{} {} {:#<width$} <", i + 1, count, "")?,
+                (false, false) => write!(f, "\n{} {} {:#<width$}", i + 1, count, "")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let record = PlayRecord::parse("2022-01-01 rebus 4 won").unwrap();
+        assert_eq!(record.to_string(), "2022-01-01 rebus 4 won");
+        assert!(PlayRecord::parse("2022-01-01 rebus 4 maybe").is_none());
+        let stats = Stats::parse("2022-01-01 rebus 4 won\n2022-01-02 boost 2 won\nnot a record\n\
+                                  2022-01-02 truss 6 lost\n2022-01-03 siege 4 won\n");
+        assert_eq!(stats.played(), 4);
+        assert_eq!(stats.win_percentage(), 75);
+        assert_eq!((stats.current_streak(), stats.max_streak()), (1, 2));
+        assert_eq!(stats.distribution(), [0, 1, 0, 2, 0, 0]);
+        assert_eq!(stats.to_string().lines().count(), 6 + SimulatedGame::MAX_ROUNDS as usize);
+        assert_eq!(Stats::default().win_percentage(), 0);
+        assert_eq!(stats.best_time(), None);
+
+        let record = PlayRecord::parse("2022-01-04 siege 3 won 31.7").unwrap();
+        assert_eq!(record.time, Some(Duration::from_millis(31_700)));
+        assert_eq!(record.to_string(), "2022-01-04 siege 3 won 31.7");
+        assert!(PlayRecord::parse("2022-01-04 siege 3 won -1").is_none());
+        let stats = Stats::parse("2022-01-01 rebus 4 won 40.0\n2022-01-02 boost 6 lost 90.0\n2022-01-03 truss 3 won 20.0\n");
+        assert_eq!(stats.best_time(), Some(Duration::from_secs(20)));
+        assert_eq!(stats.average_time(), Some(Duration::from_secs(30)));
+    }
+}
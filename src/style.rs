@@ -0,0 +1,206 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::pattern::Color;
+
+/// When output is styled with ANSI escape codes, chosen with `--color`.
+///
+/// # Variants
+/// * `Auto` - Only if standard output is a terminal and the `NO_COLOR` environment variable
+///   is not set, see <https://no-color.org>.
+/// * `Always` - Always, e.g. when piping into `less -R`.
+/// * `Never` - Never.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Whether output is styled. Styled unless turned off, so that the library behaves the same
+/// whether or not a [`ColorChoice`] was applied.
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+impl ColorChoice {
+    /// Whether output is styled with this choice, in the current environment.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                && io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+
+    /// Styles all output from now on if [`ColorChoice::enabled`] says so.
+    pub fn apply(self) {
+        ENABLED.store(self.enabled(), Ordering::Relaxed);
+    }
+}
+
+/// Whether output is currently styled, see [`ColorChoice::apply`].
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Select Graphic Rendition codes, the part of an escape sequence between `\x1b[` and `m`.
+pub const BOLD: &str = "1";
+pub const DIM: &str = "2";
+pub const REVERSE: &str = "7";
+pub const GREEN: &str = "32";
+pub const YELLOW: &str = "33";
+pub const GRAY: &str = "90";
+pub const BOLD_GREEN: &str = "1;32";
+pub const BOLD_YELLOW: &str = "1;33";
+pub const BOLD_ORANGE: &str = "1;38;5;208";
+pub const BOLD_BLUE: &str = "1;38;5;33";
+pub const BOLD_ON_GREEN: &str = "1;42";
+pub const BOLD_ON_ORANGE: &str = "1;48;5;208";
+
+/// The colors feedback is shown in, chosen once at startup with [`Palette::set_current`].
+///
+/// # Variants
+/// * `Standard` - Green and yellow, as in the original game.
+/// * `HighContrast` - Orange and blue, as in the high contrast mode of the New York Times
+///   game, for players who cannot tell green from yellow.
+///
+/// In both, letters that are not in the solution are gray, which is readable on dark
+/// terminals, unlike black.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::style::Palette;
+/// use wordl_rust_bot::Color;
+///
+/// assert_eq!(Palette::Standard.square(Color::Green), '🟩');
+/// assert_eq!(Palette::HighContrast.square(Color::Green), '🟧');
+/// assert_eq!(Palette::HighContrast.square(Color::Yellow), '🟦');
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    #[default]
+    Standard,
+    HighContrast,
+}
+
+/// Whether the current palette is [`Palette::HighContrast`].
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+impl Palette {
+    /// The palette feedback is shown in, [`Palette::Standard`] unless set otherwise.
+    pub fn current() -> Palette {
+        match HIGH_CONTRAST.load(Ordering::Relaxed) {
+            true => Palette::HighContrast,
+            false => Palette::Standard,
+        }
+    }
+
+    /// Makes `self` the palette feedback is shown in from now on.
+    pub fn set_current(self) {
+        HIGH_CONTRAST.store(self == Palette::HighContrast, Ordering::Relaxed);
+    }
+
+    /// The SGR code for the letters of a pattern, e.g. the `g` of `bgybb`.
+    pub fn pattern_code(self, color: Color) -> &'static str {
+        match (self, color) {
+            (Palette::Standard, Color::Green) => GREEN,
+            (Palette::Standard, Color::Yellow) => YELLOW,
+            (Palette::HighContrast, Color::Green) => BOLD_ORANGE,
+            (Palette::HighContrast, Color::Yellow) => BOLD_BLUE,
+            (_, Color::Black) => GRAY,
+        }
+    }
+
+    /// The SGR code for the letters of the keyboard of `play`.
+    pub fn key_code(self, color: Color) -> &'static str {
+        match (self, color) {
+            (Palette::Standard, Color::Green) => BOLD_GREEN,
+            (Palette::Standard, Color::Yellow) => BOLD_YELLOW,
+            (Palette::HighContrast, Color::Green) => BOLD_ORANGE,
+            (Palette::HighContrast, Color::Yellow) => BOLD_BLUE,
+            (_, Color::Black) => GRAY,
+        }
+    }
+
+    /// The SGR code for highlighting a won game, e.g. in the statistics.
+    pub fn highlight_code(self) -> &'static str {
+        match self {
+            Palette::Standard => BOLD_ON_GREEN,
+            Palette::HighContrast => BOLD_ON_ORANGE,
+        }
+    }
+
+    /// The square emoji of `color` in share grids.
+    pub fn square(self, color: Color) -> char {
+        match (self, color) {
+            (Palette::Standard, Color::Green) => '🟩',
+            (Palette::Standard, Color::Yellow) => '🟨',
+            (Palette::HighContrast, Color::Green) => '🟧',
+            (Palette::HighContrast, Color::Yellow) => '🟦',
+            (_, Color::Black) => '⬛',
+        }
+    }
+}
+
+/// `content` displayed in the style of an SGR `code`, or plainly if output is not styled.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::style::{self, Styled};
+///
+/// assert_eq!(style::bold("Success!").to_string(), "\x1b[1mSuccess!\x1b[0m");
+/// assert_eq!(Styled::new(style::GREEN, 'g').to_string(), "\x1b[32mg\x1b[0m");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Styled<T> {
+    code: &'static str,
+    content: T,
+}
+
+impl<T: Display> Styled<T> {
+    pub fn new(code: &'static str, content: T) -> Styled<T> {
+        Styled { code, content }
+    }
+}
+
+impl<T: Display> Display for Styled<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match enabled() {
+            true => write!(f, "\x1b[{}m{}\x1b[0m", self.code, self.content),
+            false => write!(f, "{}", self.content),
+        }
+    }
+}
+
+/// `content` in bold, as used for headings and for the words and patterns of a game.
+pub fn bold<T: Display>(content: T) -> Styled<T> {
+    Styled::new(BOLD, content)
+}
+
+/// `content` dimmed, as used for provisional output that is overwritten later.
+pub fn dim<T: Display>(content: T) -> Styled<T> {
+    Styled::new(DIM, content)
+}
+
+/// The escape sequence that moves back to the start of the line and erases it, for
+/// overwriting provisional output, or nothing if output is not styled.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::style::{self, ColorChoice};
+///
+/// ColorChoice::Never.apply();
+/// assert_eq!(style::clear_line(), "");
+/// ColorChoice::Always.apply();
+/// assert_eq!(style::clear_line(), "\r\x1b[K");
+/// ```
+pub fn clear_line() -> &'static str {
+    match enabled() {
+        true => "\r\x1b[K",
+        false => "",
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+use serde_json::{json, Value};
+use crate::daily::Date;
+use crate::error::WordleError;
+use crate::game::Solver;
+use crate::pattern::{Color, Pattern};
+use crate::server::Session;
+use crate::share::Share;
+use crate::style::Palette;
+use crate::word::Word;
+
+/// The address of the Telegram Bot API.
+const API: &str = "https://api.telegram.org";
+
+/// How long a request for updates waits for one to arrive, in seconds.
+const POLL_TIMEOUT: u64 = 25;
+
+/// How long to wait before asking for updates again after a failed request.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A message to the bot.
+///
+/// # Variants
+/// * `Suggest` - `/suggest`: shows the board and the best guesses.
+/// * `Guess` - `crane bbygb`: enters a guess and its pattern, then suggests.
+/// * `Pick` - `crane`: shows a keyboard to enter the colors of a guess by tapping its letters.
+/// * `Reset` - `/reset`: starts a new game.
+/// * `Stats` - `/stats`: shows the games of the chat today.
+/// * `Help` - `/help`, `/start`, or anything else: explains the bot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TelegramCommand {
+    Suggest,
+    Guess(String, String),
+    Pick(String),
+    Reset,
+    Stats,
+    Help,
+}
+
+impl TelegramCommand {
+    /// Parses the text of a message. In groups, commands may name the bot, as in
+    /// `/stats@wordle_bot`.
+    pub fn parse(text: &str) -> TelegramCommand {
+        let parts = text.split_whitespace()
+            .map(|part| part.split_once('@').filter(|_| part.starts_with('/')).map_or(part, |(command, _)| command))
+            .collect::<Vec<&str>>();
+        match parts.as_slice() {
+            ["/suggest"] | ["/wordle"] => TelegramCommand::Suggest,
+            ["/reset"] | ["/new"] => TelegramCommand::Reset,
+            ["/stats"] => TelegramCommand::Stats,
+            ["/guess", guess] => TelegramCommand::Pick(guess.to_lowercase()),
+            ["/guess", guess, pattern] => TelegramCommand::Guess(guess.to_lowercase(), pattern.to_lowercase()),
+            [guess] if !guess.starts_with('/') => TelegramCommand::Pick(guess.to_lowercase()),
+            [guess, pattern] if !guess.starts_with('/') => {
+                TelegramCommand::Guess(guess.to_lowercase(), pattern.to_lowercase())
+            }
+            _ => TelegramCommand::Help,
+        }
+    }
+}
+
+/// The usage of the bot, as shown by [`TelegramCommand::Help`].
+pub const USAGE: &str = "Send a guess and its colors (g = green, y = yellow, b = black), e.g. \
+                         `crane bbygb`, or just the guess to tap its colors in. /suggest shows \
+                         the best guesses, /reset starts a new game and /stats shows today's games.";
+
+/// A request to the Bot API.
+///
+/// # Fields
+/// * `method` - The name of the method, e.g. `sendMessage`.
+/// * `parameters` - The parameters of the method, sent as a JSON object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiCall {
+    pub method: &'static str,
+    pub parameters: Value,
+}
+
+impl ApiCall {
+    fn send(chat: i64, text: String, keyboard: Option<Value>) -> ApiCall {
+        let mut parameters = json!({ "chat_id": chat, "text": text });
+        if let Some(keyboard) = keyboard {
+            parameters["reply_markup"] = keyboard;
+        }
+        ApiCall { method: "sendMessage", parameters }
+    }
+}
+
+/// The games a chat played on one day.
+///
+/// # Fields
+/// * `date` - The day, in UTC.
+/// * `games` - The games with at least one guess entered.
+/// * `solved` - The games that ended with an all green pattern.
+/// * `guesses` - The guesses of the solved games.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyStats {
+    pub date: Date,
+    pub games: usize,
+    pub solved: usize,
+    pub guesses: usize,
+}
+
+impl DailyStats {
+    fn new(date: Date) -> DailyStats {
+        DailyStats { date, games: 0, solved: 0, guesses: 0 }
+    }
+}
+
+impl std::fmt::Display for DailyStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} games, {} solved", self.date, self.games, self.solved)?;
+        if self.solved > 0 {
+            write!(f, " in {:.1} guesses on average", self.guesses as f64 / self.solved as f64)?;
+        }
+        Ok(())
+    }
+}
+
+struct Chat<'a> {
+    session: Session<'a>,
+    stats: DailyStats,
+}
+
+/// Plays one game per chat, whose guesses can be typed in or tapped in on a keyboard of
+/// their letters, and counts the games of each chat by day. Stats are kept in memory only.
+///
+/// # Example
+///
+/// ```
+/// use serde_json::json;
+/// use wordl_rust_bot::telegram::TelegramBot;
+/// use wordl_rust_bot::{Solver, Word};
+///
+/// let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+/// let mut bot = TelegramBot::new(Solver::new(&words));
+/// let calls = bot.handle(&json!({ "message": { "chat": { "id": 7 }, "text": "crane bygby" } }));
+/// assert_eq!(calls[0].method, "sendMessage");
+/// assert!(calls[0].parameters["text"].as_str().unwrap().contains("3 possible solutions"));
+/// ```
+pub struct TelegramBot<'a> {
+    initial: Session<'a>,
+    chats: HashMap<i64, Chat<'a>>,
+}
+
+impl<'a> TelegramBot<'a> {
+    pub fn new(solver: Solver<'a>) -> TelegramBot<'a> {
+        TelegramBot { initial: Session::new(solver), chats: HashMap::new() }
+    }
+
+    /// Answers an update of the Bot API: a message, or a tap on a button of a keyboard the
+    /// bot sent. Other updates are ignored.
+    ///
+    /// # Returns
+    /// The requests that answer the update, in order.
+    pub fn handle(&mut self, update: &Value) -> Vec<ApiCall> {
+        if let Some(chat) = update["message"]["chat"]["id"].as_i64() {
+            let text = update["message"]["text"].as_str().unwrap_or("");
+            return vec![self.answer(chat, TelegramCommand::parse(text))];
+        }
+        let query = &update["callback_query"];
+        let (Some(id), Some(data)) = (query["id"].as_str(), query["data"].as_str()) else {
+            return Vec::new();
+        };
+        let mut calls = vec![ApiCall { method: "answerCallbackQuery", parameters: json!({ "callback_query_id": id }) }];
+        let (Some(chat), Some(message)) = (query["message"]["chat"]["id"].as_i64(), query["message"]["message_id"].as_i64()) else {
+            return calls;
+        };
+        let edit = |keyboard: Value| ApiCall {
+            method: "editMessageReplyMarkup",
+            parameters: json!({ "chat_id": chat, "message_id": message, "reply_markup": keyboard }),
+        };
+        match Tap::parse(data) {
+            Some(Tap::Letter(guess, pattern, i)) => {
+                let color = match pattern[i] {
+                    Color::Black => Color::Yellow,
+                    Color::Yellow => Color::Green,
+                    Color::Green => Color::Black,
+                };
+                let mut pattern = pattern;
+                pattern.set(i, color);
+                calls.push(edit(keyboard(&guess, pattern)));
+            }
+            Some(Tap::Enter(guess, pattern)) => {
+                calls.push(edit(json!({ "inline_keyboard": [] })));
+                calls.push(self.answer(chat, TelegramCommand::Guess(guess.to_string(), pattern.letters())));
+            }
+            None => {}
+        }
+        calls
+    }
+
+    /// Answers `command` for the game of `chat`.
+    fn answer(&mut self, chat: i64, command: TelegramCommand) -> ApiCall {
+        let initial = &self.initial;
+        let entry = self.chats.entry(chat).or_insert_with(|| Chat {
+            session: initial.clone(),
+            stats: DailyStats::new(Date::today()),
+        });
+        if entry.stats.date != Date::today() {
+            entry.stats = DailyStats::new(Date::today());
+        }
+        let session = &mut entry.session;
+        match command {
+            TelegramCommand::Suggest => suggest(chat, session),
+            TelegramCommand::Reset => {
+                session.reset();
+                suggest(chat, session)
+            }
+            TelegramCommand::Stats => ApiCall::send(chat, entry.stats.to_string(), None),
+            TelegramCommand::Pick(guess) => match Word::try_from_str_ignore_case(&guess) {
+                Ok(guess) => ApiCall::send(chat, format!("Tap the letters of {} until they have its colors.", guess),
+                                           Some(keyboard(&guess, Pattern::all_black()))),
+                Err(e) => ApiCall::send(chat, format!("{}. {}", e, USAGE), None),
+            },
+            TelegramCommand::Guess(guess, pattern) => {
+                match (Word::try_from_str_ignore_case(&guess), Pattern::try_from_str(&pattern)) {
+                    (Ok(guess), Ok(pattern)) => {
+                        if session.solved() {
+                            session.reset();
+                        }
+                        session.feedback(guess, pattern);
+                        let guesses = session.solver().state().history.len();
+                        if guesses == 1 {
+                            entry.stats.games += 1;
+                        }
+                        if session.solved() {
+                            entry.stats.solved += 1;
+                            entry.stats.guesses += guesses;
+                        }
+                        suggest(chat, session)
+                    }
+                    (Err(e), _) => ApiCall::send(chat, format!("{}. {}", e, USAGE), None),
+                    (_, Err(e)) => ApiCall::send(chat, format!("{}. {}", e, USAGE), None),
+                }
+            }
+            TelegramCommand::Help => ApiCall::send(chat, USAGE.to_string(), None),
+        }
+    }
+
+    /// Answers updates from the Bot API with the bot `token` until the program is stopped.
+    /// Failed requests are reported on standard error and retried, so the bot outlives
+    /// short network outages.
+    ///
+    /// # Errors
+    /// If Telegram rejects the token.
+    pub fn run(mut self, token: &str) -> Result<(), WordleError> {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(POLL_TIMEOUT + 10))
+            .user_agent("wordl-rust-bot")
+            .build();
+        let me = call(&agent, token, &ApiCall { method: "getMe", parameters: json!({}) })?;
+        eprintln!("Running as @{}", me["username"].as_str().unwrap_or("?"));
+        let mut offset = 0;
+        loop {
+            let updates = ApiCall {
+                method: "getUpdates",
+                parameters: json!({ "offset": offset, "timeout": POLL_TIMEOUT,
+                                    "allowed_updates": ["message", "callback_query"] }),
+            };
+            let updates = match call(&agent, token, &updates) {
+                Ok(updates) => updates,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    thread::sleep(RETRY_DELAY);
+                    continue;
+                }
+            };
+            for update in updates.as_array().into_iter().flatten() {
+                offset = offset.max(update["update_id"].as_i64().unwrap_or(0) + 1);
+                for answer in self.handle(update) {
+                    if let Err(e) = call(&agent, token, &answer) {
+                        eprintln!("{}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The message with the board of the game in `session` and its best guesses, or the
+/// solution once it is known. While the game is open, the best guess comes with a keyboard
+/// to tap in its colors.
+fn suggest(chat: i64, session: &Session) -> ApiCall {
+    let solver = session.solver();
+    let mut lines = solver.state().history.iter()
+        .map(|(guess, pattern)| format!("{} {}", Share::row(pattern), guess))
+        .collect::<Vec<String>>();
+    let remaining = solver.solution_space();
+    let mut best = None;
+    lines.push(match remaining.len() {
+        _ if session.solved() => {
+            format!("Solved in {} guesses. /reset starts a new game.", solver.state().history.len())
+        }
+        0 => "No word in the list fits this feedback. /reset starts a new game.".to_string(),
+        1 => format!("The solution is {}.", remaining[0]),
+        n => {
+            let suggestions = session.suggest(5);
+            best = suggestions.first().map(|e| *e.word);
+            let suggestions = suggestions.iter()
+                .map(|e| format!("{} ({:.2})", e.word, e.entropy))
+                .collect::<Vec<String>>();
+            format!("{} possible solutions. Try {}.", n, suggestions.join(", "))
+        }
+    });
+    ApiCall::send(chat, lines.join("\n"), best.map(|guess| keyboard(&guess, Pattern::all_black())))
+}
+
+/// A tap on a button of a [`keyboard`], as encoded in its callback data.
+///
+/// # Variants
+/// * `Letter` - `t:crane:bbybb:2`: the letter at the index was tapped to change its color.
+/// * `Enter` - `e:crane:bbybb`: the pattern is complete.
+enum Tap {
+    Letter(Word, Pattern, usize),
+    Enter(Word, Pattern),
+}
+
+impl Tap {
+    fn parse(data: &str) -> Option<Tap> {
+        let parts = data.split(':').collect::<Vec<&str>>();
+        let word = |text: &str| Word::try_from_str(text).ok();
+        let pattern = |text: &str| Pattern::try_from_str(text).ok();
+        match parts.as_slice() {
+            ["t", guess, colors, i] => {
+                let i = i.parse().ok().filter(|&i| i < crate::word::WORD_LENGTH)?;
+                Some(Tap::Letter(word(guess)?, pattern(colors)?, i))
+            }
+            ["e", guess, colors] => Some(Tap::Enter(word(guess)?, pattern(colors)?)),
+            _ => None,
+        }
+    }
+}
+
+/// An inline keyboard with a button for every letter of `guess`, showing its color in
+/// `pattern`, and a button to enter the pattern. The state of the keyboard is kept in the
+/// callback data of its buttons, at most 64 bytes each.
+fn keyboard(guess: &Word, pattern: Pattern) -> Value {
+    let letters = guess.letters().iter().enumerate()
+        .map(|(i, letter)| json!({
+            "text": format!("{} {}", letter.to_uppercase(), Palette::current().square(pattern[i])),
+            "callback_data": format!("t:{}:{}:{}", guess, pattern.letters(), i),
+        }))
+        .collect::<Vec<Value>>();
+    let enter = json!({ "text": format!("Enter {}", guess), "callback_data": format!("e:{}:{}", guess, pattern.letters()) });
+    json!({ "inline_keyboard": [letters, [enter]] })
+}
+
+/// Sends `request` to the Bot API and returns its result.
+///
+/// # Errors
+/// If the API cannot be reached or does not answer with a result. The error does not show
+/// the address called, which contains the token.
+fn call(agent: &ureq::Agent, token: &str, request: &ApiCall) -> Result<Value, WordleError> {
+    let error = |reason: String| WordleError::Telegram { method: request.method.to_string(), reason };
+    let response = agent.post(&format!("{}/bot{}/{}", API, token, request.method))
+        .set("Content-Type", "application/json")
+        .send_string(&request.parameters.to_string());
+    let body = match response {
+        // Telegram explains a failed request in the body of the response.
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response.into_string(),
+        Err(ureq::Error::Transport(transport)) => return Err(error(transport.kind().to_string())),
+    }.map_err(|e| error(e.to_string()))?;
+    let body = serde_json::from_str::<Value>(&body).map_err(|_| error("malformed response".to_string()))?;
+    match body["ok"].as_bool() {
+        Some(true) => Ok(body["result"].clone()),
+        _ => Err(error(body["description"].as_str().unwrap_or("unknown error").to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_telegram() {
+        assert_eq!(TelegramCommand::parse("/stats@wordle_bot"), TelegramCommand::Stats);
+        assert_eq!(TelegramCommand::parse("Crane BBYGB"), TelegramCommand::Guess("crane".to_string(), "bbygb".to_string()));
+        assert_eq!(TelegramCommand::parse("/guess crane"), TelegramCommand::Pick("crane".to_string()));
+        assert_eq!(TelegramCommand::parse("/what"), TelegramCommand::Help);
+
+        let words = sample_words();
+        let mut bot = TelegramBot::new(Solver::new(&words));
+        let message = |text: &str| json!({ "message": { "chat": { "id": 7 }, "text": text } });
+        let tap = |data: &str| json!({ "callback_query": { "id": "q", "data": data,
+                                                           "message": { "chat": { "id": 7 }, "message_id": 3 } } });
+
+        // Tapping the third letter twice turns it green, keeping the state in the buttons.
+        let calls = bot.handle(&tap("t:crane:bbybb:2"));
+        assert_eq!(calls.iter().map(|call| call.method).collect::<Vec<&str>>(),
+                   ["answerCallbackQuery", "editMessageReplyMarkup"]);
+        let buttons = &calls[1].parameters["reply_markup"]["inline_keyboard"];
+        assert_eq!(buttons[0][2]["text"], "A 🟩");
+        assert_eq!(buttons[0][2]["callback_data"], "t:crane:bbgbb:2");
+        assert_eq!(buttons[1][0]["callback_data"], "e:crane:bbgbb");
+
+        let calls = bot.handle(&tap("e:crane:bygby"));
+        assert_eq!(calls[2].parameters["text"].as_str().unwrap().lines().next(), Some("⬛🟨🟩⬛🟨 crane"));
+        assert!(bot.handle(&message("tears ggggg"))[0].parameters["text"].as_str().unwrap().contains("Solved in 2"));
+        let stats = bot.handle(&message("/stats"));
+        assert!(stats[0].parameters["text"].as_str().unwrap().ends_with("1 games, 1 solved in 2.0 guesses on average"));
+        assert!(bot.handle(&tap("x:crane")).len() == 1);
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "serde")]
+use std::fs::{File, OpenOptions};
+#[cfg(feature = "serde")]
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+#[cfg(feature = "serde")]
+use std::path::Path;
+#[cfg(feature = "serde")]
+use miette::Diagnostic;
+#[cfg(feature = "serde")]
+use thiserror::Error;
+use crate::batch::GameRecord;
+use crate::game::score;
+use crate::pattern::Pattern;
+use crate::style;
+use crate::word::Word;
+
+/// The subcommand a game of a [`Transcript`] was played in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum Mode {
+    Assist,
+    Play,
+    Batch,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Mode::Assist => "assist",
+            Mode::Play => "play",
+            Mode::Batch => "batch",
+        })
+    }
+}
+
+/// One round of a [`Transcript`].
+///
+/// # Fields
+/// * `guess` - The word guessed.
+/// * `pattern` - The feedback it got.
+/// * `remaining` - The number of candidates that fit the feedback before the guess.
+/// * `rank` - The position of the guess among the suggestions of the round, counting from 1,
+///   or `None` if it was not suggested or the game made no suggestions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round {
+    pub guess: Word,
+    pub pattern: Pattern,
+    pub remaining: usize,
+    pub rank: Option<usize>,
+}
+
+/// The record of one game, round by round, for later analysis or replay.
+///
+/// # Fields
+/// * `mode` - Where the game was played.
+/// * `solution` - The solution, if it is known: always for `play` and `batch`, and for
+///   `assist` once a single candidate is left.
+/// * `solved` - Whether the game found the solution.
+/// * `rounds` - The rounds played, in order.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::batch::GameRecord;
+/// use wordl_rust_bot::transcript::{Mode, Transcript};
+/// use wordl_rust_bot::Word;
+///
+/// let record = GameRecord {
+///     solution: Word::from_str("bears"),
+///     guesses: vec![Word::from_str("tears"), Word::from_str("bears")],
+///     solved: true,
+///     space_sizes: vec![100, 3],
+/// };
+/// let transcript = Transcript::simulated(&record);
+/// assert_eq!(transcript.mode, Mode::Batch);
+/// assert_eq!(transcript.rounds[0].pattern.letters(), "bgggg");
+/// assert_eq!(transcript.rounds[1].remaining, 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transcript {
+    pub mode: Mode,
+    pub solution: Option<Word>,
+    pub solved: bool,
+    pub rounds: Vec<Round>,
+}
+
+impl Transcript {
+    /// The transcript of a game simulated by `batch`, in which the solver made every guess.
+    pub fn simulated(record: &GameRecord) -> Transcript {
+        let rounds = record.guesses.iter().zip(&record.space_sizes)
+            .map(|(&guess, &remaining)| Round {
+                guess,
+                pattern: score(&guess, &record.solution),
+                remaining,
+                rank: None,
+            })
+            .collect();
+        Transcript { mode: Mode::Batch, solution: Some(record.solution), solved: record.solved, rounds }
+    }
+
+    /// A line describing the game as a whole, e.g. `batch game, solution aahed, solved in 5`.
+    pub fn summary(&self) -> String {
+        let solution = match self.solution {
+            Some(solution) => format!("solution {}", solution),
+            None => "solution unknown".to_string(),
+        };
+        let outcome = match (self.solved, self.rounds.len()) {
+            (_, 0) => "no guesses".to_string(),
+            (true, rounds) => format!("solved in {}", rounds),
+            (false, rounds) => format!("not solved after {}", rounds),
+        };
+        format!("{} game, {}, {}", self.mode, solution, outcome)
+    }
+}
+
+/// The game round by round, e.g.
+///
+/// ```text
+/// assist game, solution unknown, not solved after 1
+///   1. crane  bgybb  2315 candidates, suggestion 2
+/// ```
+impl Display for Transcript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.summary())?;
+        for (i, round) in self.rounds.iter().enumerate() {
+            write!(f, "\n{:>3}. {}  {}  {} candidate{}", i + 1, style::bold(round.guess), round.pattern,
+                   round.remaining, if round.remaining == 1 { "" } else { "s" })?;
+            if let Some(rank) = round.rank {
+                write!(f, ", suggestion {}", rank)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A line of a transcript file that could not be read.
+#[cfg(feature = "serde")]
+#[derive(Debug, Error, Diagnostic)]
+#[error("line {line} of {name}: {reason}")]
+#[diagnostic(help("each line must be a game as written by --log"))]
+pub struct TranscriptError {
+    pub name: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Reads the games of a transcript file written with [`TranscriptLog`], in order. Lines that
+/// cannot be read are skipped and added to `skipped`.
+///
+/// # Errors
+/// If the file cannot be read.
+#[cfg(feature = "serde")]
+pub fn read_transcripts<R: Read>(name: &str, reader: R,
+                                 skipped: &mut Vec<TranscriptError>) -> io::Result<Vec<Transcript>> {
+    let mut transcripts = Vec::new();
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(transcript) => transcripts.push(transcript),
+            Err(e) => skipped.push(TranscriptError { name: name.to_string(), line: i + 1, reason: e.to_string() }),
+        }
+    }
+    Ok(transcripts)
+}
+
+/// Appends transcripts to a JSON Lines file, one line per game, so that the games of many
+/// runs collect in the same file.
+#[cfg(feature = "serde")]
+pub struct TranscriptLog {
+    out: BufWriter<File>,
+}
+
+#[cfg(feature = "serde")]
+impl TranscriptLog {
+    /// Opens `path` for appending, creating it if it does not exist yet.
+    pub fn open(path: &Path) -> io::Result<TranscriptLog> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(TranscriptLog { out: BufWriter::new(file) })
+    }
+
+    pub fn write(&mut self, transcript: &Transcript) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, transcript)?;
+        writeln!(self.out)
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_transcript_json() {
+        let transcript = Transcript {
+            mode: Mode::Assist,
+            solution: None,
+            solved: false,
+            rounds: vec![Round {
+                guess: Word::from_str("crane"),
+                pattern: Pattern::try_from_str("bgybb").unwrap(),
+                remaining: 2315,
+                rank: Some(2),
+            }],
+        };
+        let json = serde_json::to_string(&transcript).unwrap();
+        assert_eq!(json, r#"{"mode":"assist","solution":null,"solved":false,"rounds":[{"guess":"crane","pattern":"bgybb","remaining":2315,"rank":2}]}"#);
+        let mut skipped = Vec::new();
+        let text = format!("{}\n\n{{\"mode\":\"batch\"}}\n{}\n", json, json);
+        assert_eq!(read_transcripts("log", text.as_bytes(), &mut skipped).unwrap(), [transcript.clone(), transcript]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 3);
+    }
+}
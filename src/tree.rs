@@ -0,0 +1,676 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::{self, Read, Write};
+use std::mem;
+use std::sync::Arc;
+use rayon::prelude::*;
+use crate::game::{score, GameState, SimulatedGame, Solver, Strategy};
+use crate::matrix::PatternMatrix;
+use crate::pattern::Pattern;
+use crate::style;
+use crate::word::Word;
+
+/// Marks the start of a decision tree in the binary format.
+const MAGIC: &[u8; 8] = b"WRBTREE2";
+
+/// Marks the start of a decision tree written before hard mode was supported. It has no flags.
+const MAGIC_V1: &[u8; 8] = b"WRBTREE1";
+
+/// The number of guesses tried per node when searching for a hard-mode tree, see
+/// [`DecisionTree::build_hard_mode`].
+const SEARCH_WIDTH: usize = 3;
+
+/// A node of a [`DecisionTree`]: the guess to make, and for every feedback it can get (except
+/// all green, which ends the game) the node to continue with.
+///
+/// # Fields
+/// * `guess` - The word to guess at this node.
+/// * `branches` - The nodes to continue with, by the feedback `guess` got. A node without
+///   branches is a leaf, where `guess` is the only remaining candidate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeNode {
+    pub guess: Word,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub branches: BTreeMap<Pattern, TreeNode>,
+}
+
+/// A complete play book for a word list: starting at the root, make the guess of the current
+/// node and follow the branch of the feedback, until the feedback is all green.
+///
+/// # Fields
+/// * `strategy` - The strategy that picked the guesses.
+/// * `hard_mode` - Whether every guess is consistent with the feedback on the path to it.
+/// * `root` - The node of the first guess.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::tree::DecisionTree;
+/// use wordl_rust_bot::{Strategy, Word};
+///
+/// let words = ["tears", "bears", "gears", "crane", "slimy"].map(Word::from_str);
+/// let tree = DecisionTree::build(&words, &words, Strategy::Entropy, None);
+/// for solution in &words {
+///     assert!(tree.play(solution).is_some());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecisionTree {
+    pub strategy: Strategy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hard_mode: bool,
+    pub root: TreeNode,
+}
+
+/// The file formats of decision trees.
+///
+/// # Variants
+/// * `Binary` - A compact format, see [`DecisionTree::write`].
+/// * `Json` - The tree as nested JSON objects (only with the `serde` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeFormat {
+    Binary,
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// The graph description languages decision trees can be exported to, see
+/// [`DecisionTree::write_graph`].
+///
+/// # Variants
+/// * `Dot` - For Graphviz, e.g. `dot -Tsvg tree.dot > tree.svg`.
+/// * `Mermaid` - A Mermaid flowchart, which renders directly in Markdown on many platforms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+/// How well a decision tree does on a list of solutions, see [`DecisionTree::stats`].
+///
+/// # Fields
+/// * `solutions` - The number of solutions the tree was played against.
+/// * `average` - The average number of guesses over all solutions the tree reaches.
+/// * `max_depth` - The largest number of guesses needed for a solution.
+/// * `failures` - The number of solutions that take more than [`SimulatedGame::MAX_ROUNDS`]
+///   guesses.
+/// * `unreachable` - Solutions that the tree never guesses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeStats {
+    pub solutions: usize,
+    pub average: f64,
+    pub max_depth: usize,
+    pub failures: usize,
+    pub unreachable: Vec<Word>,
+}
+
+impl DecisionTree {
+    /// Computes the decision tree of `strategy` for a game in which guesses are taken from
+    /// `words` and the solution is one of `solutions`. The branches of every node are built
+    /// in parallel.
+    ///
+    /// # Arguments
+    /// * `words` - The list of allowed guesses.
+    /// * `solutions` - The possible solutions. Duplicates are ignored.
+    /// * `strategy` - How to pick the guess of every node.
+    /// * `first_guess` - The guess of the root, or `None` to let the strategy pick it.
+    ///
+    /// # Panics
+    /// If a solution is not in `words`, see [`DecisionTree::unguessable`]. Solutions that can
+    /// never be guessed may be impossible to tell apart, and the tree would never end.
+    pub fn build(words: &[Word], solutions: &[Word], strategy: Strategy, first_guess: Option<Word>) -> DecisionTree {
+        Self::assert_guessable(words, solutions);
+        let distinct = Self::distinct(solutions);
+        let matrix = Arc::new(PatternMatrix::new(words, &distinct));
+        let solver = Solver::with_solutions(words, &distinct).with_matrix(matrix);
+        let guess = first_guess.unwrap_or_else(|| solver.guess_with(strategy));
+        DecisionTree { strategy, hard_mode: false, root: Self::build_node(&solver, guess, strategy) }
+    }
+
+    /// Like [`DecisionTree::build`], but for hard mode: every guess must be consistent with
+    /// the feedback on the path to it, i.e. it must be a word that could still be the solution.
+    ///
+    /// Always making the guess the strategy likes best can lead into dead ends in hard mode,
+    /// such as a group of words only differing in one letter that have to be guessed one by
+    /// one. Therefore, whenever a subtree would need more than [`SimulatedGame::MAX_ROUNDS`]
+    /// guesses, the next best guesses are tried instead, up to three per node.
+    ///
+    /// # Returns
+    /// The tree, and whether it solves every solution within the round limit. If the search
+    /// finds no such tree, the tree of the guesses the strategy likes best is returned. This
+    /// does not prove that no complete tree exists, as only a few guesses per node are tried.
+    ///
+    /// # Panics
+    /// If a solution is not in `words`, as for [`DecisionTree::build`].
+    pub fn build_hard_mode(words: &[Word], solutions: &[Word], strategy: Strategy,
+                           first_guess: Option<Word>) -> (DecisionTree, bool) {
+        Self::assert_guessable(words, solutions);
+        let distinct = Self::distinct(solutions);
+        let matrix = Arc::new(PatternMatrix::new(words, &distinct));
+        let solver = Solver::with_solutions(words, &distinct).with_matrix(matrix).hard_mode();
+        let roots = match first_guess {
+            Some(guess) => vec![guess],
+            None => solver.ranked_guesses(strategy, SEARCH_WIDTH),
+        };
+        let tree = |root| DecisionTree { strategy, hard_mode: true, root };
+        for guess in &roots {
+            if let Some(root) = Self::search_node(&solver, *guess, strategy, SimulatedGame::MAX_ROUNDS as usize) {
+                return (tree(root), true);
+            }
+        }
+        (tree(Self::build_node(&solver, roots[0], strategy)), false)
+    }
+
+    /// The words of `solutions` that are not in `words`, so that no tree can guess them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::tree::DecisionTree;
+    /// use wordl_rust_bot::Word;
+    ///
+    /// let words = ["tears", "crane"].map(Word::from_str);
+    /// assert_eq!(DecisionTree::unguessable(&words, &["crane", "bears"].map(Word::from_str)), [Word::from_str("bears")]);
+    /// ```
+    pub fn unguessable(words: &[Word], solutions: &[Word]) -> Vec<Word> {
+        let words = words.iter().collect::<HashSet<&Word>>();
+        solutions.iter().filter(|s| !words.contains(s)).copied().collect()
+    }
+
+    fn assert_guessable(words: &[Word], solutions: &[Word]) {
+        let unguessable = Self::unguessable(words, solutions);
+        assert!(unguessable.is_empty(), "solutions not among the allowed guesses: {:?}", unguessable);
+    }
+
+    /// The solutions without duplicates, which could never be told apart.
+    fn distinct(solutions: &[Word]) -> Vec<Word> {
+        let mut seen = HashSet::new();
+        solutions.iter().filter(|s| seen.insert(**s)).copied().collect()
+    }
+
+    /// Builds the subtree of `guess` in the state of `solver`, such that every solution is
+    /// found within `depth` guesses, or returns `None` if none of the guesses tried allow that.
+    fn search_node(solver: &Solver, guess: Word, strategy: Strategy, depth: usize) -> Option<TreeNode> {
+        let patterns = solver.solution_space().iter()
+            .map(|solution| score(&guess, solution))
+            .filter(|pattern| *pattern != Pattern::all_green())
+            .collect::<BTreeSet<Pattern>>();
+        if !patterns.is_empty() && depth <= 1 {
+            return None;
+        }
+        let branches = patterns.into_par_iter().map(|pattern| {
+            let mut next = solver.clone();
+            next.feedback(guess, pattern);
+            next.ranked_guesses(strategy, SEARCH_WIDTH).into_iter()
+                .find_map(|next_guess| Self::search_node(&next, next_guess, strategy, depth - 1))
+                .map(|node| (pattern, node))
+        }).collect::<Option<BTreeMap<Pattern, TreeNode>>>()?;
+        Some(TreeNode { guess, branches })
+    }
+
+    fn build_node(solver: &Solver, guess: Word, strategy: Strategy) -> TreeNode {
+        let patterns = solver.solution_space().iter()
+            .map(|solution| score(&guess, solution))
+            .filter(|pattern| *pattern != Pattern::all_green())
+            .collect::<BTreeSet<Pattern>>();
+        let branches = patterns.into_par_iter().map(|pattern| {
+            let mut next = solver.clone();
+            next.feedback(guess, pattern);
+            let next_guess = next.guess_with(strategy);
+            (pattern, Self::build_node(&next, next_guess, strategy))
+        }).collect();
+        TreeNode { guess, branches }
+    }
+
+    /// Plays the tree against `solution`.
+    ///
+    /// # Returns
+    /// The guesses made, the last one being `solution`, or `None` if the tree has no branch
+    /// for a feedback on the way, i.e. `solution` is not among the solutions it was built for.
+    pub fn play(&self, solution: &Word) -> Option<Vec<Word>> {
+        let mut node = &self.root;
+        let mut guesses = vec![node.guess];
+        loop {
+            let pattern = score(&node.guess, solution);
+            if pattern == Pattern::all_green() {
+                return Some(guesses);
+            }
+            node = node.branches.get(&pattern)?;
+            guesses.push(node.guess);
+        }
+    }
+
+    /// Plays the tree against every word of `solutions`.
+    pub fn stats(&self, solutions: &[Word]) -> TreeStats {
+        let games = solutions.par_iter()
+            .map(|solution| (solution, self.play(solution).map(|guesses| guesses.len())))
+            .collect::<Vec<_>>();
+        let depths = games.iter().filter_map(|(_, depth)| *depth).collect::<Vec<usize>>();
+        TreeStats {
+            solutions: solutions.len(),
+            average: depths.iter().sum::<usize>() as f64 / depths.len().max(1) as f64,
+            max_depth: depths.iter().copied().max().unwrap_or(0),
+            failures: depths.iter().filter(|d| **d > SimulatedGame::MAX_ROUNDS as usize).count(),
+            unreachable: games.iter().filter(|(_, depth)| depth.is_none()).map(|(s, _)| **s).collect(),
+        }
+    }
+
+    /// Checks that the tree is a valid play book for a game with the allowed guesses `words`
+    /// and the solutions `solutions`: every solution is reached within `max_rounds` guesses,
+    /// and every guess is an allowed word. With `hard_mode`, every guess must also be
+    /// consistent with the feedback on the path to it.
+    pub fn verify(&self, words: &[Word], solutions: &[Word], max_rounds: usize, hard_mode: bool) -> TreeVerification {
+        let mut verification = TreeVerification { solutions: solutions.len(), hard_mode, ..TreeVerification::default() };
+        for solution in solutions {
+            match self.play(solution) {
+                None => verification.unreachable.push(*solution),
+                Some(guesses) if guesses.len() > max_rounds => verification.too_deep.push((*solution, guesses)),
+                Some(_) => {}
+            }
+        }
+        let words = words.iter().collect::<HashSet<&Word>>();
+        let mut stack = vec![(&self.root, GameState::new())];
+        while let Some((node, state)) = stack.pop() {
+            if !words.contains(&node.guess) {
+                verification.illegal.push((state.clone(), node.guess));
+            }
+            if hard_mode && !state.history.iter().all(|(guess, pattern)| score(guess, &node.guess) == *pattern) {
+                verification.hard_mode_violations.push((state.clone(), node.guess));
+            }
+            for (pattern, child) in node.branches.iter().rev() {
+                let mut next = state.clone();
+                next.push(node.guess, *pattern);
+                stack.push((child, next));
+            }
+        }
+        verification
+    }
+
+    /// The number of nodes in the tree.
+    pub fn nodes(&self) -> usize {
+        fn count(node: &TreeNode) -> usize {
+            1 + node.branches.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+
+    /// Writes the tree in `format`.
+    ///
+    /// # Binary Layout
+    ///
+    /// ```text
+    /// MAGIC | strategy: u8 | flags: u8 | node
+    /// node = guess length: u8 | guess (UTF-8) | branches: u8 | (pattern index: u8, node)*
+    /// ```
+    ///
+    /// Strategies are numbered in the order of [`Strategy::ALL`], without their parameters,
+    /// such as the rollouts of [`Strategy::MonteCarlo`]. The lowest bit of the flags
+    /// is set for hard-mode trees. Every node takes only a few
+    /// bytes, so a tree takes little more space than the list of its solutions.
+    ///
+    /// # Errors
+    /// If writing fails.
+    pub fn write<W: Write>(&self, mut out: W, format: TreeFormat) -> io::Result<()> {
+        match format {
+            TreeFormat::Binary => {
+                out.write_all(MAGIC)?;
+                let strategy = Strategy::ALL.iter()
+                    .position(|s| mem::discriminant(s) == mem::discriminant(&self.strategy)).unwrap();
+                out.write_all(&[strategy as u8, self.hard_mode as u8])?;
+                Self::write_node(&self.root, &mut out)?;
+            }
+            #[cfg(feature = "serde")]
+            TreeFormat::Json => {
+                serde_json::to_writer(&mut out, self)?;
+                writeln!(out)?;
+            }
+        }
+        out.flush()
+    }
+
+    fn write_node<W: Write>(node: &TreeNode, out: &mut W) -> io::Result<()> {
+        let guess = node.guess.to_string();
+        out.write_all(&[guess.len() as u8])?;
+        out.write_all(guess.as_bytes())?;
+        out.write_all(&[node.branches.len() as u8])?;
+        for (pattern, child) in &node.branches {
+            out.write_all(&[pattern.index() as u8])?;
+            Self::write_node(child, out)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the tree as a graph for visualization: every node is labeled by its guess and
+    /// every edge by the feedback leading to it. Leaves, where the guess is the only remaining
+    /// candidate, are highlighted.
+    ///
+    /// # Arguments
+    /// * `out` - Where to write the graph.
+    /// * `format` - The graph description language.
+    /// * `depth` - The number of guesses to include, or `None` for the whole tree. Full trees
+    ///   for large word lists have thousands of nodes, which is too much for most renderers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::tree::{DecisionTree, GraphFormat};
+    /// use wordl_rust_bot::{Strategy, Word};
+    ///
+    /// let words = ["tears", "bears"].map(Word::from_str);
+    /// let tree = DecisionTree::build(&words, &words, Strategy::Entropy, Some(words[0]));
+    /// let mut dot = Vec::new();
+    /// tree.write_graph(&mut dot, GraphFormat::Dot, None).unwrap();
+    /// assert!(String::from_utf8(dot).unwrap().contains("n0 -> n1 [label=\"bgggg\"];"));
+    /// ```
+    pub fn write_graph<W: Write>(&self, mut out: W, format: GraphFormat, depth: Option<usize>) -> io::Result<()> {
+        match format {
+            GraphFormat::Dot => {
+                writeln!(out, "digraph tree {{")?;
+                writeln!(out, "  node [shape=box, fontname=monospace];")?;
+                writeln!(out, "  edge [fontname=monospace];")?;
+            }
+            GraphFormat::Mermaid => writeln!(out, "flowchart TD")?,
+        }
+        let mut next_id = 0;
+        Self::write_graph_node(&self.root, format, depth.unwrap_or(usize::MAX), &mut next_id, &mut out)?;
+        if format == GraphFormat::Dot {
+            writeln!(out, "}}")?;
+        }
+        out.flush()
+    }
+
+    /// Writes `node` with the id `next_id`, and its branches up to `depth` guesses, with the
+    /// following ids.
+    fn write_graph_node<W: Write>(node: &TreeNode, format: GraphFormat, depth: usize, next_id: &mut usize,
+                                  out: &mut W) -> io::Result<()> {
+        let id = *next_id;
+        *next_id += 1;
+        let leaf = node.branches.is_empty();
+        match (format, leaf) {
+            (GraphFormat::Dot, false) => writeln!(out, "  n{} [label=\"{}\"];", id, node.guess)?,
+            (GraphFormat::Dot, true) => writeln!(out, "  n{} [label=\"{}\", style=filled, fillcolor=palegreen];", id, node.guess)?,
+            (GraphFormat::Mermaid, false) => writeln!(out, "  n{}[\"{}\"]", id, node.guess)?,
+            (GraphFormat::Mermaid, true) => writeln!(out, "  n{}([\"{}\"])", id, node.guess)?,
+        }
+        if depth <= 1 {
+            return Ok(());
+        }
+        for (pattern, child) in &node.branches {
+            let child_id = *next_id;
+            Self::write_graph_node(child, format, depth - 1, next_id, out)?;
+            match format {
+                GraphFormat::Dot => writeln!(out, "  n{} -> n{} [label=\"{}\"];", id, child_id, pattern.letters())?,
+                GraphFormat::Mermaid => writeln!(out, "  n{} -- {} --> n{}", id, pattern.letters(), child_id)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a tree written by [`DecisionTree::write`], in either format.
+    ///
+    /// # Errors
+    /// If reading fails or the input is not a valid tree.
+    pub fn read<R: Read>(mut input: R) -> io::Result<DecisionTree> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        let invalid = |reason: String| io::Error::new(io::ErrorKind::InvalidData, reason);
+        let (version, mut rest) = if let Some(rest) = bytes.strip_prefix(MAGIC) {
+            (2, rest)
+        } else if let Some(rest) = bytes.strip_prefix(MAGIC_V1) {
+            (1, rest)
+        } else {
+            #[cfg(feature = "serde")]
+            return serde_json::from_slice(&bytes).map_err(|e| invalid(e.to_string()));
+            #[cfg(not(feature = "serde"))]
+            return Err(invalid("not a binary decision tree".to_string()));
+        };
+        let mut take = |n: usize| -> io::Result<&[u8]> {
+            if rest.len() < n {
+                return Err(invalid("decision tree ends early".to_string()));
+            }
+            let (head, tail) = rest.split_at(n);
+            rest = tail;
+            Ok(head)
+        };
+        let strategy = *Strategy::ALL.get(take(1)?[0] as usize)
+            .ok_or_else(|| invalid("unknown strategy".to_string()))?;
+        let hard_mode = version >= 2 && take(1)?[0] & 1 == 1;
+        let root = Self::read_node(&mut take)?;
+        Ok(DecisionTree { strategy, hard_mode, root })
+    }
+
+    fn read_node<'b, F>(take: &mut F) -> io::Result<TreeNode> where F: FnMut(usize) -> io::Result<&'b [u8]> {
+        let length = take(1)?[0] as usize;
+        let guess = std::str::from_utf8(take(length)?)
+            .ok()
+            .and_then(|guess| Word::try_from_str(guess).ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid guess in decision tree"))?;
+        let mut branches = BTreeMap::new();
+        for _ in 0..take(1)?[0] {
+            let index = take(1)?[0] as usize;
+            if index >= Pattern::MAX {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid pattern in decision tree"));
+            }
+            branches.insert(Pattern::from_index(index), Self::read_node(take)?);
+        }
+        Ok(TreeNode { guess, branches })
+    }
+}
+
+/// The problems [`DecisionTree::verify`] found in a tree. Every problem comes with a
+/// counterexample.
+///
+/// # Fields
+/// * `solutions` - The number of solutions checked.
+/// * `hard_mode` - Whether hard-mode compliance was checked.
+/// * `unreachable` - Solutions the tree never guesses, because it has no branch for a
+///   feedback on the way.
+/// * `too_deep` - Solutions that take more than the allowed number of guesses, with the
+///   guesses the tree makes for them.
+/// * `illegal` - Guesses that are not in the word list, with the state of the game in which
+///   the tree makes them.
+/// * `hard_mode_violations` - Guesses that are inconsistent with the feedback on the path to
+///   them, with that state. Only checked for hard mode.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeVerification {
+    pub solutions: usize,
+    pub hard_mode: bool,
+    pub unreachable: Vec<Word>,
+    pub too_deep: Vec<(Word, Vec<Word>)>,
+    pub illegal: Vec<(GameState, Word)>,
+    pub hard_mode_violations: Vec<(GameState, Word)>,
+}
+
+impl TreeVerification {
+    /// The number of counterexamples printed per kind of problem.
+    const EXAMPLES: usize = 10;
+
+    /// The total number of problems.
+    pub fn problems(&self) -> usize {
+        self.unreachable.len() + self.too_deep.len() + self.illegal.len() + self.hard_mode_violations.len()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.problems() == 0
+    }
+
+    pub fn print(&self) {
+        fn section<T>(name: &str, problems: &[T], format: impl Fn(&T) -> String) {
+            let mark = if problems.is_empty() { "pass" } else { "FAIL" };
+            println!("{}  {} ({} problems)", mark, name, problems.len());
+            for problem in problems.iter().take(TreeVerification::EXAMPLES) {
+                println!("      {}", format(problem));
+            }
+            if problems.len() > TreeVerification::EXAMPLES {
+                println!("      ...");
+            }
+        }
+        let state = |state: &GameState| if state.round() == 0 { "start".to_string() } else { state.to_string() };
+        println!("{}", style::bold(format_args!("Verifying the tree against {} solutions:", self.solutions)));
+        section("every solution is reachable", &self.unreachable, |w| w.to_string());
+        section("every solution within the round limit", &self.too_deep, |(w, guesses)| {
+            format!("{}: {}", w, guesses.iter().map(Word::to_string).collect::<Vec<_>>().join(", "))
+        });
+        section("every guess is in the word list", &self.illegal, |(s, w)| format!("{} → {}", state(s), w));
+        if self.hard_mode {
+            section("every guess is allowed in hard mode", &self.hard_mode_violations,
+                    |(s, w)| format!("{} → {}", state(s), w));
+        } else {
+            println!("skip  every guess is allowed in hard mode");
+        }
+        if self.passed() {
+            println!("{}", style::bold("Passed."));
+        } else {
+            println!("{}", style::bold(format_args!("Failed with {} problems.", self.problems())));
+        }
+    }
+}
+
+/// Follows a game through a [`DecisionTree`], so that the tree's guess for the current state
+/// can be looked up instead of computed. Once the game leaves the tree, because a guess other
+/// than the tree's was made or the feedback has no branch, the cursor has no guess anymore.
+#[derive(Clone, Debug)]
+pub struct TreeCursor {
+    tree: Arc<DecisionTree>,
+    path: Vec<Pattern>,
+    on_tree: bool,
+}
+
+impl TreeCursor {
+    /// Starts at the root of `tree`.
+    pub fn new(tree: Arc<DecisionTree>) -> TreeCursor {
+        TreeCursor { tree, path: Vec::new(), on_tree: true }
+    }
+
+    fn node(&self) -> Option<&TreeNode> {
+        if !self.on_tree {
+            return None;
+        }
+        let mut node = &self.tree.root;
+        for pattern in &self.path {
+            node = node.branches.get(pattern)?;
+        }
+        Some(node)
+    }
+
+    /// The tree's guess for the current state, or `None` if the game has left the tree.
+    pub fn guess(&self) -> Option<Word> {
+        self.node().map(|node| node.guess)
+    }
+
+    /// Enters that `guess` got the feedback `pattern`.
+    pub fn advance(&mut self, guess: &Word, pattern: Pattern) {
+        let on_tree = self.node()
+            .is_some_and(|node| node.guess == *guess && node.branches.contains_key(&pattern));
+        if on_tree {
+            self.path.push(pattern);
+        } else {
+            self.on_tree = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::word::sample_words;
+
+    #[test]
+    fn test_build_tree() {
+        let words = ["tears", "bears", "gears", "fears", "crane", "bagel", "slimy"].map(Word::from_str);
+        let solutions = &words[..4];
+        for strategy in Strategy::ALL {
+            let tree = DecisionTree::build(&words, solutions, strategy, None);
+            let stats = tree.stats(solutions);
+            assert!(stats.unreachable.is_empty());
+            assert_eq!(stats.failures, 0);
+        }
+        let tree = DecisionTree::build(&words, solutions, Strategy::Entropy, Some(Word::from_str("slimy")));
+        assert_eq!(tree.root.guess, Word::from_str("slimy"));
+        assert_eq!(tree.play(&Word::from_str("fears")).unwrap()[0], Word::from_str("slimy"));
+        assert_eq!(tree.play(&Word::from_str("zzzzz")), None);
+
+        let mut bytes = Vec::new();
+        tree.write(&mut bytes, TreeFormat::Binary).unwrap();
+        assert_eq!(DecisionTree::read(bytes.as_slice()).unwrap(), tree);
+        #[cfg(feature = "serde")]
+        {
+            let mut json = Vec::new();
+            tree.write(&mut json, TreeFormat::Json).unwrap();
+            assert_eq!(DecisionTree::read(json.as_slice()).unwrap(), tree);
+        }
+        assert!(DecisionTree::read(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_build_hard_mode() {
+        // After `tears`, the solutions only differ in the first letter. In hard mode, they can
+        // only be guessed one by one, which takes too long for all of them.
+        let mut words = ["tears", "bears", "dears", "fears", "gears", "hears", "lears", "nears", "pears",
+                         "sears", "wears", "years"].map(Word::from_str).to_vec();
+        let (tree, complete) = DecisionTree::build_hard_mode(&words, &words, Strategy::Entropy, Some(words[0]));
+        assert!(!complete);
+        assert!(tree.hard_mode);
+        assert!(tree.stats(&words).failures > 0);
+        // Without hard mode, words covering several of the letters help.
+        words.extend(["bdfgh", "lnpwy"].map(Word::from_str));
+        let tree = DecisionTree::build(&words, &words[..12], Strategy::Entropy, Some(words[0]));
+        assert_eq!(tree.stats(&words[..12]).failures, 0);
+
+        let words = sample_words();
+        let (tree, complete) = DecisionTree::build_hard_mode(&words, &words, Strategy::Minimax, None);
+        assert!(complete);
+        let mut bytes = Vec::new();
+        tree.write(&mut bytes, TreeFormat::Binary).unwrap();
+        assert_eq!(DecisionTree::read(bytes.as_slice()).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_verify() {
+        let words = ["tears", "bears", "gears", "fears", "crane", "bagel", "slimy"].map(Word::from_str);
+        let solutions = &words[..4];
+        let slimy = Some(Word::from_str("slimy"));
+        let tree = DecisionTree::build(&words, solutions, Strategy::Entropy, slimy);
+        assert!(tree.verify(&words, solutions, 6, false).passed());
+        // `crane` is not a solution of this tree, and no guess after `slimy` tells four
+        // solutions apart.
+        let verification = tree.verify(&words, &words[..5], 2, false);
+        assert_eq!(verification.unreachable, vec![Word::from_str("crane")]);
+        assert!(!verification.too_deep.is_empty());
+        assert_eq!(verification.illegal, vec![]);
+
+        let (mut tree, _) = DecisionTree::build_hard_mode(&words, solutions, Strategy::Entropy, slimy);
+        assert!(tree.verify(&words, solutions, 6, true).passed());
+
+        tree.root.branches.values_mut().next().unwrap().guess = Word::from_str("xylyl");
+        let verification = tree.verify(&words, solutions, 6, true);
+        assert_eq!(verification.illegal[0].0.to_string(), "slimy:ybbbb");
+        assert_eq!(verification.illegal[0].1, Word::from_str("xylyl"));
+        // The guesses below `xylyl` are checked against its feedback as stored in the tree.
+        assert_eq!(verification.hard_mode_violations[0], verification.illegal[0]);
+    }
+
+    #[test]
+    fn test_tree_cursor() {
+        let words = ["tears", "bears", "gears", "fears", "crane", "bagel", "slimy"].map(Word::from_str);
+        let tree = Arc::new(DecisionTree::build(&words, &words[..4], Strategy::Entropy, None));
+        let solution = Word::from_str("fears");
+        let mut cursor = TreeCursor::new(Arc::clone(&tree));
+        let mut guesses = Vec::new();
+        while let Some(guess) = cursor.guess() {
+            guesses.push(guess);
+            if guess == solution {
+                break;
+            }
+            cursor.advance(&guess, score(&guess, &solution));
+        }
+        assert_eq!(Some(guesses), tree.play(&solution));
+
+        // Leaving the tree with another guess.
+        let mut cursor = TreeCursor::new(tree);
+        let slimy = Word::from_str("slimy");
+        cursor.advance(&slimy, score(&slimy, &solution));
+        assert_eq!(cursor.guess(), None);
+    }
+}
@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+use crate::error::WordleError;
+use crate::ffi::WordLists;
+use crate::game::Solver;
+use crate::pattern::Pattern;
+use crate::word::{read_words, Word};
+
+/// The game of the browser interface, which owns its word lists like [`crate::ffi::Wordle`].
+struct Game {
+    // Declared first, so that it is dropped before the lists it borrows.
+    solver: Solver<'static>,
+    _lists: WordLists,
+}
+
+thread_local! {
+    /// The game started by the last call to [`init`]. WebAssembly in the browser runs on a
+    /// single thread, so there is one game per page.
+    static GAME: RefCell<Option<Game>> = const { RefCell::new(None) };
+}
+
+fn start(words: &str, solutions: Option<&str>) -> Result<usize, WordleError> {
+    let words = read_words("words", words.as_bytes())?;
+    let solutions = match solutions {
+        Some(solutions) => read_words("solutions", solutions.as_bytes())?,
+        None => words.clone(),
+    };
+    let count = solutions.len();
+    let lists = WordLists::new(words, solutions);
+    // SAFETY: the game drops the solver before the lists.
+    let solver = unsafe { lists.solver() };
+    GAME.with_borrow_mut(|game| *game = Some(Game { solver, _lists: lists }));
+    Ok(count)
+}
+
+fn enter(guess: &str, pattern: &str) -> Result<Option<usize>, WordleError> {
+    let guess = Word::try_from_str_ignore_case(guess)?;
+    let pattern = Pattern::try_from_str(pattern)?;
+    Ok(GAME.with_borrow_mut(|game| game.as_mut().map(|game| {
+        game.solver.feedback(guess, pattern);
+        game.solver.solution_space().len()
+    })))
+}
+
+fn best(count: usize) -> Option<Vec<String>> {
+    GAME.with_borrow(|game| game.as_ref().map(|game| {
+        game.solver.suggestions(count).iter().map(|e| e.word.to_string()).collect()
+    }))
+}
+
+/// Starts a new game, replacing the last one. `words` and `solutions` have one word per line;
+/// without `solutions`, every word can be the solution.
+///
+/// # Returns
+/// The number of possible solutions.
+///
+/// # Errors
+/// If a list is empty or has a line that is not a word.
+#[wasm_bindgen]
+pub fn init(words: &str, solutions: Option<String>) -> Result<usize, JsError> {
+    Ok(start(words, solutions.as_deref())?)
+}
+
+/// Enters the feedback `pattern`, e.g. `"bygby"`, that the game gave for `guess`.
+///
+/// # Returns
+/// The number of solutions that are still possible.
+///
+/// # Errors
+/// If `guess` is not a word, `pattern` is not a pattern, or [`init`] was not called.
+#[wasm_bindgen]
+pub fn feedback(guess: &str, pattern: &str) -> Result<usize, JsError> {
+    enter(guess, pattern)?.ok_or_else(|| JsError::new("call init before feedback"))
+}
+
+/// The `count` guesses with the highest entropy, best first. The ranking runs on the calling
+/// thread, so call it from a web worker to keep the page responsive for large word lists.
+///
+/// # Errors
+/// If [`init`] was not called.
+#[wasm_bindgen]
+pub fn suggest(count: usize) -> Result<Vec<String>, JsError> {
+    best(count).ok_or_else(|| JsError::new("call init before suggest"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wasm() {
+        assert_eq!(best(1), None);
+        assert!(start("tears\nnope\n", None).is_err());
+        assert_eq!(start("tears\nbears\ngears\ncrane\nslimy\n", None).unwrap(), 5);
+        assert_eq!(enter("CRANE", "bygby").unwrap(), Some(3));
+        assert!(enter("crane", "xx").is_err());
+        assert_eq!(best(3).unwrap().len(), 3);
+        assert_eq!(start("tears\ncrane\n", Some("tears\n")).unwrap(), 1);
+        assert_eq!(best(1).unwrap(), ["tears"]);
+    }
+}
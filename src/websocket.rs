@@ -0,0 +1,140 @@
+use std::io::{self, Write};
+
+/// The GUID a server appends to the key of a WebSocket handshake, see RFC 6455, section 1.3.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The value of the `Sec-WebSocket-Accept` header that answers a handshake with the
+/// `Sec-WebSocket-Key` header `key`: the Base64 encoded SHA-1 hash of the key and [`GUID`].
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::websocket::accept_key;
+///
+/// assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+/// ```
+pub fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{}{}", key.trim(), GUID).as_bytes()))
+}
+
+/// Writes the response that accepts a WebSocket handshake with the key `key`.
+///
+/// # Errors
+/// If writing fails.
+pub fn write_handshake<W: Write>(mut out: W, key: &str) -> io::Result<()> {
+    write!(out, "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                 Sec-WebSocket-Accept: {}\r\n\r\n", accept_key(key))?;
+    out.flush()
+}
+
+/// Writes `text` as a single, unmasked text frame, as sent by servers.
+///
+/// # Errors
+/// If writing fails.
+pub fn write_text<W: Write>(out: W, text: &str) -> io::Result<()> {
+    write_frame(out, 0x1, text.as_bytes())
+}
+
+/// Writes a close frame without a status code.
+///
+/// # Errors
+/// If writing fails.
+pub fn write_close<W: Write>(out: W) -> io::Result<()> {
+    write_frame(out, 0x8, &[])
+}
+
+/// Writes a final frame with the opcode `opcode` and the payload `payload`.
+fn write_frame<W: Write>(mut out: W, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut header = vec![0x80 | opcode];
+    match payload.len() {
+        length @ 0..=125 => header.push(length as u8),
+        length @ 126..=0xFFFF => {
+            header.push(126);
+            header.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            header.push(127);
+            header.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+    out.write_all(&header)?;
+    out.write_all(payload)?;
+    out.flush()
+}
+
+/// The SHA-1 hash of `data`. SHA-1 is only used because the WebSocket handshake requires it,
+/// not for anything that needs to be secure.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+    for block in message.chunks(64) {
+        let mut w = [0_u32; 80];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes(block[4 * i..4 * i + 4].try_into().unwrap());
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, w) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*w);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+        }
+        for (h, x) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(x);
+        }
+    }
+    let mut digest = [0; 20];
+    for (chunk, h) in digest.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&h.to_be_bytes());
+    }
+    digest
+}
+
+/// Encodes `data` in standard Base64 with padding.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        assert_eq!(base64(b"ab"), "YWI=");
+        let hex = sha1(b"abc").iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(hex, "a9993e364706816aba3e25717850c26c9cd0d89d");
+
+        let mut frame = Vec::new();
+        write_text(&mut frame, "hi").unwrap();
+        assert_eq!(frame, [0x81, 2, b'h', b'i']);
+        let mut frame = Vec::new();
+        write_text(&mut frame, &"x".repeat(300)).unwrap();
+        assert_eq!(frame[..4], [0x81, 126, 1, 44]);
+    }
+}
@@ -1,34 +1,110 @@
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
-use std::io;
+use std::io::Read;
 use std::ops::Index;
+use std::sync::RwLock;
+use miette::Diagnostic;
+use thiserror::Error;
+use crate::error::{WordListError, WordleError};
+use crate::input;
+use crate::locale::normalize;
 
 /// The fixed length of words in the Wordle game. In Wordle, all valid words have
 /// a length of 5 characters, though this for this implementation any other constant
 /// word size would work.
 pub const WORD_LENGTH: usize = 5;
 
+/// The letters outside ASCII seen in any word so far. Letter `i` of the table is stored in a
+/// [`Word`] as the code `0x80 + i`, so that words of any alphabet take one byte per letter.
+static LETTERS: RwLock<Vec<char>> = RwLock::new(Vec::new());
+
+/// The most letters outside ASCII that words may use, over all word lists together.
+pub const MAX_LETTERS: usize = 0x80;
+
+/// The code of `letter` in a [`Word`]: lowercase ASCII letters are stored as themselves,
+/// others as their index in [`LETTERS`] plus `0x80`. Returns `None` if the table is full.
+pub(crate) fn encode(letter: char) -> Option<u8> {
+    if letter.is_ascii() {
+        return Some(letter as u8);
+    }
+    let letters = LETTERS.read().unwrap_or_else(|e| e.into_inner());
+    if let Some(i) = letters.iter().position(|l| *l == letter) {
+        return Some(0x80 + i as u8);
+    }
+    drop(letters);
+    let mut letters = LETTERS.write().unwrap_or_else(|e| e.into_inner());
+    let i = match letters.iter().position(|l| *l == letter) {
+        Some(i) => i,
+        None if letters.len() < MAX_LETTERS => {
+            letters.push(letter);
+            letters.len() - 1
+        }
+        None => return None,
+    };
+    Some(0x80 + i as u8)
+}
+
+/// The letter with the code `code` in a [`Word`], see [`encode`].
+pub(crate) fn decode(code: u8) -> char {
+    if code.is_ascii() {
+        char::from(code)
+    } else {
+        LETTERS.read().unwrap_or_else(|e| e.into_inner())[(code - 0x80) as usize]
+    }
+}
+
+/// Describes why a string is not a valid [`Word`].
+///
+/// # Variants
+/// * `Length` - The string does not have exactly [`WORD_LENGTH`] characters.
+/// * `Letter` - The character at `position` (counting characters, not bytes) is not a
+///   lowercase letter.
+/// * `Alphabet` - The letter at `position` would be one more than the [`MAX_LETTERS`] letters
+///   outside ASCII that all words together may use.
+#[derive(Debug, Error, Diagnostic, Clone, PartialEq, Eq)]
+pub enum WordError {
+    #[error("word <{word}> has {length} letters instead of {WORD_LENGTH}")]
+    #[diagnostic(help("words must have exactly {WORD_LENGTH} letters"))]
+    Length { word: String, length: usize },
+    #[error("word <{word}> contains '{letter}', which is not a lowercase letter")]
+    #[diagnostic(help("words may only contain lowercase letters of any alphabet, no digits, \
+                       punctuation or hyphens"))]
+    Letter { word: String, letter: char, position: usize },
+    #[error("word <{word}> contains '{letter}', but the word lists already use {MAX_LETTERS} letters besides a to z")]
+    #[diagnostic(help("words of all lists together may use at most {MAX_LETTERS} letters outside ASCII"))]
+    Alphabet { word: String, letter: char, position: usize },
+}
+
 /// Represents a word used in the Wordle game.
 ///
-/// The `Word` struct stores a word as an array of characters with a fixed length of
-/// `WORD_LENGTH`. This struct is used for both guesses and possible solutions in the game.
+/// The `Word` struct stores a word as an array of lowercase letters with a fixed length
+/// of `WORD_LENGTH`, one byte each. Letters are not limited to `a` to `z`: word lists in German,
+/// Spanish or Russian work just as well, see [`crate::alphabet::Alphabet`]. This struct is used
+/// for both guesses and possible solutions in the game.
 ///
 /// # Fields
-/// * `chars` - An array of `char` representing the individual characters of the word.
+/// * `letters` - The codes of the individual letters of the word: the ASCII code for `a` to
+///   `z`, and `0x80` and up for every other letter, in the order they were first read.
 ///
 /// # Derives
 /// * `Clone` - Allows the `Word` to be cloned.
 /// * `Copy` - Enables the `Word` to be copied by value.
 /// * `Eq`, `PartialEq` - Allows for equality comparisons between `Word` instances.
 ///
+/// Words are ordered alphabetically, by the code points of their letters.
+///
 /// # Example
-/// ```rust
+/// ```
+/// use wordl_rust_bot::Word;
+///
 /// let word = Word::from_str("crane");
-/// assert_eq!(word[0], 'c');
-/// assert_eq!(word.chars.len(), WORD_LENGTH);
+/// assert_eq!(word[0], b'c');
+/// assert_eq!(word.letter(0), 'c');
+/// assert_eq!(word.to_string(), "crane");
 /// ```
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Word {
-    chars: [char; WORD_LENGTH],
+    letters: [u8; WORD_LENGTH],
 }
 
 impl Word {
@@ -42,50 +118,142 @@ impl Word {
     /// * `word` - A string slice (`&str`) representing the word to be converted into a `Word`.
     ///
     /// # Panics
-    /// This function will panic if the length of the input string, after trimming,
-    /// is not equal to `WORD_LENGTH`.
+    /// This function will panic if the input is not a valid word, see [`Word::try_from_str`].
     ///
     /// # See Also
     /// * [`WORD_LENGTH`] - The constant representing the fixed length of a word.
+    #[allow(clippy::should_implement_trait)] // panics instead of returning a `Result`
     pub fn from_str(word: &str) -> Word {
+        Word::try_from_str(word).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates a `Word` from a string slice, like [`Word::from_str`], but reports malformed
+    /// input instead of panicking.
+    ///
+    /// # Errors
+    /// * [`WordError::Length`] if the trimmed input does not have exactly [`WORD_LENGTH`] characters.
+    /// * [`WordError::Letter`] if one of the characters is not a letter, or an uppercase one.
+    /// * [`WordError::Alphabet`] if a letter outside ASCII is new, but there is no code left for it.
+    pub fn try_from_str(word: &str) -> Result<Word, WordError> {
         let word = word.trim();
         let chars = word.chars().collect::<Vec<char>>();
-        assert_eq!(chars.len(), WORD_LENGTH, "word <{}> has bad length", word);
-        let mut word = Word{ chars: ['?'; WORD_LENGTH]};
-        for i in 0..WORD_LENGTH {
-            word.chars[i] = chars[i];
+        if chars.len() != WORD_LENGTH {
+            return Err(WordError::Length { word: word.to_string(), length: chars.len() });
+        }
+        if let Some(position) = chars.iter().position(|c| !c.is_alphabetic() || c.is_uppercase()) {
+            return Err(WordError::Letter { word: word.to_string(), letter: chars[position], position });
+        }
+        let mut letters = [0; WORD_LENGTH];
+        for (position, letter) in chars.iter().enumerate() {
+            letters[position] = encode(*letter)
+                .ok_or_else(|| WordError::Alphabet { word: word.to_string(), letter: *letter, position })?;
         }
-        word
+        Ok(Word { letters })
+    }
+
+    /// Like [`Word::try_from_str`], but lowercases the word first, so that `CRANE` and `Crane`
+    /// are read as `crane`. Used for words typed by the user. The word is converted by
+    /// [`normalize`], so that with Turkish rules `KIRAZ` is read as `kıraz`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wordl_rust_bot::Word;
+    ///
+    /// assert_eq!(Word::try_from_str_ignore_case("CraNe").unwrap(), Word::from_str("crane"));
+    /// assert!(Word::try_from_str("CraNe").is_err());
+    /// ```
+    pub fn try_from_str_ignore_case(word: &str) -> Result<Word, WordError> {
+        Word::try_from_str(&normalize(word))
+    }
+
+    /// The letter at `index`.
+    ///
+    /// # Panics
+    /// If `index` is not less than [`WORD_LENGTH`].
+    pub fn letter(&self, index: usize) -> char {
+        decode(self.letters[index])
+    }
+
+    /// The letters of the word.
+    pub fn letters(&self) -> [char; WORD_LENGTH] {
+        self.letters.map(decode)
+    }
+
+    /// The letters of the word as their codes, see [`Word`]. Words that only have the letters
+    /// `a` to `z` are their ASCII codes.
+    pub fn as_bytes(&self) -> &[u8; WORD_LENGTH] {
+        &self.letters
     }
 
 
     /// Reads a word from standard input and converts it into a `Word`.
     ///
-    /// This function reads a single line of input from the user, trims any leading or trailing whitespace,
-    /// and converts the resulting string into a `Word` using the `Word::from_str` function. If the input
-    /// cannot be read or is of incorrect length, the function will panic.
-    pub fn read() -> Word {
-        let mut line = String::new();
-        io::stdin().read_line(&mut line).expect("Read failed");
-        Word::from_str(&line)
+    /// This function reads a single line of input from the user and converts it into a `Word`
+    /// using [`Word::try_from_str_ignore_case`]. If the input is not a valid word, the problem is shown
+    /// and the user is asked again.
+    ///
+    /// # Errors
+    /// If standard input cannot be read or has ended, see [`input::read_line`].
+    pub fn read() -> Result<Word, WordleError> {
+        input::read_parsed(Word::try_from_str_ignore_case)
+    }
+}
+
+impl TryFrom<&str> for Word {
+    type Error = WordError;
+
+    fn try_from(word: &str) -> Result<Word, WordError> {
+        Word::try_from_str(word)
+    }
+}
+
+/// Words are serialized as plain strings, e.g. `"crane"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Word {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Word {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Word, D::Error> {
+        let word = String::deserialize(deserializer)?;
+        Word::try_from_str(&word).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Ord for Word {
+    fn cmp(&self, other: &Word) -> Ordering {
+        if self.letters.is_ascii() && other.letters.is_ascii() {
+            self.letters.cmp(&other.letters)
+        } else {
+            self.letters().cmp(&other.letters())
+        }
+    }
+}
+
+impl PartialOrd for Word {
+    fn partial_cmp(&self, other: &Word) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 impl Index<usize> for Word {
-    type Output = char;
+    type Output = u8;
 
-    /// Allows indexing into a `Word` using the `[]` syntax to access individual characters in the word.
+    /// Allows indexing into a `Word` using the `[]` syntax to access the code of individual
+    /// letters in the word, see [`Word::letter`] for the letter as a `char`.
     /// This implementation will panic if the index is out of bounds (i.e., greater than or equal to `WORD_LENGTH`).
     fn index(&self, index: usize) -> &Self::Output {
-        &self.chars[index]
+        &self.letters[index]
     }
 }
 
 impl Display for Word {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}{}{}{}",
-               self[0], self[1], self[2], self[3], self[4])
+        self.letters.iter().try_for_each(|letter| write!(f, "{}", decode(*letter)))
     }
 }
 
@@ -93,4 +261,143 @@ impl Debug for Word {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self)
     }
-}
\ No newline at end of file
+}
+
+/// Reads a word list with one word per line. Blank lines are ignored. A line may also give the
+/// frequency of its word after a tab, e.g. `crane\t1350`; the frequencies are ignored here,
+/// see [`crate::wordlist::WeightedList`] to keep them.
+///
+/// # Arguments
+/// * `name` - The name of the list, e.g. its file name, used in error messages.
+/// * `reader` - The source of the list.
+///
+/// # Errors
+/// If the list cannot be read, is empty, or contains a line that is not a valid word.
+/// In the latter case the error points at the offending line and column.
+///
+/// # See Also
+/// * [`read_words_lenient`] - to skip malformed lines instead.
+pub fn read_words<R: Read>(name: &str, reader: R) -> Result<Vec<Word>, WordListError> {
+    let mut skipped = Vec::new();
+    let words = read_words_lenient(name, reader, &mut skipped)?;
+    match skipped.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(words),
+    }
+}
+
+/// Reads a word list like [`read_words`], but skips lines that are not valid words instead
+/// of failing. For every skipped line, an [`WordListError::Entry`] is added to `skipped`.
+///
+/// # Errors
+/// If the list cannot be read, or if it does not contain a single valid word.
+pub fn read_words_lenient<R: Read>(name: &str, reader: R,
+                                   skipped: &mut Vec<WordListError>) -> Result<Vec<Word>, WordListError> {
+    let entries = read_entries(name, reader, Word::try_from_str, skipped)?;
+    Ok(entries.into_iter().map(|(word, _)| word).collect())
+}
+
+/// Reads a word list like [`read_words_lenient`], but lowercases every entry first, so that
+/// lists with entries such as `Crane` or `CRANE` work as well.
+///
+/// # Errors
+/// If the list cannot be read, or if it does not contain a single valid word.
+pub fn read_words_ignore_case<R: Read>(name: &str, reader: R,
+                                       skipped: &mut Vec<WordListError>) -> Result<Vec<Word>, WordListError> {
+    let entries = read_entries(name, reader, Word::try_from_str_ignore_case, skipped)?;
+    Ok(entries.into_iter().map(|(word, _)| word).collect())
+}
+
+/// Reads a word list, converting every line that is not blank with `parse`, together with the
+/// frequency given after a tab, if any. Lines with a malformed word or frequency are skipped.
+pub(crate) fn read_entries<R: Read>(name: &str, mut reader: R, parse: fn(&str) -> Result<Word, WordError>,
+                                    skipped: &mut Vec<WordListError>)
+                                    -> Result<Vec<(Word, Option<f64>)>, WordListError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)
+        .map_err(|source| WordListError::Io { name: name.to_string(), source })?;
+    let mut words = Vec::new();
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        let start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (entry, frequency) = match line.split_once('\t') {
+            Some((entry, frequency)) => (entry, Some(frequency.trim())),
+            None => (line, None),
+        };
+        let frequency = match frequency.map(str::parse::<f64>) {
+            Some(Ok(frequency)) if frequency.is_finite() && frequency >= 0.0 => Some(frequency),
+            Some(_) => {
+                let value = frequency.unwrap_or("").to_string();
+                skipped.push(WordListError::Frequency { name: name.to_string(), line: i + 1, value });
+                continue;
+            }
+            None => None,
+        };
+        match parse(entry) {
+            Ok(word) => words.push((word, frequency)),
+            Err(e) => skipped.push(WordListError::entry(name, &text, i + 1, start, e)),
+        }
+    }
+    if words.is_empty() {
+        return Err(WordListError::Empty { name: name.to_string() });
+    }
+    Ok(words)
+}
+
+/// The word list of the unit tests: `tears`, `bears` and `gears` only differ in their first
+/// letter and take several guesses to tell apart, which `crane` and `slimy` help with.
+#[cfg(test)]
+pub(crate) fn sample_words() -> Vec<Word> {
+    sample_words_with(&[])
+}
+
+/// Like [`sample_words`], with `extra` words for the tests that need them, e.g. an anagram
+/// such as `stare` or words with repeated letters such as `geese`.
+#[cfg(test)]
+pub(crate) fn sample_words_with(extra: &[&str]) -> Vec<Word> {
+    ["tears", "bears", "gears", "crane", "slimy"].iter().chain(extra).map(|word| Word::from_str(word)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pattern::Pattern;
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(Word::try_from(" crane\n").unwrap(), Word::from_str("crane"));
+        assert_eq!(Word::try_from_str("abc"),
+                   Err(WordError::Length { word: "abc".to_string(), length: 3 }));
+        assert_eq!(Word::try_from_str("ab-cd"),
+                   Err(WordError::Letter { word: "ab-cd".to_string(), letter: '-', position: 2 }));
+        assert_eq!(Word::try_from_str("abläu").unwrap().letter(3), 'ä');
+        assert!(Word::from_str("äpfel") > Word::from_str("zebra"));
+        assert_eq!(Word::from_str("слово").to_string(), "слово");
+        assert_eq!(Word::try_from_str("ab1cd"),
+                   Err(WordError::Letter { word: "ab1cd".to_string(), letter: '1', position: 2 }));
+        assert!(Word::try_from_str("Crane").is_err());
+    }
+
+    #[test]
+    fn test_read_words_lenient() {
+        let mut skipped = Vec::new();
+        let words = read_words_lenient("list", "crane\n\nab-cd\nslimy\n".as_bytes(), &mut skipped).unwrap();
+        assert_eq!(words, vec![Word::from_str("crane"), Word::from_str("slimy")]);
+        assert_eq!(skipped.len(), 1);
+        assert!(matches!(skipped[0], WordListError::Entry { line: 3, column: 3, .. }));
+        assert!(read_words("list", "crane\nab-cd\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_read_words_ignore_case() {
+        let mut skipped = Vec::new();
+        let words = read_words_ignore_case("list", "Crane\nSLIMY\nab-cd\n".as_bytes(), &mut skipped).unwrap();
+        assert_eq!(words, vec![Word::from_str("crane"), Word::from_str("slimy")]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(Pattern::try_from_str("BgYbb").unwrap(), Pattern::from_string("bgybb"));
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::str::FromStr;
+use crate::error::WordListError;
+use crate::locale::normalize;
+use crate::word::{read_entries, Word, WordError};
+
+/// A problem with an entry of a word list, found by [`validate`].
+///
+/// # Variants
+/// * `Invalid` - The entry is not a word, e.g. it has the wrong length or a digit in it.
+/// * `Case` - The entry is only a word once converted to lowercase (and with accents folded,
+///   if that is chosen), e.g. `Crane`.
+/// * `Duplicate` - The entry is the same word as the one on line `first`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    Invalid { line: usize, error: WordError },
+    Case { line: usize, entry: String, word: Word },
+    Duplicate { line: usize, word: Word, first: usize },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Invalid { line, error } => write!(f, "line {}: {}", line, error),
+            Issue::Case { line, entry, word } => write!(f, "line {}: <{}> is read as <{}>", line, entry, word),
+            Issue::Duplicate { line, word, first } => write!(f, "line {}: <{}> repeats line {}", line, word, first),
+        }
+    }
+}
+
+/// The result of checking a word list with [`validate`].
+///
+/// # Fields
+/// * `entries` - The number of lines that are not blank.
+/// * `words` - The words of the list, converted to lowercase and without duplicates, in the
+///   order of the list. This is the cleaned list.
+/// * `issues` - The problems found, in the order of the list.
+#[derive(Clone, Debug)]
+pub struct Validation {
+    pub entries: usize,
+    pub words: Vec<Word>,
+    pub issues: Vec<Issue>,
+}
+
+impl Validation {
+    /// Whether the list can be read as it is, without any entry being skipped or changed.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for Validation {
+    /// Prints the issues followed by a summary of how many there are of each kind.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        let count = |kind: fn(&Issue) -> bool| self.issues.iter().filter(|issue| kind(issue)).count();
+        write!(f, "{} entries, {} words: {} not words, {} not lowercase, {} duplicates",
+               self.entries, self.words.len(),
+               count(|issue| matches!(issue, Issue::Invalid { .. })),
+               count(|issue| matches!(issue, Issue::Case { .. })),
+               count(|issue| matches!(issue, Issue::Duplicate { .. })))
+    }
+}
+
+/// Checks every line of a word list for entries that are not words, that are not in lowercase,
+/// and that repeat an earlier entry. Blank lines and frequencies after a tab are ignored. Unlike [`crate::read_words`],
+/// this does not stop at the first problem, and also succeeds for lists without a valid word.
+///
+/// # Arguments
+/// * `name` - The name of the list, e.g. its file name, used in error messages.
+/// * `reader` - The source of the list.
+///
+/// # Errors
+/// If the list cannot be read.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::wordlist::validate;
+///
+/// let validation = validate("list", "crane\nCrane\nab-cd\n\nslimy\n".as_bytes()).unwrap();
+/// assert_eq!(validation.words.len(), 2);
+/// assert_eq!(validation.issues.len(), 3);
+/// assert_eq!(validation.issues[2].to_string(),
+///            "line 3: word <ab-cd> contains '-', which is not a lowercase letter");
+/// ```
+pub fn validate<R: Read>(name: &str, mut reader: R) -> Result<Validation, WordListError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)
+        .map_err(|source| WordListError::Io { name: name.to_string(), source })?;
+    let mut validation = Validation { entries: 0, words: Vec::new(), issues: Vec::new() };
+    let mut lines = HashMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let entry = line.split('\t').next().unwrap_or("").trim();
+        let line = i + 1;
+        if entry.is_empty() {
+            continue;
+        }
+        validation.entries += 1;
+        let word = match Word::try_from_str_ignore_case(entry) {
+            Ok(word) => word,
+            Err(error) => {
+                validation.issues.push(Issue::Invalid { line, error });
+                continue;
+            }
+        };
+        if word.to_string() != entry {
+            validation.issues.push(Issue::Case { line, entry: entry.to_string(), word });
+        }
+        match lines.get(&word) {
+            Some(&first) => validation.issues.push(Issue::Duplicate { line, word, first }),
+            None => {
+                lines.insert(word, line);
+                validation.words.push(word);
+            }
+        }
+    }
+    Ok(validation)
+}
+
+/// Merges word lists into one, for maintaining custom lists.
+///
+/// # Arguments
+/// * `lists` - The lists to merge. Every word is kept once, at its first occurrence.
+/// * `subtract` - Words to leave out, e.g. past answers.
+/// * `sort` - Whether to sort the result alphabetically instead of keeping the order of `lists`.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::wordlist::merge;
+/// use wordl_rust_bot::Word;
+///
+/// let lists = [["slimy", "crane"].map(Word::from_str), ["crane", "tears"].map(Word::from_str)];
+/// let merged = merge(lists.iter().map(|list| &list[..]), &[Word::from_str("tears")], true);
+/// assert_eq!(merged, ["crane", "slimy"].map(Word::from_str));
+/// ```
+pub fn merge<'a, I: IntoIterator<Item = &'a [Word]>>(lists: I, subtract: &[Word], sort: bool) -> Vec<Word> {
+    let mut seen = subtract.iter().copied().collect::<HashSet<Word>>();
+    let mut merged = lists.into_iter().flatten().copied().filter(|word| seen.insert(*word)).collect::<Vec<Word>>();
+    if sort {
+        merged.sort_unstable();
+    }
+    merged
+}
+
+/// Extracts the words with `length` letters from a dictionary file with one entry per line, such
+/// as `/usr/share/dict/words`, to make word lists for games with other word lengths.
+///
+/// Entries are [`normalize`]d first. Entries with anything but letters, e.g. `o'clock`, are left
+/// out, and every word is kept once, at its first occurrence. The words are returned as strings,
+/// because a [`Word`] always has [`crate::WORD_LENGTH`] letters.
+///
+/// # Arguments
+/// * `name` - The name of the file, used in error messages.
+/// * `reader` - The source of the file.
+/// * `length` - The number of letters of the words to extract.
+/// * `skip_capitalized` - Whether to leave out entries that start with an uppercase letter,
+///   which are mostly names in dictionary files.
+///
+/// # Errors
+/// If the file cannot be read.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::wordlist::filter_length;
+///
+/// let text = "Paris\ncat\ncrane\nCRANE\no'clock\nsix-ty\nslimy\n";
+/// assert_eq!(filter_length("words", text.as_bytes(), 5, true).unwrap(), ["crane", "slimy"]);
+/// assert_eq!(filter_length("words", text.as_bytes(), 5, false).unwrap(), ["paris", "crane", "slimy"]);
+/// ```
+pub fn filter_length<R: Read>(name: &str, mut reader: R, length: usize,
+                              skip_capitalized: bool) -> Result<Vec<String>, WordListError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)
+        .map_err(|source| WordListError::Io { name: name.to_string(), source })?;
+    let mut seen = HashSet::new();
+    let words = text.lines()
+        .map(str::trim)
+        .filter(|entry| !skip_capitalized || !entry.chars().next().is_some_and(char::is_uppercase))
+        .map(normalize)
+        .filter(|word| word.chars().count() == length && word.chars().all(|c| c.is_alphabetic() && !c.is_uppercase()))
+        .filter(|word| seen.insert(word.clone()))
+        .collect();
+    Ok(words)
+}
+
+/// A word list that keeps a weight for every word, read from lists that give the frequency of
+/// each word after a tab, e.g. `crane\t1350`.
+///
+/// The weights say how likely each word is to be the solution, in proportion to each other:
+/// common words make more likely answers than rare ones. Words without a frequency in the
+/// list get weight 1, so a list without any frequencies weighs all words the same.
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::wordlist::WeightedList;
+/// use wordl_rust_bot::Word;
+///
+/// let mut skipped = Vec::new();
+/// let list = WeightedList::read("list", "crane\t30\nslimy\t10\nxylyl\n".as_bytes(), false, &mut skipped).unwrap();
+/// assert_eq!(list.words().len(), 3);
+/// assert_eq!(list.weight(&Word::from_str("slimy")), Some(10.0));
+/// assert_eq!(list.probability(&Word::from_str("crane")), Some(30.0 / 41.0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WeightedList {
+    words: Vec<Word>,
+    weights: Vec<f64>,
+    ids: HashMap<Word, usize>,
+}
+
+impl WeightedList {
+    /// Reads a word list with an optional frequency after each word, skipping malformed lines
+    /// like [`crate::read_words_lenient`]. A word listed twice keeps its first weight.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the list, e.g. its file name, used in error messages.
+    /// * `reader` - The source of the list.
+    /// * `ignore_case` - Whether to convert the words to lowercase first, like
+    ///   [`crate::read_words_ignore_case`].
+    /// * `skipped` - Where the errors of skipped lines are added.
+    ///
+    /// # Errors
+    /// If the list cannot be read, or if it does not contain a single valid word.
+    pub fn read<R: Read>(name: &str, reader: R, ignore_case: bool,
+                         skipped: &mut Vec<WordListError>) -> Result<WeightedList, WordListError> {
+        let parse = match ignore_case {
+            true => Word::try_from_str_ignore_case,
+            false => Word::try_from_str,
+        };
+        let entries = read_entries(name, reader, parse, skipped)?;
+        Ok(WeightedList::new(entries.into_iter().map(|(word, frequency)| (word, frequency.unwrap_or(1.0)))))
+    }
+
+    /// The list of `words` with their weights. A word listed twice keeps its first weight.
+    pub fn new<I: IntoIterator<Item = (Word, f64)>>(words: I) -> WeightedList {
+        let mut list = WeightedList::default();
+        for (word, weight) in words {
+            if let Entry::Vacant(entry) = list.ids.entry(word) {
+                entry.insert(list.words.len());
+                list.words.push(word);
+                list.weights.push(weight);
+            }
+        }
+        list
+    }
+
+    /// The list of `words`, all with weight 1.
+    pub fn uniform(words: &[Word]) -> WeightedList {
+        WeightedList::new(words.iter().map(|word| (*word, 1.0)))
+    }
+
+    /// The words, in the order of the list.
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// The weights of the words, in the same order as [`WeightedList::words`].
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// The weight of `word`, or `None` if it is not in the list.
+    pub fn weight(&self, word: &Word) -> Option<f64> {
+        self.ids.get(word).map(|&id| self.weights[id])
+    }
+
+    /// The weight of `word` divided by the total weight of the list, or `None` if it is not
+    /// in the list.
+    pub fn probability(&self, word: &Word) -> Option<f64> {
+        let total = self.weights.iter().sum::<f64>();
+        self.weight(word).map(|weight| if total > 0.0 { weight / total } else { 0.0 })
+    }
+
+    /// Whether all words have the same weight.
+    pub fn is_uniform(&self) -> bool {
+        self.weights.windows(2).all(|pair| pair[0] == pair[1])
+    }
+
+    /// The words without their weights, in the order of the list.
+    pub fn into_words(self) -> Vec<Word> {
+        self.words
+    }
+}
+
+/// A schedule that sharpens the weights of the solutions toward common words as the game goes
+/// on, modelling that the answers are picked by editors, who favour familiar words: after
+/// `round` guesses, every weight is raised to the power `start + step * round`, but at most
+/// `max`. An exponent of 1 keeps the weights, larger ones favour common words more, and 0
+/// weighs all words the same. Only matters for lists with weights, see [`WeightedList`].
+///
+/// Written as `START,STEP,MAX`, e.g. `1,0.5,3` for [`Sharpening::DEFAULT`].
+///
+/// # Example
+///
+/// ```
+/// use wordl_rust_bot::wordlist::Sharpening;
+///
+/// let sharpening: Sharpening = "1,0.5,2".parse().unwrap();
+/// assert_eq!(sharpening.exponent(0), 1.0);
+/// assert_eq!(sharpening.exponent(1), 1.5);
+/// assert_eq!(sharpening.exponent(5), 2.0);
+/// assert_eq!(sharpening.sharpen(3.0, 2), 9.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sharpening {
+    pub start: f64,
+    pub step: f64,
+    pub max: f64,
+}
+
+impl Sharpening {
+    /// The schedule used unless set otherwise: the weights as they are for the first guess,
+    /// then squared by the third guess and cubed from the fifth on.
+    pub const DEFAULT: Sharpening = Sharpening { start: 1.0, step: 0.5, max: 3.0 };
+
+    /// The exponent the weights are raised to after `round` guesses.
+    pub fn exponent(&self, round: usize) -> f64 {
+        (self.start + self.step * round as f64).min(self.max)
+    }
+
+    /// `weight` sharpened for the round after `round` guesses.
+    pub fn sharpen(&self, weight: f64, round: usize) -> f64 {
+        weight.powf(self.exponent(round))
+    }
+}
+
+impl Display for Sharpening {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.start, self.step, self.max)
+    }
+}
+
+impl FromStr for Sharpening {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Sharpening, String> {
+        let numbers = value.split(',')
+            .map(|number| number.trim().parse::<f64>().ok().filter(|n| n.is_finite() && *n >= 0.0))
+            .collect::<Option<Vec<f64>>>();
+        match numbers.as_deref() {
+            Some(&[start, step, max]) => Ok(Sharpening { start, step, max }),
+            _ => Err(format!("<{}> is not a schedule of three non-negative numbers START,STEP,MAX", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let validation = validate("list", "crane\nSLIMY\nabcdef\ncrane\nslimy\n".as_bytes()).unwrap();
+        assert_eq!(validation.entries, 5);
+        assert_eq!(validation.words, vec![Word::from_str("crane"), Word::from_str("slimy")]);
+        assert_eq!(validation.issues, vec![
+            Issue::Case { line: 2, entry: "SLIMY".to_string(), word: Word::from_str("slimy") },
+            Issue::Invalid { line: 3, error: WordError::Length { word: "abcdef".to_string(), length: 6 } },
+            Issue::Duplicate { line: 4, word: Word::from_str("crane"), first: 1 },
+            Issue::Duplicate { line: 5, word: Word::from_str("slimy"), first: 2 },
+        ]);
+        assert!(!validation.is_clean());
+        assert!(validate("list", "crane\n\nslimy".as_bytes()).unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_merge() {
+        let first = ["tears", "crane", "tears"].map(Word::from_str);
+        let second = ["slimy", "bears"].map(Word::from_str);
+        let merged = merge([&first[..], &second[..]], &[Word::from_str("slimy")], false);
+        assert_eq!(merged, ["tears", "crane", "bears"].map(Word::from_str));
+        assert!(merge([&first[..]], &first, true).is_empty());
+    }
+
+    #[test]
+    fn test_weighted_list() {
+        let mut skipped = Vec::new();
+        let text = "crane\t2.5\nslimy\nTEARS\t4\ncrane\t9\nbears\t-1\ngears\tlots\n";
+        let list = WeightedList::read("list", text.as_bytes(), true, &mut skipped).unwrap();
+        assert_eq!(list.words(), ["crane", "slimy", "tears"].map(Word::from_str));
+        assert_eq!(list.weights(), [2.5, 1.0, 4.0]);
+        assert_eq!(skipped.len(), 2);
+        assert!(matches!(skipped[0], WordListError::Frequency { line: 5, .. }));
+        assert!(!list.is_uniform());
+        assert!(WeightedList::uniform(list.words()).is_uniform());
+    }
+
+    #[test]
+    fn test_sharpening() {
+        assert_eq!("1,0.5,3".parse(), Ok(Sharpening::DEFAULT));
+        assert_eq!(Sharpening::DEFAULT.to_string().parse(), Ok(Sharpening::DEFAULT));
+        assert!("1,0.5".parse::<Sharpening>().is_err());
+        assert!("1,-0.5,3".parse::<Sharpening>().is_err());
+        assert_eq!(Sharpening::DEFAULT.exponent(4), 3.0);
+        // An exponent of 0 weighs all words the same, also those with weight 0.
+        let flat = Sharpening { start: 0.0, step: 0.0, max: 0.0 };
+        assert_eq!([0.0, 1.0, 7.0].map(|weight| flat.sharpen(weight, 3)), [1.0; 3]);
+    }
+}